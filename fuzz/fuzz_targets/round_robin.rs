@@ -0,0 +1,34 @@
+//! Plays the workloads decoded from the fuzzer input on a round robin
+//! scheduler, the first two bytes are its timeslice and its minimum
+//! remaining timeslice.
+//!
+//! ```sh
+//! cargo +nightly fuzz run round_robin
+//! ```
+//!
+//! `cargo fuzz` builds with debug assertions, so an overflow panics.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scheduler::testing::{decode, play_to_end};
+use scheduler::{RoundRobinScheduler, SchedulingDecision};
+use std::num::NonZeroUsize;
+
+fuzz_target!(|data: &[u8]| {
+    let [timeslice, minimum_remaining, workload @ ..] = data else {
+        return;
+    };
+    let timeslice = NonZeroUsize::new(1 + *timeslice as usize % 16).unwrap();
+    let minimum_remaining = *minimum_remaining as usize % (timeslice.get() + 1);
+    let mut scheduler = RoundRobinScheduler::new(timeslice, minimum_remaining);
+
+    match play_to_end(&mut scheduler, &decode(workload)) {
+        // the processes finish unless they all wait for events
+        Ok(SchedulingDecision::Done | SchedulingDecision::Panic | SchedulingDecision::Deadlock) => {
+        }
+        Ok(decision) => panic!("the workload has ended with {}", decision),
+        Err(violation) => panic!("{}", violation),
+    }
+    assert_eq!(scheduler.validate(), Ok(()));
+});
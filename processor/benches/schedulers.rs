@@ -0,0 +1,150 @@
+//! Benchmarks of the hot paths of the schedulers, for every scheduler
+//! that [`make_scheduler`] builds.
+//!
+//! The workloads are played by the [`Simulator`], so the schedulers are
+//! called the way a simulation calls them. The whole simulation is
+//! measured, the throughput is the number of processes of the workload.
+//!
+//! Some schedulers take a time quadratic in the number of processes, the
+//! largest workloads take minutes with them. A filter runs only some of
+//! the benchmarks:
+//!
+//! ```sh
+//! cargo bench --bench schedulers -- next/round-robin
+//! ```
+
+use criterion::{
+    criterion_group, criterion_main, BenchmarkId, Criterion, SamplingMode, Throughput,
+};
+use processor::replay::{replay, ReplayOutcome};
+use processor::simulator::{Simulation, Simulator};
+use processor::workload::{Op, Workload};
+use scheduler::{make_scheduler, Scheduler, SchedulerConfig, SchedulerKind};
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+
+/// A sleep that lasts until every other process has finished.
+const FOREVER: usize = 1_000_000_000;
+
+/// A timeslice long enough for PID 1 to fork every process before
+/// any of them runs.
+const LONG: usize = 1_000_000;
+
+fn config(timeslice: usize) -> SchedulerConfig {
+    SchedulerConfig::new(NonZeroUsize::new(timeslice).unwrap(), 1)
+        .aging(NonZeroUsize::new(5).unwrap())
+}
+
+fn simulate(kind: SchedulerKind, timeslice: usize, workload: &Workload) -> Simulation {
+    Simulator::new(make_scheduler(kind, config(timeslice)), workload.clone())
+        .run()
+        .expect("The workloads only have valid operations")
+}
+
+fn fork(ops: Vec<Op>) -> Op {
+    Op::Fork { priority: 0, ops }
+}
+
+/// The process with PID 1 forks `processes` processes that do `ops`,
+/// then sleeps until they have all exited.
+fn forks(processes: usize, ops: Vec<Op>) -> Workload {
+    let mut init = vec![fork(ops); processes];
+    init.push(Op::Sleep(FOREVER));
+    Workload { ops: init }
+}
+
+/// A process that forks two processes that do the same, `depth` times.
+fn fork_bomb(depth: usize) -> Vec<Op> {
+    match depth {
+        0 => vec![],
+        _ => vec![fork(fork_bomb(depth - 1)), fork(fork_bomb(depth - 1))],
+    }
+}
+
+/// Measures the simulations of every workload, labelled with its number
+/// of processes, with every scheduler.
+fn bench_simulations(
+    c: &mut Criterion,
+    name: &str,
+    timeslice: usize,
+    workloads: &[(usize, Workload)],
+) {
+    let mut group = c.benchmark_group(name);
+    group.sampling_mode(SamplingMode::Flat).sample_size(10);
+    for kind in SchedulerKind::ALL {
+        for (size, workload) in workloads {
+            group.throughput(Throughput::Elements(*size as u64));
+            group.bench_with_input(
+                BenchmarkId::new(kind.name(), size),
+                workload,
+                |b, workload| b.iter_with_large_drop(|| simulate(kind, timeslice, workload)),
+            );
+        }
+    }
+    group.finish();
+}
+
+/// `next()` with many ready processes that yield.
+fn next(c: &mut Criterion) {
+    let workloads = [10, 1_000, 100_000]
+        .map(|processes| (processes, forks(processes, vec![Op::Yield, Op::Yield])));
+    bench_simulations(c, "next", LONG, &workloads);
+}
+
+/// `stop(Fork)`, every process forks two processes, up to 2047 processes.
+fn fork_loop(c: &mut Criterion) {
+    let mut ops = fork_bomb(10);
+    ops.push(Op::Sleep(FOREVER));
+    bench_simulations(c, "fork", 3, &[(2047, Workload { ops })]);
+}
+
+/// `stop(Exit)`, many ready processes that exit as soon as they run.
+fn mass_exit(c: &mut Criterion) {
+    let workloads = [1_000, 10_000].map(|processes| (processes, forks(processes, vec![])));
+    bench_simulations(c, "exit", LONG, &workloads);
+}
+
+/// The wake path, with processes that sleep for different times.
+fn sleepers(c: &mut Criterion) {
+    let workloads = [100, 1_000].map(|processes| {
+        let mut init = (0..processes)
+            .map(|process| {
+                let sleeps = (0..20).flat_map(|i| {
+                    let time = 1 + (process + i) % 7;
+                    [Op::Sleep(time), Op::Run(NonZeroUsize::MIN)]
+                });
+                fork(sleeps.collect())
+            })
+            .collect::<Vec<_>>();
+        init.push(Op::Sleep(FOREVER));
+        (processes, Workload { ops: init })
+    });
+    bench_simulations(c, "sleep", 3, &workloads);
+}
+
+/// `list()` on a large process table, made by replaying a simulation in
+/// which every process ends up waiting.
+fn list(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list");
+    for processes in [1_000, 10_000] {
+        let mut init = vec![fork(vec![Op::Wait(1)]); processes];
+        init.push(Op::Wait(2));
+        let workload = Workload { ops: init };
+        for kind in SchedulerKind::ALL {
+            let recording = simulate(kind, 3, &workload).recording;
+            let mut scheduler = make_scheduler(kind, config(3));
+            assert_eq!(
+                replay(scheduler.as_mut(), &recording),
+                ReplayOutcome::Identical
+            );
+            group.throughput(Throughput::Elements(processes as u64 + 1));
+            group.bench_function(BenchmarkId::new(kind.name(), processes), |b| {
+                b.iter(|| black_box(scheduler.list().len()))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, next, fork_loop, mass_exit, sleepers, list);
+criterion_main!(benches);
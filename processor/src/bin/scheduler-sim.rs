@@ -0,0 +1,15 @@
+use processor::cli::run_cli;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    match run_cli(std::env::args().skip(1)) {
+        Ok(output) => {
+            print!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
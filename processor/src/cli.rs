@@ -0,0 +1,416 @@
+//! The `scheduler-sim` command, which simulates a [`Workload`] file.
+//!
+//! ```text
+//! scheduler-sim run --scheduler round-robin --timeslice 5 --min-remaining 2 workload.txt
+//! scheduler-sim compare --scheduler round-robin --scheduler cfs --timeslice 5 workload.txt
+//! ```
+//!
+//! `run` prints the processes left at the end of the simulation and the
+//! lifetime of every process, and the timeline with `--trace`. `compare`
+//! prints the [`ComparisonReport`] of the schedulers. `--format` selects
+//! `table` (the default), `json` or `csv`. With the `tui` feature, `tui`
+//! plays the simulation in the terminal, see [`crate::tui`].
+
+use crate::compare::{self, ComparisonReport, Factory, Metric};
+use crate::simulator::{Simulation, SimulationError, Simulator};
+use crate::workload::{ParseError, Workload};
+use scheduler::{make_scheduler, SchedulerConfig, SchedulerKind};
+use std::fmt::{self, Display, Write};
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+const USAGE: &str = "\
+usage: scheduler-sim run --scheduler <kind> --timeslice <time> [options] <workload>
+       scheduler-sim compare --scheduler <kind>... --timeslice <time> [options] <workload>
+       scheduler-sim tui --scheduler <kind> --timeslice <time> [options] <workload>
+
+options:
+    --scheduler <kind>      round-robin, priority, cfs, fcfs, srtf or stride
+    --timeslice <time>      the timeslice of the scheduler
+    --min-remaining <time>  the least time left for a process to keep running, 0 by default
+    --aging <time>          the aging time of the priority scheduler
+    --trace                 print the timeline of the simulation, only for run
+    --format <format>       table, json or csv, table by default
+";
+
+/// The reason the command has failed.
+#[derive(Debug)]
+pub enum CliError {
+    /// The arguments are wrong, the message explains why.
+    Usage(String),
+
+    /// The workload file could not be read.
+    Read { path: String, message: String },
+
+    /// The workload file is malformed.
+    Parse { path: String, error: ParseError },
+
+    /// The workload could not be simulated.
+    Simulation(SimulationError),
+
+    /// The terminal could not be used to show the simulation.
+    Terminal(String),
+}
+
+impl CliError {
+    /// The exit code of the command, 2 for wrong arguments and 1 otherwise.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Usage(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{}\n\n{}", message, USAGE),
+            CliError::Read { path, message } => write!(f, "{}: {}", path, message),
+            CliError::Parse { path, error } => write!(f, "{}: {}", path, error),
+            CliError::Simulation(error) => write!(f, "{}", error),
+            CliError::Terminal(message) => write!(f, "terminal: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<SimulationError> for CliError {
+    fn from(error: SimulationError) -> Self {
+        CliError::Simulation(error)
+    }
+}
+
+/// How the results are printed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = CliError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(CliError::Usage(format!(
+                "unknown format `{}`, expected table, json or csv",
+                name
+            ))),
+        }
+    }
+}
+
+/// The arguments of a subcommand.
+struct Options {
+    schedulers: Vec<SchedulerKind>,
+    timeslice: Option<NonZeroUsize>,
+    minimum_remaining: usize,
+    aging: Option<NonZeroUsize>,
+    trace: bool,
+    format: Format,
+    workload: Option<String>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, CliError> {
+        let mut options = Options {
+            schedulers: vec![],
+            timeslice: None,
+            minimum_remaining: 0,
+            aging: None,
+            trace: false,
+            format: Format::Table,
+            workload: None,
+        };
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                if let Some(workload) = options.workload.replace(arg) {
+                    return Err(CliError::Usage(format!(
+                        "unexpected argument `{}`",
+                        workload
+                    )));
+                }
+                continue;
+            }
+            // both `--name value` and `--name=value` are accepted
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            if name == "--trace" {
+                match inline {
+                    Some(_) => {
+                        return Err(CliError::Usage(String::from("`--trace` takes no value")))
+                    }
+                    None => options.trace = true,
+                }
+                continue;
+            }
+            let value = inline
+                .or_else(|| args.next())
+                .ok_or_else(|| CliError::Usage(format!("`{}` needs a value", name)))?;
+            match name.as_str() {
+                "--scheduler" => options.schedulers.push(value.parse().map_err(
+                    |error: scheduler::UnknownSchedulerKind| CliError::Usage(error.to_string()),
+                )?),
+                "--timeslice" => options.timeslice = Some(number(&name, &value)?),
+                "--min-remaining" => options.minimum_remaining = number(&name, &value)?,
+                "--aging" => options.aging = Some(number(&name, &value)?),
+                "--format" => options.format = value.parse()?,
+                _ => return Err(CliError::Usage(format!("unknown option `{}`", name))),
+            }
+        }
+        Ok(options)
+    }
+
+    /// The only scheduler of `command`.
+    fn scheduler(&self, command: &str) -> Result<SchedulerKind, CliError> {
+        match self.schedulers[..] {
+            [kind] => Ok(kind),
+            [] => Err(CliError::Usage(String::from("`--scheduler` is required"))),
+            _ => Err(CliError::Usage(format!(
+                "`{}` takes one `--scheduler`, use `compare` for several",
+                command
+            ))),
+        }
+    }
+
+    fn config(&self) -> Result<SchedulerConfig, CliError> {
+        let timeslice = self
+            .timeslice
+            .ok_or_else(|| CliError::Usage(String::from("`--timeslice` is required")))?;
+        let config = SchedulerConfig::new(timeslice, self.minimum_remaining);
+        Ok(match self.aging {
+            Some(aging) => config.aging(aging),
+            None => config,
+        })
+    }
+
+    /// Reads and parses the workload file.
+    fn workload(&self) -> Result<Workload, CliError> {
+        let path = self
+            .workload
+            .as_ref()
+            .ok_or_else(|| CliError::Usage(String::from("the workload file is missing")))?;
+        let text = std::fs::read_to_string(path).map_err(|error| CliError::Read {
+            path: path.clone(),
+            message: error.to_string(),
+        })?;
+        Workload::parse(&text).map_err(|error| CliError::Parse {
+            path: path.clone(),
+            error,
+        })
+    }
+}
+
+fn number<T: FromStr>(name: &str, value: &str) -> Result<T, CliError> {
+    value
+        .parse()
+        .map_err(|_| CliError::Usage(format!("invalid value `{}` for `{}`", value, name)))
+}
+
+/// Runs the command with `args`, without the name of the program,
+/// and returns what it prints.
+pub fn run_cli<I>(args: I) -> Result<String, CliError>
+where
+    I: IntoIterator,
+    I::Item: Into<String>,
+{
+    let mut args = args.into_iter().map(Into::into);
+    match args.next().as_deref() {
+        Some("run") => run(Options::parse(args)?),
+        Some("compare") => compare(Options::parse(args)?),
+        Some("tui") => tui(Options::parse(args)?),
+        Some("help" | "--help" | "-h") => Ok(String::from(USAGE)),
+        Some(command) => Err(CliError::Usage(format!("unknown command `{}`", command))),
+        None => Err(CliError::Usage(String::from("a command is required"))),
+    }
+}
+
+fn run(options: Options) -> Result<String, CliError> {
+    let kind = options.scheduler("run")?;
+    let config = options.config()?;
+    let mut simulator = Simulator::new(make_scheduler(kind, config), options.workload()?);
+    if options.trace {
+        simulator = simulator.with_timeline();
+    }
+    let simulation = simulator.run()?;
+    Ok(match options.format {
+        Format::Table => simulation_table(&simulation),
+        Format::Json => simulation_json(&simulation),
+        Format::Csv => simulation_csv(&simulation),
+    })
+}
+
+fn compare(options: Options) -> Result<String, CliError> {
+    if options.schedulers.is_empty() {
+        return Err(CliError::Usage(String::from("`--scheduler` is required")));
+    }
+    if options.trace {
+        return Err(CliError::Usage(String::from("`--trace` is only for `run`")));
+    }
+    let config = options.config()?;
+    let schedulers = options
+        .schedulers
+        .iter()
+        .map(|&kind| {
+            let factory: Factory = Box::new(move || make_scheduler(kind, config));
+            (kind.to_string(), factory)
+        })
+        .collect();
+    let report = compare::compare(&options.workload()?, schedulers)?;
+    Ok(match options.format {
+        Format::Table => report.to_string(),
+        Format::Json => {
+            serde_json::to_string_pretty(&report).expect("A report is always serializable")
+        }
+        Format::Csv => report_csv(&report),
+    })
+}
+
+/// Plays the simulation in the terminal, it prints nothing once it is done.
+#[cfg(feature = "tui")]
+fn tui(options: Options) -> Result<String, CliError> {
+    let kind = options.scheduler("tui")?;
+    if options.trace || options.format != Format::Table {
+        return Err(CliError::Usage(String::from(
+            "`tui` takes no `--trace` or `--format`",
+        )));
+    }
+    let config = options.config()?;
+    let player = crate::tui::Player::simulate(make_scheduler(kind, config), options.workload()?)?;
+    crate::tui::run(player).map_err(|error| CliError::Terminal(error.to_string()))?;
+    Ok(String::new())
+}
+
+#[cfg(not(feature = "tui"))]
+fn tui(_options: Options) -> Result<String, CliError> {
+    Err(CliError::Usage(String::from(
+        "`tui` needs scheduler-sim to be built with the tui feature",
+    )))
+}
+
+fn simulation_table(simulation: &Simulation) -> String {
+    let mut s = String::new();
+    if let Some(outcome) = simulation.decisions.last() {
+        writeln!(s, "{} after {} time units", outcome, simulation.elapsed).unwrap();
+    }
+
+    writeln!(s, "\nPID\tSTATE\t\tPRI\tTOTAL\tSYSCALL\tEXECUTE\tEXTRA").unwrap();
+    for process in simulation.processes.iter() {
+        let (total, syscalls, execution) = process.timings;
+        writeln!(
+            s,
+            "{}\t{}\t\t{}\t{}\t{}\t{}\t{}",
+            process.pid, process.state, process.priority, total, syscalls, execution, process.extra
+        )
+        .unwrap();
+    }
+
+    writeln!(s, "\nPID\tCREATED\tEXITED\tRUNNING\tTURNAROUND\tWAITING").unwrap();
+    for lifetime in simulation.lifetimes.iter() {
+        writeln!(
+            s,
+            "{}\t{}\t{}\t{}\t{}\t\t{}",
+            lifetime.pid,
+            lifetime.created,
+            optional(lifetime.exited),
+            lifetime.running,
+            optional(lifetime.turnaround()),
+            optional(lifetime.waiting())
+        )
+        .unwrap();
+    }
+
+    if let Some(timeline) = simulation.timeline.as_ref() {
+        write!(s, "\n{}", timeline).unwrap();
+    }
+    s
+}
+
+/// Formats a value that a process that has not exited does not have.
+fn optional(value: Option<usize>) -> String {
+    value.map_or_else(|| String::from("-"), |value| value.to_string())
+}
+
+fn simulation_json(simulation: &Simulation) -> String {
+    let lifetimes = simulation
+        .lifetimes
+        .iter()
+        .map(|lifetime| {
+            serde_json::json!({
+                "pid": lifetime.pid,
+                "created": lifetime.created,
+                "exited": lifetime.exited,
+                "running": lifetime.running,
+                "turnaround": lifetime.turnaround(),
+                "waiting": lifetime.waiting(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut json = serde_json::json!({
+        "outcome": simulation.decisions.last(),
+        "elapsed": simulation.elapsed,
+        "processes": simulation.processes,
+        "lifetimes": lifetimes,
+    });
+    if let Some(timeline) = simulation.timeline.as_ref() {
+        json["timeline"] = serde_json::json!(timeline);
+    }
+    serde_json::to_string_pretty(&json).expect("A simulation is always serializable")
+}
+
+/// Writes the processes, the lifetimes and the timeline as CSV tables,
+/// separated by empty lines.
+fn simulation_csv(simulation: &Simulation) -> String {
+    let mut csv = vec![];
+    scheduler::export::write_snapshots_csv(&simulation.processes, &mut csv)
+        .expect("Writing to a vector does not fail");
+    let mut s = String::from_utf8(csv).expect("The CSV is valid UTF-8");
+
+    writeln!(s, "\npid,created,exited,running,turnaround,waiting").unwrap();
+    for lifetime in simulation.lifetimes.iter() {
+        let field = |value: Option<usize>| value.map(|value| value.to_string()).unwrap_or_default();
+        writeln!(
+            s,
+            "{},{},{},{},{},{}",
+            lifetime.pid,
+            lifetime.created,
+            field(lifetime.exited),
+            lifetime.running,
+            field(lifetime.turnaround()),
+            field(lifetime.waiting())
+        )
+        .unwrap();
+    }
+
+    if let Some(timeline) = simulation.timeline.as_ref() {
+        writeln!(s, "\nstart,end,activity").unwrap();
+        for segment in timeline.segments() {
+            writeln!(s, "{},{},{}", segment.start, segment.end, segment.what).unwrap();
+        }
+    }
+    s
+}
+
+fn report_csv(report: &ComparisonReport) -> String {
+    let mut s = String::from("scheduler");
+    for metric in Metric::ALL {
+        write!(s, ",{}", metric.header()).unwrap();
+    }
+    writeln!(s).unwrap();
+    for (name, metrics) in report.entries.iter() {
+        write!(s, "{}", name).unwrap();
+        for metric in Metric::ALL {
+            write!(s, ",{}", metrics.get(metric)).unwrap();
+        }
+        writeln!(s).unwrap();
+    }
+    s
+}
@@ -0,0 +1,194 @@
+//! Runs the same [`Workload`] against several schedulers and compares them.
+
+use crate::simulator::{Simulation, SimulationError, Simulator};
+use crate::workload::Workload;
+use scheduler::{Scheduler, SchedulingDecision};
+use std::fmt::{self, Display};
+
+/// Creates a new scheduler for every simulation.
+pub type Factory = Box<dyn Fn() -> Box<dyn Scheduler> + Send + Sync>;
+
+/// A measure of how well a scheduler has run a workload,
+/// lower values are better for all of them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Metric {
+    /// The mean time from the creation of a process to its exit.
+    MeanTurnaround,
+
+    /// The mean time during which a process has existed without running.
+    MeanWaiting,
+
+    /// The longest time during which a process has existed without running.
+    MaxWaiting,
+
+    /// How many times the processor has been given to another process.
+    ContextSwitches,
+
+    /// The time that the whole workload has taken.
+    Makespan,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 5] = [
+        Metric::MeanTurnaround,
+        Metric::MeanWaiting,
+        Metric::MaxWaiting,
+        Metric::ContextSwitches,
+        Metric::Makespan,
+    ];
+
+    /// The header of the column of the metric in the table.
+    pub fn header(&self) -> &'static str {
+        match self {
+            Metric::MeanTurnaround => "turnaround",
+            Metric::MeanWaiting => "waiting",
+            Metric::MaxWaiting => "max waiting",
+            Metric::ContextSwitches => "switches",
+            Metric::Makespan => "makespan",
+        }
+    }
+}
+
+/// The metrics of one scheduler. Only the processes that have
+/// exited are taken into account for the turnaround and waiting times.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metrics {
+    pub mean_turnaround: f64,
+    pub mean_waiting: f64,
+    pub max_waiting: usize,
+    pub context_switches: usize,
+    pub makespan: usize,
+}
+
+impl Metrics {
+    pub fn new(simulation: &Simulation) -> Self {
+        let turnarounds = simulation
+            .lifetimes
+            .iter()
+            .filter_map(|lifetime| lifetime.turnaround())
+            .collect::<Vec<_>>();
+        let waitings = simulation
+            .lifetimes
+            .iter()
+            .filter_map(|lifetime| lifetime.waiting())
+            .collect::<Vec<_>>();
+
+        let mut context_switches = 0;
+        let mut last = None;
+        for decision in simulation.decisions.iter() {
+            if let SchedulingDecision::Run { pid, .. } = decision {
+                if last.is_some_and(|last| last != *pid) {
+                    context_switches += 1;
+                }
+                last = Some(*pid);
+            }
+        }
+
+        Metrics {
+            mean_turnaround: mean(&turnarounds),
+            mean_waiting: mean(&waitings),
+            max_waiting: waitings.iter().copied().max().unwrap_or(0),
+            context_switches,
+            makespan: simulation.elapsed,
+        }
+    }
+
+    pub fn get(&self, metric: Metric) -> f64 {
+        match metric {
+            Metric::MeanTurnaround => self.mean_turnaround,
+            Metric::MeanWaiting => self.mean_waiting,
+            Metric::MaxWaiting => self.max_waiting as f64,
+            Metric::ContextSwitches => self.context_switches as f64,
+            Metric::Makespan => self.makespan as f64,
+        }
+    }
+}
+
+fn mean(values: &[usize]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<usize>() as f64 / values.len() as f64
+    }
+}
+
+/// The metrics of every compared scheduler, in the order they were given.
+///
+/// The plain text rendering is a table with one line for every scheduler,
+/// where the best value of every metric is marked with `*`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ComparisonReport {
+    pub entries: Vec<(String, Metrics)>,
+}
+
+impl ComparisonReport {
+    /// The name of the scheduler with the best value of `metric`,
+    /// the first one if several have the same value.
+    pub fn winner(&self, metric: Metric) -> Option<&str> {
+        let best = self.best(metric)?;
+        self.entries
+            .iter()
+            .find(|(_, metrics)| metrics.get(metric) == best)
+            .map(|(name, _)| name.as_str())
+    }
+
+    fn best(&self, metric: Metric) -> Option<f64> {
+        self.entries
+            .iter()
+            .map(|(_, metrics)| metrics.get(metric))
+            .min_by(f64::total_cmp)
+    }
+}
+
+impl Display for ComparisonReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .entries
+            .iter()
+            .map(|(name, _)| name.len())
+            .chain(Some("scheduler".len()))
+            .max()
+            .unwrap_or(0);
+
+        write!(f, "{:<width$}", "scheduler")?;
+        for metric in Metric::ALL {
+            write!(f, "  {:>12}", metric.header())?;
+        }
+        writeln!(f)?;
+
+        for (name, metrics) in self.entries.iter() {
+            write!(f, "{:<width$}", name)?;
+            for metric in Metric::ALL {
+                let value = match metric {
+                    Metric::MeanTurnaround | Metric::MeanWaiting => {
+                        format!("{:.2}", metrics.get(metric))
+                    }
+                    _ => format!("{}", metrics.get(metric)),
+                };
+                let mark = if self.best(metric) == Some(metrics.get(metric)) {
+                    "*"
+                } else {
+                    " "
+                };
+                write!(f, "  {:>11}{}", value, mark)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Simulates `workload` with a new scheduler from every factory.
+pub fn compare(
+    workload: &Workload,
+    schedulers: Vec<(String, Factory)>,
+) -> Result<ComparisonReport, SimulationError> {
+    let mut entries = vec![];
+    for (name, factory) in schedulers {
+        let simulation = Simulator::new(factory(), workload.clone()).run()?;
+        entries.push((name, Metrics::new(&simulation)));
+    }
+    Ok(ComparisonReport { entries })
+}
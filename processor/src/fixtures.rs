@@ -0,0 +1,235 @@
+//! Fixtures that keep what a scheduler has done with a workload, to find
+//! out whether a later version of the scheduler still does the same.
+//!
+//! A fixture is a JSON file with the version of its format, the scheduler
+//! and the workload that have been simulated, the calls made to the
+//! scheduler and the metrics of the simulation. [`record`] writes one and
+//! [`check`] simulates it again with the current schedulers.
+
+use crate::compare::{Metric, Metrics};
+use crate::replay::{replay, Divergence, Recording, ReplayOutcome};
+use crate::simulator::{SimulationError, Simulator};
+use crate::workload::Workload;
+use scheduler::{make_scheduler, Scheduler, SchedulerConfig, SchedulerKind};
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::Path;
+
+/// The version of the format of the fixtures, raised whenever a change
+/// to the format makes the older fixtures unreadable.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A scheduler that can be built again, see [`make_scheduler`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SchedulerDescriptor {
+    pub kind: SchedulerKind,
+    pub config: SchedulerConfig,
+}
+
+impl SchedulerDescriptor {
+    pub fn new(kind: SchedulerKind, config: SchedulerConfig) -> Self {
+        SchedulerDescriptor { kind, config }
+    }
+
+    /// Builds a new scheduler.
+    pub fn build(&self) -> Box<dyn Scheduler> {
+        make_scheduler(self.kind, self.config)
+    }
+}
+
+/// What a scheduler has done with a workload.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Fixture {
+    /// The version of the format, [`SCHEMA_VERSION`] when it has been written.
+    pub version: u32,
+    pub scheduler: SchedulerDescriptor,
+    pub workload: Workload,
+    /// Every call made to the scheduler during the simulation.
+    pub recording: Recording,
+    pub metrics: Metrics,
+}
+
+impl Fixture {
+    /// Simulates `workload` with a new scheduler of `scheduler`.
+    pub fn simulate(
+        workload: &Workload,
+        scheduler: SchedulerDescriptor,
+    ) -> Result<Fixture, FixtureError> {
+        let simulation = Simulator::new(scheduler.build(), workload.clone()).run()?;
+        Ok(Fixture {
+            version: SCHEMA_VERSION,
+            scheduler,
+            workload: workload.clone(),
+            metrics: Metrics::new(&simulation),
+            recording: simulation.recording,
+        })
+    }
+
+    /// Reads the fixture stored in `path`, which has to be written in
+    /// the current version of the format.
+    pub fn read(path: impl AsRef<Path>) -> Result<Fixture, FixtureError> {
+        let text = fs::read_to_string(path)?;
+        let value: serde_json::Value = serde_json::from_str(&text)?;
+        // the version is checked first, the rest of an older fixture
+        // might not be readable anymore
+        let version = value.get("version").and_then(|version| version.as_u64());
+        if version != Some(SCHEMA_VERSION as u64) {
+            return Err(FixtureError::Version(version));
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Writes the fixture to `path`.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), FixtureError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// The reason a fixture could not be recorded or checked.
+#[derive(Debug)]
+pub enum FixtureError {
+    /// The fixture file could not be read or written.
+    Io(std::io::Error),
+
+    /// The fixture file is not a valid fixture.
+    Format(serde_json::Error),
+
+    /// The fixture file has been written in another version of the
+    /// format, or has no version.
+    Version(Option<u64>),
+
+    /// The workload could not be simulated.
+    Simulation(SimulationError),
+}
+
+impl Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixtureError::Io(error) => write!(f, "{}", error),
+            FixtureError::Format(error) => write!(f, "invalid fixture: {}", error),
+            FixtureError::Version(Some(version)) => write!(
+                f,
+                "the fixture has version {}, expected {}",
+                version, SCHEMA_VERSION
+            ),
+            FixtureError::Version(None) => write!(f, "the fixture has no version"),
+            FixtureError::Simulation(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for FixtureError {}
+
+impl From<std::io::Error> for FixtureError {
+    fn from(error: std::io::Error) -> Self {
+        FixtureError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for FixtureError {
+    fn from(error: serde_json::Error) -> Self {
+        FixtureError::Format(error)
+    }
+}
+
+impl From<SimulationError> for FixtureError {
+    fn from(error: SimulationError) -> Self {
+        FixtureError::Simulation(error)
+    }
+}
+
+/// A metric that has another value than in the fixture.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MetricDelta {
+    pub metric: Metric,
+    pub recorded: f64,
+    pub current: f64,
+}
+
+impl MetricDelta {
+    /// How much the metric has grown, negative if it has improved.
+    pub fn delta(&self) -> f64 {
+        self.current - self.recorded
+    }
+}
+
+/// How the current scheduler differs from a fixture.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixtureDiff {
+    /// The first call to which the scheduler has answered differently,
+    /// [`None`] if it has answered every call like in the fixture.
+    pub divergence: Option<Divergence>,
+
+    /// The metrics that have changed, in the order of [`Metric::ALL`].
+    pub metrics: Vec<MetricDelta>,
+}
+
+impl FixtureDiff {
+    /// Whether the scheduler behaves exactly like in the fixture.
+    pub fn is_clean(&self) -> bool {
+        self.divergence.is_none() && self.metrics.is_empty()
+    }
+}
+
+impl Display for FixtureDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_clean() {
+            return writeln!(f, "no difference");
+        }
+        if let Some(divergence) = self.divergence {
+            writeln!(f, "{}", divergence)?;
+        }
+        for delta in self.metrics.iter() {
+            writeln!(
+                f,
+                "{}: {} -> {} ({:+})",
+                delta.metric.header(),
+                delta.recorded,
+                delta.current,
+                delta.delta()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Simulates `workload` with a new scheduler of `scheduler` and writes
+/// the fixture of the simulation to `path`.
+pub fn record(
+    workload: &Workload,
+    scheduler: SchedulerDescriptor,
+    path: impl AsRef<Path>,
+) -> Result<Fixture, FixtureError> {
+    let fixture = Fixture::simulate(workload, scheduler)?;
+    fixture.write(path)?;
+    Ok(fixture)
+}
+
+/// Simulates the fixture stored in `path` again and reports how the
+/// scheduler differs from the fixture.
+///
+/// The calls of the fixture are replayed against a new scheduler up to
+/// the first answer that differs, and the metrics of a new simulation of
+/// the workload are compared with the recorded ones.
+pub fn check(path: impl AsRef<Path>) -> Result<FixtureDiff, FixtureError> {
+    let fixture = Fixture::read(path)?;
+    let divergence = match replay(&mut *fixture.scheduler.build(), &fixture.recording) {
+        ReplayOutcome::Identical => None,
+        ReplayOutcome::Diverged(divergence) => Some(divergence),
+    };
+    let current = Fixture::simulate(&fixture.workload, fixture.scheduler)?;
+    let metrics = Metric::ALL
+        .into_iter()
+        .map(|metric| MetricDelta {
+            metric,
+            recorded: fixture.metrics.get(metric),
+            current: current.metrics.get(metric),
+        })
+        .filter(|delta| delta.recorded != delta.current)
+        .collect();
+    Ok(FixtureDiff {
+        divergence,
+        metrics,
+    })
+}
@@ -8,6 +8,17 @@ use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
 use std::{mem, thread};
 
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod compare;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+pub mod replay;
+pub mod simulator;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod workload;
+
 use scheduler::{
     Pid, ProcessState, Scheduler, SchedulingDecision, StopReason, Syscall, SyscallResult,
 };
@@ -132,7 +143,7 @@ impl<S: Scheduler + 'static> Processor<S> {
     ///
     /// * `scheduler` - the scheduler to use for the simulation.
     /// * `f` - a function with the instructions for the process with
-    ///         PID 1.
+    ///   PID 1.
     ///
     /// ## Example
     ///
@@ -374,6 +385,23 @@ impl<S: Scheduler + 'static> Process<S> {
         self.suspend();
     }
 
+    /// Send a [`Syscall::Yield`] system call.
+    pub fn yield_now(&self) {
+        println!("{}: YIELD", self.pid);
+        self.processor.scheduler(StopReason::syscall(Syscall::Yield));
+        self.suspend();
+    }
+
+    /// Send a [`Syscall::SetPriority`] system call.
+    ///
+    /// * `priority` - the new priority of the process.
+    pub fn set_priority(&self, priority: i8) {
+        println!("{}: SET PRIORITY {}", self.pid, priority);
+        self.processor
+            .scheduler(StopReason::syscall(Syscall::SetPriority(priority)));
+        self.suspend();
+    }
+
     fn exit(&self) {
         println!("{}: EXIT", self.pid);
         self.processor.scheduler(StopReason::syscall(Syscall::Exit));
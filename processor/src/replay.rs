@@ -0,0 +1,170 @@
+//! Recordings of what a scheduler has been asked and what it has answered,
+//! to check that another run of a scheduler takes the same decisions.
+
+use scheduler::{Scheduler, SchedulingDecision, StopReason, SyscallResult};
+use std::fmt::{self, Display};
+
+/// A call to the scheduler and its answer.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Step {
+    /// A call to [`Scheduler::next`].
+    Next(SchedulingDecision),
+
+    /// A call to [`Scheduler::stop`].
+    Stop {
+        reason: StopReason,
+        result: SyscallResult,
+    },
+}
+
+/// Every call made to a scheduler, in order, with its answer.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Recording {
+    pub steps: Vec<Step>,
+}
+
+impl Recording {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls [`Scheduler::next`] and records the decision.
+    pub fn next(&mut self, scheduler: &mut dyn Scheduler) -> SchedulingDecision {
+        let decision = scheduler.next();
+        self.steps.push(Step::Next(decision));
+        decision
+    }
+
+    /// Calls [`Scheduler::stop`] and records the result.
+    pub fn stop(&mut self, scheduler: &mut dyn Scheduler, reason: StopReason) -> SyscallResult {
+        let result = scheduler.stop(reason);
+        self.steps.push(Step::Stop { reason, result });
+        result
+    }
+}
+
+/// How the answer of a scheduler differs from the recorded one.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Difference {
+    /// The scheduler has chosen to run another process.
+    Pid {
+        expected: SchedulingDecision,
+        got: SchedulingDecision,
+    },
+
+    /// The scheduler has chosen the same process, with another timeslice.
+    Timeslice {
+        expected: SchedulingDecision,
+        got: SchedulingDecision,
+    },
+
+    /// The scheduler has taken another kind of decision.
+    Decision {
+        expected: SchedulingDecision,
+        got: SchedulingDecision,
+    },
+
+    /// A system call has had another result.
+    Result {
+        expected: SyscallResult,
+        got: SyscallResult,
+    },
+}
+
+impl Difference {
+    fn new(expected: SchedulingDecision, got: SchedulingDecision) -> Self {
+        match (expected, got) {
+            (
+                SchedulingDecision::Run {
+                    pid: expected_pid, ..
+                },
+                SchedulingDecision::Run { pid, .. },
+            ) if expected_pid == pid => Difference::Timeslice { expected, got },
+            (SchedulingDecision::Run { .. }, SchedulingDecision::Run { .. }) => {
+                Difference::Pid { expected, got }
+            }
+            _ => Difference::Decision { expected, got },
+        }
+    }
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::Pid { expected, got } => {
+                write!(f, "another process, expected `{}`, got `{}`", expected, got)
+            }
+            Difference::Timeslice { expected, got } => {
+                write!(
+                    f,
+                    "another timeslice, expected `{}`, got `{}`",
+                    expected, got
+                )
+            }
+            Difference::Decision { expected, got } => {
+                write!(
+                    f,
+                    "another decision, expected `{}`, got `{}`",
+                    expected, got
+                )
+            }
+            Difference::Result { expected, got } => {
+                write!(f, "another result, expected {:?}, got {:?}", expected, got)
+            }
+        }
+    }
+}
+
+/// The first step at which a scheduler has answered differently from a recording.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Divergence {
+    /// The index of the step in the recording.
+    pub index: usize,
+    pub difference: Difference,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "step {}: {}", self.index, self.difference)
+    }
+}
+
+/// The result of a [`replay`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ReplayOutcome {
+    /// The scheduler has answered every call like in the recording.
+    Identical,
+
+    /// The scheduler has answered differently, the replay has stopped there.
+    Diverged(Divergence),
+}
+
+/// Makes the calls of `recording` to `scheduler` and compares its answers
+/// with the recorded ones, up to the first one that differs.
+pub fn replay(scheduler: &mut dyn Scheduler, recording: &Recording) -> ReplayOutcome {
+    for (index, step) in recording.steps.iter().enumerate() {
+        let difference = match *step {
+            Step::Next(expected) => {
+                let got = scheduler.next();
+                (got != expected).then(|| Difference::new(expected, got))
+            }
+            Step::Stop { reason, result } => {
+                let got = scheduler.stop(reason);
+                (got != result).then_some(Difference::Result {
+                    expected: result,
+                    got,
+                })
+            }
+        };
+        if let Some(difference) = difference {
+            return ReplayOutcome::Diverged(Divergence { index, difference });
+        }
+    }
+    ReplayOutcome::Identical
+}
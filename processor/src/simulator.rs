@@ -0,0 +1,270 @@
+//! Runs a [`Workload`] against a scheduler, without threads.
+
+use crate::replay::Recording;
+use crate::workload::{Op, Workload};
+use scheduler::{
+    Activity, Pid, ProcessSnapshot, Scheduler, SchedulingDecision, StopReason, Syscall,
+    SyscallResult, Timeline,
+};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::{self, Display};
+
+/// The reason a workload could not be simulated.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SimulationError {
+    /// The process has operations left after its `exit`.
+    ActsAfterExit(Pid),
+
+    /// The scheduler has dispatched a process that does not exist.
+    UnknownProcess(Pid),
+}
+
+impl Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulationError::ActsAfterExit(pid) => {
+                write!(f, "process {} has operations after its exit", pid)
+            }
+            SimulationError::UnknownProcess(pid) => {
+                write!(
+                    f,
+                    "the scheduler has dispatched process {}, which does not exist",
+                    pid
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+/// When a process of a simulation has existed and how long it has run.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Lifetime {
+    pub pid: Pid,
+
+    /// The time at which the fork that has created the process has ended.
+    pub created: usize,
+
+    /// The time at which the exit of the process has ended, or [`None`]
+    /// if the process has not exited.
+    pub exited: Option<usize>,
+
+    /// The time during which the process has run, including its system calls.
+    pub running: usize,
+}
+
+impl Lifetime {
+    fn new(pid: Pid, created: usize) -> Self {
+        Lifetime {
+            pid,
+            created,
+            exited: None,
+            running: 0,
+        }
+    }
+
+    /// The time from the creation of the process to its exit.
+    pub fn turnaround(&self) -> Option<usize> {
+        self.exited.map(|exited| exited - self.created)
+    }
+
+    /// The time from the creation of the process to its exit during
+    /// which the process has not run.
+    pub fn waiting(&self) -> Option<usize> {
+        self.turnaround()
+            .map(|turnaround| turnaround.saturating_sub(self.running))
+    }
+}
+
+/// What has happened during a simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    /// Every decision of the scheduler, the last one is
+    /// [`SchedulingDecision::Done`], [`SchedulingDecision::Panic`]
+    /// or [`SchedulingDecision::Deadlock`].
+    pub decisions: Vec<SchedulingDecision>,
+
+    /// The processes left when the simulation has ended.
+    pub processes: Vec<ProcessSnapshot>,
+
+    /// The time the simulation has taken.
+    pub elapsed: usize,
+
+    /// Every process that has been created, in the order of their PIDs.
+    pub lifetimes: Vec<Lifetime>,
+
+    /// The timeline of the simulation, if it has been recorded.
+    pub timeline: Option<Timeline>,
+
+    /// Every call made to the scheduler, to replay the simulation.
+    pub recording: Recording,
+}
+
+/// A simulation of a [`Workload`] on one processor.
+///
+/// Unlike the [`Processor`], which runs every process in a thread, the
+/// simulator plays the operations of the processes one after the other.
+/// Every system call takes one time unit, like for the [`Processor`].
+///
+/// [`Processor`]: crate::Processor
+pub struct Simulator {
+    scheduler: Box<dyn Scheduler>,
+    workload: Workload,
+    timeline: Option<Timeline>,
+}
+
+impl Simulator {
+    pub fn new(scheduler: Box<dyn Scheduler>, workload: Workload) -> Self {
+        Simulator {
+            scheduler,
+            workload,
+            timeline: None,
+        }
+    }
+
+    /// Records the timeline of the simulation.
+    pub fn with_timeline(mut self) -> Self {
+        self.timeline = Some(Timeline::new());
+        self
+    }
+
+    /// Runs the workload until the scheduler is done, panics or
+    /// finds a deadlock.
+    pub fn run(mut self) -> Result<Simulation, SimulationError> {
+        let mut scripts = HashMap::new();
+        let mut decisions = vec![];
+        let mut elapsed = 0;
+        let mut lifetimes = BTreeMap::new();
+        let mut recording = Recording::new();
+
+        let init = recording.stop(
+            self.scheduler.as_mut(),
+            StopReason::syscall(Syscall::Fork(0)),
+        );
+        if let SyscallResult::Pid(pid) = init {
+            scripts.insert(pid, Script::new(self.workload.ops.clone()));
+            lifetimes.insert(pid, Lifetime::new(pid, 0));
+        }
+        loop {
+            let decision = recording.next(self.scheduler.as_mut());
+            decisions.push(decision);
+            match decision {
+                SchedulingDecision::Run { pid, timeslice } => {
+                    let script = scripts
+                        .get_mut(&pid)
+                        .ok_or(SimulationError::UnknownProcess(pid))?;
+                    let (reason, consumed) = script.play(timeslice.get());
+                    let forked = script.forked.take();
+                    let acts_after_exit = !script.ops.is_empty();
+                    elapsed += consumed;
+                    if let Some(lifetime) = lifetimes.get_mut(&pid) {
+                        lifetime.running += consumed;
+                    }
+                    if let Some(timeline) = self.timeline.as_mut() {
+                        timeline.push(Activity::Running(pid), consumed);
+                    }
+                    let exited = matches!(
+                        reason,
+                        StopReason::Syscall {
+                            syscall: Syscall::Exit,
+                            ..
+                        }
+                    );
+                    let result = recording.stop(self.scheduler.as_mut(), reason);
+                    if exited {
+                        if acts_after_exit {
+                            return Err(SimulationError::ActsAfterExit(pid));
+                        }
+                        scripts.remove(&pid);
+                        if let Some(lifetime) = lifetimes.get_mut(&pid) {
+                            lifetime.exited = Some(elapsed);
+                        }
+                    } else if let SyscallResult::Pid(child) = result {
+                        scripts.insert(child, Script::new(forked.unwrap_or_default()));
+                        lifetimes.insert(child, Lifetime::new(child, elapsed));
+                    }
+                }
+                SchedulingDecision::Sleep(amount) => {
+                    elapsed += amount.get();
+                    if let Some(timeline) = self.timeline.as_mut() {
+                        timeline.push(Activity::Sleeping, amount.get());
+                    }
+                }
+                SchedulingDecision::Deadlock
+                | SchedulingDecision::Panic
+                | SchedulingDecision::Done => break,
+            }
+        }
+
+        Ok(Simulation {
+            decisions,
+            processes: ProcessSnapshot::all(self.scheduler.as_mut()),
+            elapsed,
+            lifetimes: lifetimes.into_values().collect(),
+            timeline: self.timeline,
+            recording,
+        })
+    }
+}
+
+/// The operations that a process has left.
+struct Script {
+    ops: VecDeque<Op>,
+    /// The time left from the `run` operation that the process has started.
+    running: usize,
+    /// The operations of the process created by the last fork.
+    forked: Option<Vec<Op>>,
+}
+
+impl Script {
+    fn new(ops: Vec<Op>) -> Self {
+        Script {
+            ops: ops.into(),
+            running: 0,
+            forked: None,
+        }
+    }
+
+    /// Plays the operations of the process for at most `timeslice` time
+    /// units. Returns why the process has stopped and the time it has used.
+    fn play(&mut self, timeslice: usize) -> (StopReason, usize) {
+        let mut remaining = timeslice;
+        loop {
+            if self.running == 0 {
+                if let Some(Op::Run(time)) = self.ops.front() {
+                    self.running = time.get();
+                    self.ops.pop_front();
+                }
+            }
+            if self.running > 0 {
+                let time = self.running.min(remaining);
+                self.running -= time;
+                remaining -= time;
+                if remaining == 0 {
+                    return (StopReason::Expired, timeslice);
+                }
+                continue;
+            }
+            // a process without operations left exits
+            let syscall = match self.ops.pop_front().unwrap_or(Op::Exit) {
+                Op::Run(_) => unreachable!("run operations are handled above"),
+                Op::Fork { priority, ops } => {
+                    self.forked = Some(ops);
+                    Syscall::Fork(priority)
+                }
+                Op::Sleep(time) => Syscall::Sleep(time),
+                Op::Wait(event) => Syscall::Wait(event),
+                Op::Signal(event) => Syscall::Signal(event),
+                Op::Yield => Syscall::Yield,
+                Op::Exit => Syscall::Exit,
+            };
+            // the system call takes one time unit
+            remaining -= 1;
+            return (
+                StopReason::Syscall { syscall, remaining },
+                timeslice - remaining,
+            );
+        }
+    }
+}
@@ -0,0 +1,378 @@
+//! The `tui` command of `scheduler-sim`, which plays a simulation in the
+//! terminal, one dispatch at a time.
+//!
+//! The simulation is run first, with an [`ObservedScheduler`] that records
+//! what the scheduler does, then the recorded events are played back into
+//! a [`ViewModel`]. Only the events are used, so any scheduler can be shown.
+//! The rendering functions only read the [`ViewModel`], they can be checked
+//! on a [`Buffer`] without a terminal.
+
+use crate::simulator::{SimulationError, Simulator};
+use crate::workload::Workload;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Widget};
+use scheduler::{
+    ObservedEvent, ObservedScheduler, Pid, ProcessState, Scheduler, SchedulingDecision, StopReason,
+    VecObserver,
+};
+use std::io;
+
+/// What is known about a process from the events played so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessView {
+    pub pid: Pid,
+
+    /// The state of the process, [`None`] once it has exited.
+    pub state: Option<ProcessState>,
+
+    /// The time at which the process has been created.
+    pub created: usize,
+
+    /// The time during which the process has run, including its system calls.
+    pub running: usize,
+
+    /// The number of system calls of the process.
+    pub syscalls: usize,
+}
+
+/// What the terminal shows, built from the events of the scheduler.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ViewModel {
+    /// The time of the last event.
+    pub time: usize,
+
+    /// The process that has been dispatched last, until it stops running.
+    pub running: Option<Pid>,
+
+    /// The ready processes, in the order in which they have become ready.
+    pub ready: Vec<Pid>,
+
+    /// The sleeping processes, in the order in which they have fallen asleep.
+    pub sleeping: Vec<Pid>,
+
+    /// The processes waiting for an event and the event,
+    /// in the order in which they have started to wait.
+    pub waiting: Vec<(Pid, usize)>,
+
+    /// Every process that has been created, sorted by PID.
+    pub processes: Vec<ProcessView>,
+
+    /// The intervals during which processes have run, as `(pid, start, end)`.
+    pub gantt: Vec<(Pid, usize, usize)>,
+
+    /// The number of dispatches played so far.
+    pub dispatches: usize,
+
+    /// The last decision of the scheduler, once every event has been played.
+    pub outcome: Option<SchedulingDecision>,
+
+    /// The time of the last dispatch or stop, since which the running process runs.
+    since: usize,
+}
+
+impl ViewModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the view with an event of an [`ObservedScheduler`].
+    pub fn apply(&mut self, event: &ObservedEvent) {
+        match *event {
+            ObservedEvent::Dispatch { pid, now, .. } => {
+                self.time = now;
+                self.since = now;
+                self.running = Some(pid);
+                self.dispatches += 1;
+            }
+            ObservedEvent::Stop { pid, reason, now } => {
+                if let Some(pid) = pid {
+                    let ran = now.saturating_sub(self.since);
+                    if ran > 0 {
+                        match self.gantt.last_mut() {
+                            Some((last, _, end)) if *last == pid && *end == self.since => {
+                                *end = now
+                            }
+                            _ => self.gantt.push((pid, self.since, now)),
+                        }
+                    }
+                    if let Some(process) = self.process_mut(pid) {
+                        process.running += ran;
+                        if matches!(reason, StopReason::Syscall { .. }) {
+                            process.syscalls += 1;
+                        }
+                    }
+                }
+                self.time = now;
+                self.since = now;
+            }
+            ObservedEvent::Fork { child, .. } => {
+                let position = self.processes.partition_point(|p| p.pid < child);
+                self.processes.insert(
+                    position,
+                    ProcessView {
+                        pid: child,
+                        state: Some(ProcessState::Ready),
+                        created: self.time,
+                        running: 0,
+                        syscalls: 0,
+                    },
+                );
+                self.enqueue(child, ProcessState::Ready);
+            }
+            ObservedEvent::Exit { pid } => {
+                self.dequeue(pid);
+                if let Some(process) = self.process_mut(pid) {
+                    process.state = None;
+                }
+            }
+            ObservedEvent::StateChange { pid, new, .. } => {
+                self.dequeue(pid);
+                self.enqueue(pid, new);
+                if let Some(process) = self.process_mut(pid) {
+                    process.state = Some(new);
+                }
+            }
+        }
+    }
+
+    fn process_mut(&mut self, pid: Pid) -> Option<&mut ProcessView> {
+        self.processes.iter_mut().find(|p| p.pid == pid)
+    }
+
+    /// Removes a process from the queues.
+    fn dequeue(&mut self, pid: Pid) {
+        self.ready.retain(|&p| p != pid);
+        self.sleeping.retain(|&p| p != pid);
+        self.waiting.retain(|&(p, _)| p != pid);
+        if self.running == Some(pid) {
+            self.running = None;
+        }
+    }
+
+    /// Adds a process to the queue of `state`.
+    fn enqueue(&mut self, pid: Pid, state: ProcessState) {
+        match state {
+            ProcessState::Ready => self.ready.push(pid),
+            ProcessState::Running => self.running = Some(pid),
+            ProcessState::Waiting { event: None } => self.sleeping.push(pid),
+            ProcessState::Waiting { event: Some(event) } => self.waiting.push((pid, event)),
+        }
+    }
+}
+
+/// Plays the recorded events of a simulation into a [`ViewModel`],
+/// one dispatch at a time.
+pub struct Player {
+    events: Vec<ObservedEvent>,
+    played: usize,
+    outcome: Option<SchedulingDecision>,
+    view: ViewModel,
+}
+
+impl Player {
+    /// Creates a player that has played no event yet. `outcome` is the
+    /// last decision of the scheduler, shown once every event is played.
+    pub fn new(events: Vec<ObservedEvent>, outcome: Option<SchedulingDecision>) -> Self {
+        Player {
+            events,
+            played: 0,
+            outcome,
+            view: ViewModel::new(),
+        }
+    }
+
+    /// Simulates `workload` with `scheduler` and records its events.
+    pub fn simulate(
+        scheduler: Box<dyn Scheduler>,
+        workload: Workload,
+    ) -> Result<Player, SimulationError> {
+        let observer = VecObserver::new();
+        let mut observed = ObservedScheduler::new(scheduler);
+        observed.add_observer(Box::new(observer.clone()));
+        let simulation = Simulator::new(Box::new(observed), workload).run()?;
+        Ok(Player::new(
+            observer.events(),
+            simulation.decisions.last().copied(),
+        ))
+    }
+
+    pub fn view(&self) -> &ViewModel {
+        &self.view
+    }
+
+    /// Whether every event has been played.
+    pub fn is_done(&self) -> bool {
+        self.played == self.events.len()
+    }
+
+    /// Plays the events up to the next dispatch, which is the last one
+    /// played, with what the previous process has done meanwhile.
+    pub fn step(&mut self) {
+        while let Some(event) = self.events.get(self.played) {
+            self.view.apply(event);
+            self.played += 1;
+            if matches!(event, ObservedEvent::Dispatch { .. }) {
+                return;
+            }
+        }
+        self.view.outcome = self.outcome;
+    }
+
+    /// Plays every event left.
+    pub fn run_to_end(&mut self) {
+        while self.view.outcome.is_none() && !self.is_done() {
+            self.step();
+        }
+        self.view.outcome = self.outcome;
+    }
+}
+
+/// Formats a list of PIDs, or `-` if it is empty.
+fn pids(pids: impl Iterator<Item = String>) -> String {
+    let pids = pids.collect::<Vec<_>>();
+    if pids.is_empty() {
+        String::from("-")
+    } else {
+        pids.join(" ")
+    }
+}
+
+/// The lines of the queues panel.
+pub fn queue_lines(view: &ViewModel) -> Vec<String> {
+    vec![
+        format!(
+            "running   {}",
+            pids(view.running.iter().map(Pid::to_string))
+        ),
+        format!("ready     {}", pids(view.ready.iter().map(Pid::to_string))),
+        format!(
+            "sleeping  {}",
+            pids(view.sleeping.iter().map(Pid::to_string))
+        ),
+        format!(
+            "waiting   {}",
+            pids(
+                view.waiting
+                    .iter()
+                    .map(|(pid, event)| format!("{}(event {})", pid, event))
+            )
+        ),
+    ]
+}
+
+/// The last `width` time units up to the time of the view, one character
+/// for every time unit: the PID of the process that has run in base 36,
+/// or `.` if no process has run.
+pub fn gantt_strip(view: &ViewModel, width: usize) -> String {
+    let start = view.time.saturating_sub(width);
+    (start..view.time)
+        .map(|time| {
+            view.gantt
+                .iter()
+                .find(|&&(_, start, end)| start <= time && time < end)
+                .and_then(|&(pid, ..)| char::from_digit((pid.get() % 36) as u32, 36))
+                .unwrap_or('.')
+        })
+        .collect()
+}
+
+/// A bar of `width` characters, filled in proportion to `value` out of `total`.
+fn bar(value: usize, total: usize, width: usize) -> String {
+    let filled = (value * width).checked_div(total).unwrap_or(0).min(width);
+    format!("{}{}", "#".repeat(filled), "-".repeat(width - filled))
+}
+
+/// One line for every process, with its state and a bar of the time it
+/// has run out of the time it has existed.
+pub fn timing_lines(view: &ViewModel, bar_width: usize) -> Vec<String> {
+    view.processes
+        .iter()
+        .map(|process| {
+            let state = process
+                .state
+                .map_or_else(|| String::from("EXITED"), |state| state.to_string());
+            let existed = view.time.saturating_sub(process.created);
+            format!(
+                "{:>4} {:<9} [{}] {}/{} syscalls {}",
+                process.pid.get(),
+                state,
+                bar(process.running, existed, bar_width),
+                process.running,
+                existed,
+                process.syscalls
+            )
+        })
+        .collect()
+}
+
+/// The status line, with the time, the number of dispatches and the
+/// outcome once the simulation has ended.
+pub fn status_line(view: &ViewModel) -> String {
+    let status = format!("time {}  dispatches {}", view.time, view.dispatches);
+    match view.outcome {
+        Some(outcome) => format!("{}  {}", status, outcome),
+        None => status,
+    }
+}
+
+const KEYS: &str = "space step  r run to the end  q quit";
+
+/// Draws the whole view in `area` of `buf`.
+pub fn render(view: &ViewModel, area: Rect, buf: &mut Buffer) {
+    let [status, panels, gantt, keys] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(6),
+        Constraint::Length(3),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+    let [queues, timings] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(panels);
+
+    Paragraph::new(status_line(view))
+        .block(Block::bordered().title(" scheduler-sim "))
+        .render(status, buf);
+    Paragraph::new(lines(queue_lines(view)))
+        .block(Block::bordered().title(" queues "))
+        .render(queues, buf);
+    // the bar takes what is left after the other columns and the borders
+    let bar_width = (timings.width as usize).saturating_sub(40).max(5);
+    Paragraph::new(lines(timing_lines(view, bar_width)))
+        .block(Block::bordered().title(" processes "))
+        .render(timings, buf);
+    Paragraph::new(gantt_strip(view, gantt.width.saturating_sub(2) as usize))
+        .block(Block::bordered().title(" gantt "))
+        .render(gantt, buf);
+    Paragraph::new(KEYS).render(keys, buf);
+}
+
+fn lines(lines: Vec<String>) -> Vec<Line<'static>> {
+    lines.into_iter().map(Line::from).collect()
+}
+
+/// Shows the player in the terminal until `q` is pressed.
+pub fn run(mut player: Player) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = (|| loop {
+        terminal.draw(|frame| {
+            let area = frame.area();
+            render(player.view(), area, frame.buffer_mut())
+        })?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char(' ') => player.step(),
+                KeyCode::Char('r') => player.run_to_end(),
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                _ => {}
+            }
+        }
+    })();
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,250 @@
+//! A text description of what processes do, used to drive schedulers.
+//!
+//! Every line is an operation of a process, the operations that are not
+//! indented are done by the process with PID 1. The operations of a forked
+//! process follow its `fork` line, indented deeper than it. Empty lines
+//! and lines starting with `#` are ignored.
+//!
+//! ```text
+//! run 3
+//! fork prio=2
+//!     run 2
+//!     wait 1
+//! sleep 10
+//! signal 1
+//! exit
+//! ```
+//!
+//! The operations are:
+//!
+//! * `run <time>` - execute for `time` time units
+//! * `fork [prio=<priority>]` - create a process, with priority 0 by default
+//! * `sleep <time>` - issue a [`Syscall::Sleep`]
+//! * `wait <event>` - issue a [`Syscall::Wait`]
+//! * `signal <event>` - issue a [`Syscall::Signal`]
+//! * `yield` - issue a [`Syscall::Yield`]
+//! * `exit` - issue a [`Syscall::Exit`]
+//!
+//! [`Syscall::Sleep`]: scheduler::Syscall::Sleep
+//! [`Syscall::Wait`]: scheduler::Syscall::Wait
+//! [`Syscall::Signal`]: scheduler::Syscall::Signal
+//! [`Syscall::Yield`]: scheduler::Syscall::Yield
+//! [`Syscall::Exit`]: scheduler::Syscall::Exit
+
+use std::fmt::{self, Display};
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+/// An operation of a process.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Op {
+    /// Execute for an amount of time.
+    Run(NonZeroUsize),
+
+    /// Create a process.
+    Fork {
+        /// The priority of the new process.
+        priority: i8,
+
+        /// The operations of the new process.
+        ops: Vec<Op>,
+    },
+
+    /// Sleep for an amount of time.
+    Sleep(usize),
+
+    /// Wait for an event.
+    Wait(usize),
+
+    /// Signal an event.
+    Signal(usize),
+
+    /// Give up the processor.
+    Yield,
+
+    /// Finish the process.
+    Exit,
+}
+
+/// What the processes of a simulation do, starting with the process with PID 1.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Workload {
+    /// The operations of the process with PID 1.
+    pub ops: Vec<Op>,
+}
+
+impl Workload {
+    /// Parses the text description of a workload.
+    pub fn parse(text: &str) -> Result<Workload, ParseError> {
+        let lines = text
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| Line::new(index + 1, line))
+            .collect::<Vec<_>>();
+        let ops = parse_block(&lines, &mut 0, 0)?;
+        Ok(Workload { ops })
+    }
+}
+
+impl FromStr for Workload {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Workload::parse(text)
+    }
+}
+
+/// Why a line of a workload could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    /// The operation does not exist.
+    UnknownOp(String),
+
+    /// The operation needs an argument that is missing.
+    MissingArgument,
+
+    /// The operation has an argument that it does not take.
+    UnexpectedArgument(String),
+
+    /// An argument is not a valid number for the operation.
+    InvalidNumber(String),
+
+    /// The line is indented, but it does not follow a `fork`.
+    UnexpectedIndent,
+}
+
+/// An error in the text description of a workload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// The number of the line with the error, starting from 1.
+    pub line: usize,
+
+    /// What is wrong with the line.
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    fn new(line: usize, kind: ParseErrorKind) -> Self {
+        ParseError { line, kind }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ParseErrorKind::UnknownOp(op) => write!(f, "unknown operation `{}`", op),
+            ParseErrorKind::MissingArgument => write!(f, "missing argument"),
+            ParseErrorKind::UnexpectedArgument(arg) => write!(f, "unexpected argument `{}`", arg),
+            ParseErrorKind::InvalidNumber(arg) => write!(f, "invalid number `{}`", arg),
+            ParseErrorKind::UnexpectedIndent => write!(f, "unexpected indentation"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A line with an operation.
+struct Line<'a> {
+    number: usize,
+    indent: usize,
+    words: Vec<&'a str>,
+}
+
+impl<'a> Line<'a> {
+    /// Splits a line in words, or returns [`None`] if it is empty or a comment.
+    fn new(number: usize, text: &'a str) -> Option<Self> {
+        let trimmed = text.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            return None;
+        }
+        Some(Line {
+            number,
+            indent: text.len() - trimmed.len(),
+            words: trimmed.split_whitespace().collect(),
+        })
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(self.number, kind)
+    }
+
+    /// Parses the only argument of the operation.
+    fn argument<T: FromStr>(&self) -> Result<T, ParseError> {
+        match self.words[1..] {
+            [] => Err(self.error(ParseErrorKind::MissingArgument)),
+            [arg] => arg
+                .parse()
+                .map_err(|_| self.error(ParseErrorKind::InvalidNumber(arg.to_string()))),
+            [_, extra, ..] => {
+                Err(self.error(ParseErrorKind::UnexpectedArgument(extra.to_string())))
+            }
+        }
+    }
+
+    /// Checks that the operation has no arguments.
+    fn no_argument(&self) -> Result<(), ParseError> {
+        match self.words.get(1) {
+            Some(arg) => Err(self.error(ParseErrorKind::UnexpectedArgument(arg.to_string()))),
+            None => Ok(()),
+        }
+    }
+
+    /// Parses the optional `prio=<priority>` argument of a fork.
+    fn priority(&self) -> Result<i8, ParseError> {
+        match self.words[1..] {
+            [] => Ok(0),
+            [arg] => {
+                let Some(priority) = arg.strip_prefix("prio=") else {
+                    return Err(self.error(ParseErrorKind::UnexpectedArgument(arg.to_string())));
+                };
+                priority
+                    .parse()
+                    .map_err(|_| self.error(ParseErrorKind::InvalidNumber(priority.to_string())))
+            }
+            [_, extra, ..] => {
+                Err(self.error(ParseErrorKind::UnexpectedArgument(extra.to_string())))
+            }
+        }
+    }
+}
+
+/// Parses the operations indented by `indent`, starting from `position`,
+/// and moves `position` after them.
+fn parse_block(lines: &[Line], position: &mut usize, indent: usize) -> Result<Vec<Op>, ParseError> {
+    let mut ops = vec![];
+    while let Some(line) = lines.get(*position) {
+        if line.indent < indent {
+            break;
+        }
+        // a deeper line that does not follow a fork, or that is indented
+        // between a block and the enclosing one, belongs to no block
+        if line.indent > indent {
+            return Err(line.error(ParseErrorKind::UnexpectedIndent));
+        }
+        *position += 1;
+        let op = match line.words[0] {
+            "run" => Op::Run(line.argument()?),
+            "fork" => {
+                let priority = line.priority()?;
+                let ops = match lines.get(*position) {
+                    Some(child) if child.indent > indent => {
+                        parse_block(lines, position, child.indent)?
+                    }
+                    _ => vec![],
+                };
+                Op::Fork { priority, ops }
+            }
+            "sleep" => Op::Sleep(line.argument()?),
+            "wait" => Op::Wait(line.argument()?),
+            "signal" => Op::Signal(line.argument()?),
+            "yield" => line.no_argument().map(|_| Op::Yield)?,
+            "exit" => line.no_argument().map(|_| Op::Exit)?,
+            op => return Err(line.error(ParseErrorKind::UnknownOp(op.to_string()))),
+        };
+        ops.push(op);
+    }
+    Ok(ops)
+}
@@ -0,0 +1,138 @@
+use scheduler::{
+    make_scheduler, AccountingClock, ChargeKind, Pid, Process, ProcessControlBlock, ProcessState,
+    SchedulerConfig, SchedulerKind, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+/// A running process with PID 1, a ready one, a sleeping one and one
+/// waiting for an event.
+fn processes() -> [ProcessControlBlock; 4] {
+    let states = [
+        ProcessState::Running,
+        ProcessState::Ready,
+        ProcessState::Waiting { event: None },
+        ProcessState::Waiting { event: Some(1) },
+    ];
+    let mut pid = 0;
+    states.map(|state| {
+        pid += 1;
+        ProcessControlBlock::new(Pid::new(pid), state, 0, (10, 2, 3))
+    })
+}
+
+fn timings(processes: &[ProcessControlBlock]) -> Vec<(usize, usize, usize)> {
+    processes.iter().map(|p| p.timings()).collect()
+}
+
+#[test]
+pub fn charge_execution() {
+    let mut clock = AccountingClock::new();
+    let mut processes = processes();
+    clock.charge(Pid::new(1), 5, ChargeKind::Execution, &mut processes);
+    assert_eq!(
+        timings(&processes),
+        [(15, 2, 8), (15, 2, 3), (15, 2, 3), (15, 2, 3)]
+    );
+    assert_eq!(
+        (clock.time(), clock.busy_time(), clock.idle_time()),
+        (5, 5, 0)
+    );
+}
+
+#[test]
+pub fn charge_syscall() {
+    let mut clock = AccountingClock::new();
+    let mut processes = processes();
+    clock.charge(Pid::new(1), 5, ChargeKind::Syscall, &mut processes);
+    assert_eq!(
+        timings(&processes),
+        [(15, 3, 7), (15, 2, 3), (15, 2, 3), (15, 2, 3)]
+    );
+    assert_eq!(
+        (clock.time(), clock.busy_time(), clock.idle_time()),
+        (5, 5, 0)
+    );
+
+    // a system call issued right away still counts
+    clock.charge(Pid::new(1), 0, ChargeKind::Syscall, &mut processes);
+    assert_eq!(processes[0].timings(), (15, 4, 7));
+    assert_eq!(clock.time(), 5);
+}
+
+#[test]
+pub fn charge_without_the_others() {
+    let mut clock = AccountingClock::new();
+    let mut processes = processes();
+    let (running, _) = processes.split_first_mut().unwrap();
+    clock.charge(Pid::new(1), 4, ChargeKind::Execution, [running]);
+    assert_eq!(processes[0].timings(), (14, 2, 7));
+    assert_eq!(timings(&processes[1..]), [(10, 2, 3); 3]);
+    assert_eq!(clock.time(), 4);
+
+    // the runner may be left out as well
+    let (_, others) = processes.split_first_mut().unwrap();
+    clock.charge(Pid::new(1), 2, ChargeKind::Syscall, others);
+    assert_eq!(processes[0].timings(), (14, 2, 7));
+    assert_eq!(timings(&processes[1..]), [(12, 2, 3); 3]);
+    assert_eq!(clock.time(), 6);
+}
+
+#[test]
+pub fn sleep() {
+    let mut clock = AccountingClock::new();
+    let mut processes = processes();
+    clock.charge(Pid::new(1), 3, ChargeKind::Syscall, &mut processes);
+    let [_, ready, sleeping, waiting] = &mut processes;
+    clock.advance_sleep(4, [ready, sleeping, waiting]);
+
+    // the sleep is charged as existing time only, whatever the state
+    assert_eq!(
+        timings(&processes),
+        [(13, 3, 5), (17, 2, 3), (17, 2, 3), (17, 2, 3)]
+    );
+    assert_eq!(processes[2].state(), ProcessState::Waiting { event: None });
+    assert_eq!(
+        (clock.time(), clock.busy_time(), clock.idle_time()),
+        (7, 3, 4)
+    );
+}
+
+#[test]
+pub fn saturates() {
+    let mut clock = AccountingClock::new();
+    let mut processes = processes();
+    clock.charge(
+        Pid::new(1),
+        usize::MAX - 1,
+        ChargeKind::Execution,
+        &mut processes,
+    );
+    clock.advance_sleep(5, &mut processes);
+    clock.charge(Pid::new(2), 5, ChargeKind::Syscall, &mut processes);
+    assert_eq!(clock.time(), usize::MAX);
+    assert_eq!(clock.busy_time(), usize::MAX);
+    assert_eq!(clock.idle_time(), 5);
+    assert_eq!(processes[0].timings(), (usize::MAX, 2, usize::MAX));
+    assert_eq!(processes[1].timings(), (usize::MAX, 3, 7));
+}
+
+#[test]
+pub fn more_left_than_granted() {
+    for kind in SchedulerKind::ALL {
+        let config = SchedulerConfig::new(NonZeroUsize::new(5).unwrap(), 1);
+        let mut scheduler = make_scheduler(kind, config);
+        scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+        let (_, timeslice) = dispatch(&mut scheduler);
+
+        // the process is left with no more than its timeslice
+        syscall(&mut scheduler, Syscall::SignalGroup(0, 1), usize::MAX);
+        scheduler.stop(StopReason::Expired);
+        assert_eq!(
+            scheduler.list()[0].timings(),
+            (timeslice, 1, timeslice),
+            "{kind}"
+        );
+    }
+}
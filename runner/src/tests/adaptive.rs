@@ -0,0 +1,56 @@
+use scheduler::{Pid, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason, Syscall};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn timeslice_shrinks_with_load() {
+    let mut scheduler = RoundRobinScheduler::new_adaptive(
+        NonZeroUsize::new(12).unwrap(),
+        NonZeroUsize::new(3).unwrap(),
+    );
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    // every process forks one more and uses the rest of its timeslice
+    let mut granted = vec![];
+    for _ in 0..5 {
+        let (pid, timeslice) = dispatch(&mut scheduler);
+        granted.push((pid, timeslice));
+        syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+        scheduler.stop(StopReason::Expired);
+    }
+    assert_eq!(
+        granted,
+        vec![
+            (Pid::new(1), 12),
+            (Pid::new(2), 6),
+            (Pid::new(1), 4),
+            (Pid::new(3), 3),
+            (Pid::new(2), 3),
+        ]
+    );
+}
+
+#[test]
+pub fn remaining_timeslice_shrinks() {
+    let mut scheduler = RoundRobinScheduler::new_adaptive(
+        NonZeroUsize::new(10).unwrap(),
+        NonZeroUsize::new(2).unwrap(),
+    );
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 8);
+    // process 1 has 8 time units left, but only gets half of the timeslice
+    let peeked = scheduler.peek_next();
+    assert_eq!(
+        peeked,
+        SchedulingDecision::Run {
+            pid: Pid::new(1),
+            timeslice: NonZeroUsize::new(5).unwrap(),
+        }
+    );
+    assert_eq!(scheduler.next(), peeked);
+    syscall(&mut scheduler, Syscall::Yield, 3);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 5));
+}
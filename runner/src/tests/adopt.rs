@@ -0,0 +1,117 @@
+use scheduler::{
+    AdoptSpec, AdoptState, FailReason, Pid, RoundRobinScheduler, Scheduler,
+    SchedulingDecision, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+fn sleeping(pid: Option<Pid>, until: usize) -> AdoptSpec {
+    AdoptSpec {
+        pid,
+        priority: 0,
+        state: AdoptState::Sleeping { until },
+        timings: (10, 2, 4),
+        group: 0,
+    }
+}
+
+#[test]
+pub fn adopt_sleeping_process() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    assert_eq!(scheduler.adopt(sleeping(Some(Pid::new(5)), 6)), Ok(Pid::new(5)));
+
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, 1);
+    syscall(&mut scheduler, Syscall::Sleep(20), timeslice - 1);
+
+    // pid 1 has used one time unit, pid 5 wakes up at 6
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(5).unwrap())
+    );
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(5));
+
+    let list = scheduler.list();
+    let adopted = list.iter().find(|p| p.pid() == 5).unwrap();
+    assert_eq!(adopted.timings(), (16, 2, 4));
+}
+
+#[test]
+pub fn adopt_allocates_pid() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    scheduler.adopt(sleeping(Some(Pid::new(5)), 6)).unwrap();
+
+    assert_eq!(scheduler.adopt(sleeping(None, 6)), Ok(Pid::new(6)));
+}
+
+#[test]
+pub fn adopt_duplicate_pid() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    scheduler.adopt(sleeping(Some(Pid::new(5)), 6)).unwrap();
+
+    assert_eq!(
+        scheduler.adopt(sleeping(Some(Pid::new(5)), 6)),
+        Err(FailReason::PidInUse(Pid::new(5)))
+    );
+    assert_eq!(
+        scheduler.adopt(sleeping(Some(Pid::new(1)), 6)),
+        Err(FailReason::PidInUse(Pid::new(1)))
+    );
+    assert_eq!(scheduler.list().len(), 2);
+}
+
+#[test]
+pub fn adopt_past_the_limit() {
+    let mut scheduler =
+        RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1).max_processes(2);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    scheduler.adopt(sleeping(None, 6)).unwrap();
+
+    assert_eq!(
+        scheduler.adopt(sleeping(Some(Pid::new(7)), 6)),
+        Err(FailReason::MaxProcessesReached)
+    );
+    assert_eq!(scheduler.list().len(), 2);
+}
+
+#[test]
+pub fn adopt_without_pid_left() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    scheduler.adopt(sleeping(Some(Pid::new(usize::MAX)), 6)).unwrap();
+
+    assert_eq!(scheduler.adopt(sleeping(None, 6)), Err(FailReason::PidOverflow));
+    assert_eq!(scheduler.list().len(), 2);
+}
+
+#[test]
+pub fn list_sorted_by_pid() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 9);
+    syscall(&mut scheduler, Syscall::Fork(0), 8);
+    syscall(&mut scheduler, Syscall::Kill(Pid::new(2)), 7);
+    scheduler
+        .adopt(AdoptSpec {
+            pid: Some(Pid::new(2)),
+            priority: 0,
+            state: AdoptState::Ready,
+            timings: (0, 0, 0),
+            group: 0,
+        })
+        .unwrap();
+    syscall(&mut scheduler, Syscall::Fork(0), 6);
+
+    let pids = scheduler
+        .list()
+        .iter()
+        .map(|p| p.pid().get())
+        .collect::<Vec<_>>();
+    assert_eq!(pids, vec![1, 2, 3, 4]);
+}
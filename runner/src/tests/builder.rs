@@ -0,0 +1,147 @@
+use scheduler::{
+    BuildError, Pid, Scheduler, SchedulerBuilder, SchedulerEvent, SchedulerKind,
+    SchedulingDecision, StopReason, Syscall, SyscallResult,
+};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use super::syscall;
+
+#[test]
+pub fn round_robin_options() {
+    let events = Arc::new(Mutex::new(vec![]));
+    let trace = events.clone();
+    let mut scheduler = SchedulerBuilder::new()
+        .timeslice(5)
+        .minimum_remaining(2)
+        .max_processes(1)
+        .trace(Box::new(move |event| trace.lock().unwrap().push(*event)))
+        .build_round_robin()
+        .unwrap();
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Run {
+            pid: Pid::new(1),
+            timeslice: NonZeroUsize::new(5).unwrap(),
+        }
+    );
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Fork(0), 4),
+        SyscallResult::MaxProcessesReached
+    );
+    assert_eq!(
+        events.lock().unwrap()[..],
+        [
+            SchedulerEvent::Fork {
+                pid: Pid::new(1),
+                time: 0
+            },
+            SchedulerEvent::Dispatch {
+                pid: Pid::new(1),
+                time: 0
+            },
+        ]
+    );
+}
+
+#[test]
+pub fn every_kind() {
+    for kind in SchedulerKind::ALL {
+        let mut scheduler = SchedulerBuilder::new().timeslice(3).build(kind).unwrap();
+        scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+        assert!(
+            matches!(scheduler.next(), SchedulingDecision::Run { pid, .. } if pid == 1),
+            "{}",
+            kind
+        );
+    }
+}
+
+#[test]
+pub fn missing_timeslice() {
+    assert_eq!(
+        SchedulerBuilder::new().build_cfs().err(),
+        Some(BuildError::MissingTimeslice)
+    );
+}
+
+#[test]
+pub fn zero_timeslice() {
+    assert_eq!(
+        SchedulerBuilder::new().timeslice(0).build_fcfs().err(),
+        Some(BuildError::ZeroTimeslice)
+    );
+}
+
+#[test]
+pub fn zero_aging() {
+    assert_eq!(
+        SchedulerBuilder::new()
+            .timeslice(3)
+            .aging(0)
+            .build_priority()
+            .err(),
+        Some(BuildError::ZeroAging)
+    );
+}
+
+#[test]
+pub fn zero_cores() {
+    assert_eq!(
+        SchedulerBuilder::new()
+            .timeslice(3)
+            .cores(0)
+            .build_round_robin()
+            .err(),
+        Some(BuildError::ZeroCores)
+    );
+}
+
+#[test]
+pub fn cores_without_next_on() {
+    let builder = || SchedulerBuilder::new().timeslice(3).cores(2);
+    assert_eq!(
+        builder().build(SchedulerKind::RoundRobin).err(),
+        Some(BuildError::MultipleCores)
+    );
+    assert!(builder().build_round_robin().is_ok());
+    assert!(SchedulerBuilder::new()
+        .timeslice(3)
+        .cores(1)
+        .build(SchedulerKind::RoundRobin)
+        .is_ok());
+}
+
+#[test]
+pub fn unsupported_option() {
+    let error = SchedulerBuilder::new()
+        .timeslice(3)
+        .kill_orphans(true)
+        .build(SchedulerKind::Srtf)
+        .err();
+    assert_eq!(
+        error,
+        Some(BuildError::Unsupported {
+            option: "kill_orphans",
+            kind: SchedulerKind::Srtf,
+        })
+    );
+    assert_eq!(
+        error.unwrap().to_string(),
+        "the srtf scheduler has no `kill_orphans` option"
+    );
+
+    assert_eq!(
+        SchedulerBuilder::new()
+            .timeslice(3)
+            .aging(2)
+            .build_round_robin()
+            .err(),
+        Some(BuildError::Unsupported {
+            option: "aging",
+            kind: SchedulerKind::RoundRobin,
+        })
+    );
+}
@@ -0,0 +1,102 @@
+use scheduler::{
+    AdoptSpec, AdoptState, FailReason, Pid, RoundRobinScheduler, Scheduler, StopReason, Syscall,
+    SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+/// Three processes with bursts of 2, 6 and 3 time units.
+fn mixed() -> RoundRobinScheduler {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(4).unwrap(), 1);
+    for burst in [2, 6, 3] {
+        scheduler.add_process(0, NonZeroUsize::new(burst).unwrap()).unwrap();
+    }
+    scheduler
+}
+
+#[test]
+pub fn every_process_runs_its_burst() {
+    let mut scheduler = mixed();
+    for _ in 0..2 {
+        assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+        scheduler.stop(StopReason::Expired);
+        assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 6));
+        scheduler.stop(StopReason::Expired);
+        assert_eq!(dispatch(&mut scheduler), (Pid::new(3), 3));
+        scheduler.stop(StopReason::Expired);
+    }
+    let timings = scheduler
+        .list()
+        .iter()
+        .map(|p| p.timings())
+        .collect::<Vec<_>>();
+    assert_eq!(timings, vec![(22, 0, 4), (22, 0, 12), (22, 0, 6)]);
+}
+
+#[test]
+pub fn burst_after_system_call() {
+    let mut scheduler = mixed();
+    dispatch(&mut scheduler);
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 6));
+    syscall(&mut scheduler, Syscall::Sleep(1), 3);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(3), 3));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+    scheduler.stop(StopReason::Expired);
+    // the process resumes with what it had left of its burst
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 3));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(3), 3));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 6));
+}
+
+#[test]
+pub fn forked_process_gets_timeslice() {
+    let mut scheduler = mixed();
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Fork(0), 1),
+        SyscallResult::Pid(Pid::new(4))
+    );
+    assert_eq!(scheduler.list()[3].parent(), Some(Pid::new(1)));
+    assert_eq!(scheduler.list()[0].parent(), None);
+    scheduler.stop(StopReason::Expired);
+    for (pid, timeslice) in [(2, 6), (3, 3), (4, 4), (1, 2)] {
+        assert_eq!(dispatch(&mut scheduler), (Pid::new(pid), timeslice));
+        scheduler.stop(StopReason::Expired);
+    }
+}
+
+#[test]
+pub fn add_past_the_limit() {
+    let burst = NonZeroUsize::new(2).unwrap();
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(4).unwrap(), 1).max_processes(2);
+    assert_eq!(scheduler.add_process(0, burst), Ok(Pid::new(1)));
+    assert_eq!(scheduler.add_process(0, burst), Ok(Pid::new(2)));
+
+    assert_eq!(scheduler.add_process(0, burst), Err(FailReason::MaxProcessesReached));
+    assert_eq!(scheduler.list().len(), 2);
+}
+
+#[test]
+pub fn add_without_pid_left() {
+    let burst = NonZeroUsize::new(2).unwrap();
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(4).unwrap(), 1);
+    scheduler
+        .adopt(AdoptSpec {
+            pid: Some(Pid::new(usize::MAX)),
+            priority: 0,
+            state: AdoptState::Ready,
+            timings: (0, 0, 0),
+            group: 0,
+        })
+        .unwrap();
+
+    assert_eq!(scheduler.add_process(0, burst), Err(FailReason::PidOverflow));
+    assert_eq!(scheduler.list().len(), 1);
+}
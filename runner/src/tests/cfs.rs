@@ -0,0 +1,104 @@
+use scheduler::{cfs, Pid, Scheduler, SchedulerError, StopReason, Syscall, SyscallResult};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+fn execution(scheduler: &mut impl Scheduler, pid: usize) -> usize {
+    scheduler
+        .list()
+        .into_iter()
+        .find(|p| p.pid() == pid)
+        .map(|p| p.timings().2)
+        .unwrap()
+}
+
+#[test]
+pub fn priority_weights_vruntime() {
+    let mut scheduler = cfs(NonZeroUsize::new(10).unwrap(), 1);
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(5)));
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, 1);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+
+    // both processes only compute, so they always use their whole timeslice
+    for _ in 0..30 {
+        dispatch(&mut scheduler);
+        scheduler.stop(StopReason::expired());
+    }
+
+    let high = execution(&mut scheduler, 1);
+    let low = execution(&mut scheduler, 2);
+    assert!(high > 2 * low, "high priority ran {high}, low priority ran {low}");
+    assert!(low > 0, "the low priority process starved");
+}
+
+#[test]
+pub fn equal_vruntime_breaks_ties_by_pid() {
+    let mut scheduler = cfs(NonZeroUsize::new(10).unwrap(), 1);
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    scheduler.next();
+    scheduler.stop(StopReason::expired());
+
+    // pid 2 and pid 3 are both seeded with the smallest vruntime
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 2);
+    scheduler.stop(StopReason::expired());
+
+    let mut order = vec![];
+    for _ in 0..2 {
+        order.push(dispatch(&mut scheduler).0);
+        scheduler.stop(StopReason::expired());
+    }
+    assert_eq!(order, vec![Pid::new(2), Pid::new(3)]);
+}
+
+#[test]
+pub fn long_sleeper_does_not_monopolize() {
+    let mut scheduler = cfs(NonZeroUsize::new(6).unwrap(), 1);
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 2);
+    syscall(&mut scheduler, Syscall::Sleep(100), timeslice - 3);
+
+    // pid 2 and pid 3 run while pid 1 sleeps
+    while dispatch(&mut scheduler).0 != 1 {
+        scheduler.stop(StopReason::expired());
+    }
+    scheduler.stop(StopReason::expired());
+
+    // once awake, pid 1 takes its turn with the others
+    let mut order = vec![];
+    for _ in 0..9 {
+        order.push(dispatch(&mut scheduler).0.get());
+        scheduler.stop(StopReason::expired());
+    }
+    for pid in 1..=3 {
+        let runs = order.iter().filter(|&&p| p == pid).count();
+        assert_eq!(runs, 3, "pid {pid} ran {runs} times in {order:?}");
+    }
+}
+
+#[test]
+pub fn sleep_too_long() {
+    let mut scheduler = cfs(NonZeroUsize::new(6).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+
+    let error = SyscallResult::Error(SchedulerError::InvalidSleepDuration(usize::MAX));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Sleep(usize::MAX), timeslice - 1),
+        error
+    );
+    let wait = Syscall::WaitTimeout {
+        event: 1,
+        max: usize::MAX,
+    };
+    assert_eq!(syscall(&mut scheduler, wait, timeslice - 2), error);
+    // the process goes on running
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+}
@@ -0,0 +1,146 @@
+use scheduler::{
+    CheckedScheduler, Pid, RoundRobinScheduler, SchedulerError, SchedulingDecision, StopReason,
+    Syscall, SyscallResult, TryScheduler,
+};
+use std::num::NonZeroUsize;
+
+fn checked() -> CheckedScheduler<RoundRobinScheduler> {
+    CheckedScheduler::new(RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1))
+}
+
+/// Process 1 has been created and runs.
+fn running() -> CheckedScheduler<RoundRobinScheduler> {
+    let mut scheduler = checked();
+    assert_eq!(
+        scheduler.try_stop(StopReason::syscall(Syscall::Fork(0))),
+        Ok(SyscallResult::Pid(Pid::new(1)))
+    );
+    assert!(matches!(
+        scheduler.try_next(),
+        Ok(SchedulingDecision::Run { .. })
+    ));
+    scheduler
+}
+
+fn syscall(
+    scheduler: &mut impl TryScheduler,
+    syscall: Syscall,
+    remaining: usize,
+) -> Result<SyscallResult, SchedulerError> {
+    scheduler.try_stop(StopReason::Syscall { syscall, remaining })
+}
+
+#[test]
+pub fn valid_calls() {
+    let mut scheduler = running();
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Fork(0), 4),
+        Ok(SyscallResult::Pid(Pid::new(2)))
+    );
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Signal(1), 3),
+        Ok(SyscallResult::Success)
+    );
+    assert_eq!(
+        scheduler.try_stop(StopReason::Expired),
+        Ok(SyscallResult::Success)
+    );
+    assert!(matches!(
+        scheduler.try_next(),
+        Ok(SchedulingDecision::Run { pid, .. }) if pid == 2
+    ));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::WaitPid(Pid::new(1)), 4),
+        Ok(SyscallResult::Success)
+    );
+}
+
+#[test]
+pub fn stop_before_run() {
+    let mut scheduler = checked();
+    assert_eq!(
+        scheduler.try_stop(StopReason::Expired),
+        Err(SchedulerError::StopWithoutRun)
+    );
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Yield, 0),
+        Err(SchedulerError::StopWithoutRun)
+    );
+}
+
+#[test]
+pub fn stop_after_expiry() {
+    let mut scheduler = running();
+    scheduler.try_stop(StopReason::Expired).unwrap();
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Yield, 3),
+        Err(SchedulerError::StopWithoutRun)
+    );
+}
+
+#[test]
+pub fn stop_after_block() {
+    let mut scheduler = running();
+    syscall(&mut scheduler, Syscall::Sleep(3), 4).unwrap();
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Yield, 3),
+        Err(SchedulerError::StopWithoutRun)
+    );
+    assert_eq!(
+        scheduler.try_next(),
+        Ok(SchedulingDecision::Sleep(NonZeroUsize::new(3).unwrap()))
+    );
+    assert_eq!(
+        scheduler.try_stop(StopReason::Expired),
+        Err(SchedulerError::StopWithoutRun)
+    );
+}
+
+#[test]
+pub fn stop_after_done() {
+    let mut scheduler = running();
+    syscall(&mut scheduler, Syscall::Exit, 4).unwrap();
+    assert_eq!(scheduler.try_next(), Ok(SchedulingDecision::Done));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Fork(0), 4),
+        Err(SchedulerError::StopAfterDone)
+    );
+}
+
+#[test]
+pub fn sleep_for_nothing() {
+    let mut scheduler = running();
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Sleep(0), 4),
+        Err(SchedulerError::InvalidSleepDuration(0))
+    );
+    // the scheduler has not seen the system call
+    assert_eq!(scheduler.list()[0].timings(), (0, 0, 0));
+}
+
+#[test]
+pub fn unknown_pid() {
+    let mut scheduler = running();
+    let pid = Pid::new(7);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(pid), 4),
+        Err(SchedulerError::UnknownPid(pid))
+    );
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::WaitPid(pid), 4),
+        Err(SchedulerError::UnknownPid(pid))
+    );
+    assert_eq!(
+        SchedulerError::UnknownPid(pid).to_string(),
+        "there is no process with PID 7"
+    );
+}
+
+#[test]
+pub fn error_from_scheduler() {
+    let mut scheduler = running();
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Sleep(usize::MAX), 4),
+        Err(SchedulerError::InvalidSleepDuration(usize::MAX))
+    );
+}
@@ -0,0 +1,223 @@
+use processor::cli::{run_cli, CliError};
+use std::path::PathBuf;
+
+const WORKLOAD: &str = "\
+run 3
+fork prio=2
+    run 2
+    exit
+sleep 4
+exit
+";
+
+/// Writes `text` to a file named after the test and returns its path.
+fn workload(name: &str, text: &str) -> String {
+    let path: PathBuf = std::env::temp_dir().join(format!("scheduler-sim-{}.txt", name));
+    std::fs::write(&path, text).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+pub fn run_table() {
+    let path = workload("run_table", WORKLOAD);
+    let output = run_cli([
+        "run",
+        "--scheduler",
+        "round-robin",
+        "--timeslice",
+        "5",
+        "--min-remaining",
+        "2",
+        "--trace",
+        &path,
+    ])
+    .unwrap();
+
+    assert!(output.starts_with("Done, no more processes after"));
+    assert!(output.contains("PID\tCREATED\tEXITED\tRUNNING\tTURNAROUND\tWAITING\n1\t0\t"));
+    assert!(output.contains("    0     4  RUN 1\n"));
+}
+
+#[test]
+pub fn run_json() {
+    let path = workload("run_json", WORKLOAD);
+    let output = run_cli([
+        "run",
+        "--scheduler=fcfs",
+        "--timeslice=5",
+        "--format=json",
+        &path,
+    ])
+    .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["outcome"], "Done");
+    assert_eq!(json["lifetimes"].as_array().unwrap().len(), 2);
+    assert_eq!(json["lifetimes"][1]["pid"], 2);
+    assert!(json.get("timeline").is_none());
+}
+
+#[test]
+pub fn run_csv() {
+    let path = workload("run_csv", WORKLOAD);
+    let output = run_cli([
+        "run",
+        "--scheduler",
+        "cfs",
+        "--timeslice",
+        "4",
+        "--format",
+        "csv",
+        "--trace",
+        &path,
+    ])
+    .unwrap();
+
+    let tables = output.split("\n\n").collect::<Vec<_>>();
+    assert_eq!(tables.len(), 3);
+    assert_eq!(
+        tables[0],
+        "pid,state,priority,total,syscalls,execution,extra"
+    );
+    assert!(tables[1].starts_with("pid,created,exited,running,turnaround,waiting\n1,0,"));
+    assert!(tables[2].starts_with("start,end,activity\n0,"));
+}
+
+#[test]
+pub fn compare_schedulers() {
+    let path = workload("compare_schedulers", WORKLOAD);
+    let output = run_cli([
+        "compare",
+        "--scheduler",
+        "round-robin",
+        "--scheduler",
+        "stride",
+        "--timeslice",
+        "2",
+        "--format",
+        "csv",
+        &path,
+    ])
+    .unwrap();
+
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(
+        lines[0],
+        "scheduler,turnaround,waiting,max waiting,switches,makespan"
+    );
+    assert!(lines[1].starts_with("round-robin,"));
+    assert!(lines[2].starts_with("stride,"));
+    assert_eq!(lines.len(), 3);
+}
+
+#[test]
+pub fn malformed_workload() {
+    let path = workload("malformed_workload", "run 2\nfork\n    run x\n");
+    let error = run_cli(["run", "--scheduler", "cfs", "--timeslice", "2", &path]).unwrap_err();
+
+    assert!(matches!(error, CliError::Parse { ref error, .. } if error.line == 3));
+    assert_eq!(error.exit_code(), 1);
+    assert_eq!(
+        error.to_string(),
+        format!("{}: line 3: invalid number `x`", path)
+    );
+}
+
+#[test]
+pub fn bad_arguments() {
+    let path = workload("bad_arguments", WORKLOAD);
+    let usage = |args: &[&str]| match run_cli(args.iter().copied()) {
+        Err(CliError::Usage(message)) => message,
+        other => panic!("expected a usage error, got {:?}", other),
+    };
+
+    assert_eq!(usage(&[]), "a command is required");
+    assert_eq!(usage(&["simulate"]), "unknown command `simulate`");
+    assert_eq!(
+        usage(&["run", "--scheduler", "cfs", &path]),
+        "`--timeslice` is required"
+    );
+    assert_eq!(
+        usage(&["run", "--scheduler", "lottery", "--timeslice", "2", &path]),
+        "unknown scheduler `lottery`, expected one of: round-robin, priority, cfs, fcfs, srtf, stride"
+    );
+    assert_eq!(
+        usage(&["run", "--scheduler", "cfs", "--timeslice", "0", &path]),
+        "invalid value `0` for `--timeslice`"
+    );
+    assert_eq!(
+        usage(&[
+            "run",
+            "--scheduler",
+            "cfs",
+            "--scheduler",
+            "fcfs",
+            "--timeslice",
+            "2",
+            &path
+        ]),
+        "`run` takes one `--scheduler`, use `compare` for several"
+    );
+    assert_eq!(
+        usage(&[
+            "compare",
+            "--scheduler",
+            "cfs",
+            "--timeslice",
+            "2",
+            "--trace",
+            &path
+        ]),
+        "`--trace` is only for `run`"
+    );
+    assert_eq!(
+        usage(&[
+            "run",
+            "--scheduler",
+            "cfs",
+            "--timeslice",
+            "2",
+            "--format",
+            "xml",
+            &path
+        ]),
+        "unknown format `xml`, expected table, json or csv"
+    );
+    assert_eq!(
+        usage(&[
+            "tui",
+            "--scheduler",
+            "cfs",
+            "--timeslice",
+            "2",
+            "--format",
+            "csv",
+            &path
+        ]),
+        "`tui` takes no `--trace` or `--format`"
+    );
+    assert_eq!(
+        usage(&["run", "--timeslice"]),
+        "`--timeslice` needs a value"
+    );
+    assert_eq!(
+        usage(&["run", "--color", "red"]),
+        "unknown option `--color`"
+    );
+}
+
+#[test]
+pub fn missing_workload() {
+    let error = run_cli([
+        "run",
+        "--scheduler",
+        "cfs",
+        "--timeslice",
+        "2",
+        "/nonexistent/workload.txt",
+    ])
+    .unwrap_err();
+
+    assert!(matches!(error, CliError::Read { .. }));
+    assert!(error.to_string().starts_with("/nonexistent/workload.txt: "));
+}
@@ -0,0 +1,98 @@
+use scheduler::{
+    EventBus, Pid, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+fn scheduler() -> RoundRobinScheduler {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    scheduler
+}
+
+/// Process 1 forks process 2, which waits for event 1.
+fn prefix(scheduler: &mut RoundRobinScheduler) {
+    dispatch(scheduler);
+    syscall(scheduler, Syscall::Fork(0), 4);
+    syscall(scheduler, Syscall::Yield, 3);
+    assert_eq!(dispatch(scheduler).0, Pid::new(2));
+    syscall(scheduler, Syscall::Wait(1), 4);
+}
+
+/// Process 1 signals event 1 right away.
+fn signal_now(scheduler: &mut RoundRobinScheduler) {
+    assert_eq!(dispatch(scheduler).0, Pid::new(1));
+    syscall(scheduler, Syscall::Signal(1), 4);
+    syscall(scheduler, Syscall::Yield, 3);
+    assert_eq!(dispatch(scheduler).0, Pid::new(2));
+}
+
+/// Process 1 sleeps before it signals event 1.
+fn signal_later(scheduler: &mut RoundRobinScheduler) {
+    assert_eq!(dispatch(scheduler).0, Pid::new(1));
+    syscall(scheduler, Syscall::Sleep(10), 4);
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(10).unwrap())
+    );
+    assert_eq!(dispatch(scheduler).0, Pid::new(1));
+    syscall(scheduler, Syscall::Signal(1), 2);
+    syscall(scheduler, Syscall::Yield, 1);
+    assert_eq!(dispatch(scheduler).0, Pid::new(2));
+}
+
+fn timings(scheduler: &mut impl Scheduler) -> Vec<(Pid, (usize, usize, usize))> {
+    scheduler
+        .list()
+        .iter()
+        .map(|p| (p.pid(), p.timings()))
+        .collect()
+}
+
+/// Plays the prefix and then `branch` on a new scheduler.
+fn replay(branch: fn(&mut RoundRobinScheduler)) -> RoundRobinScheduler {
+    let mut scheduler = scheduler();
+    prefix(&mut scheduler);
+    branch(&mut scheduler);
+    scheduler
+}
+
+#[test]
+pub fn branches_do_not_interfere() {
+    let mut original = scheduler();
+    prefix(&mut original);
+    let mut clone = original.clone();
+
+    signal_now(&mut original);
+    signal_later(&mut clone);
+
+    // every branch has the same timings as if it had been played alone
+    assert_eq!(timings(&mut original), timings(&mut replay(signal_now)));
+    assert_eq!(timings(&mut clone), timings(&mut replay(signal_later)));
+    assert_ne!(timings(&mut original), timings(&mut clone));
+    assert_eq!(original.statistics(), replay(signal_now).statistics());
+    assert_eq!(clone.statistics(), replay(signal_later).statistics());
+}
+
+#[test]
+pub fn clone_receives_signals_from_bus() {
+    let bus = EventBus::new();
+    let mut original = scheduler().with_event_bus(bus.clone());
+    let mut signaler = scheduler().with_event_bus(bus);
+    prefix(&mut original);
+    dispatch(&mut original);
+    syscall(&mut original, Syscall::Wait(2), 4);
+
+    dispatch(&mut signaler);
+    syscall(&mut signaler, Syscall::Signal(1), 4);
+    let mut clone = original.clone();
+    syscall(&mut signaler, Syscall::Signal(2), 3);
+
+    // both branches have received both signals
+    for scheduler in [&mut original, &mut clone] {
+        assert_eq!(dispatch(scheduler).0, Pid::new(2));
+        syscall(scheduler, Syscall::Yield, 4);
+        assert_eq!(dispatch(scheduler).0, Pid::new(1));
+    }
+}
@@ -0,0 +1,58 @@
+use processor::compare::{compare, Factory, Metric};
+use processor::workload::Workload;
+use scheduler::{fcfs, round_robin};
+use std::num::NonZeroUsize;
+
+/// A long process forked before a short one, the short one waits for the
+/// whole long process with FCFS, but only for a few timeslices with round robin.
+const LONG_THEN_SHORT: &str = "\
+fork
+    run 12
+fork
+    run 1
+sleep 20
+";
+
+fn schedulers() -> Vec<(String, Factory)> {
+    vec![
+        (
+            String::from("round robin"),
+            Box::new(|| Box::new(round_robin(NonZeroUsize::new(2).unwrap(), 1))),
+        ),
+        (
+            String::from("fcfs"),
+            Box::new(|| Box::new(fcfs(NonZeroUsize::new(2).unwrap()))),
+        ),
+    ]
+}
+
+#[test]
+pub fn round_robin_against_fcfs() {
+    let workload = Workload::parse(LONG_THEN_SHORT).unwrap();
+    let report = compare(&workload, schedulers()).unwrap();
+
+    // the short process exits after 16 time units with FCFS,
+    // and after 4 time units with round robin
+    assert_eq!(report.winner(Metric::MeanTurnaround), Some("round robin"));
+    assert_eq!(report.winner(Metric::MeanWaiting), Some("round robin"));
+    assert_eq!(report.winner(Metric::ContextSwitches), Some("fcfs"));
+
+    let (_, fcfs) = report.entries[1];
+    assert_eq!(fcfs.mean_turnaround, (15.0 + 16.0 + 24.0) / 3.0);
+    assert_eq!(fcfs.makespan, 24);
+}
+
+#[test]
+pub fn table_marks_winners() {
+    let workload = Workload::parse(LONG_THEN_SHORT).unwrap();
+    let report = compare(&workload, schedulers()).unwrap();
+    let table = report.to_string();
+    let lines = table.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("scheduler"));
+    assert!(lines[1].starts_with("round robin"));
+    assert!(lines[1].contains("16.33*"));
+    assert!(lines[2].starts_with("fcfs"));
+    assert!(lines[2].contains("18.33 "));
+}
@@ -0,0 +1,131 @@
+use scheduler::{
+    Pid, ProcessState, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+fn run_on(scheduler: &mut RoundRobinScheduler, core: usize) -> Pid {
+    match scheduler.next_on(core) {
+        Some(SchedulingDecision::Run { pid, .. }) => pid,
+        decision => panic!("expected a process to be scheduled, got {decision:?}"),
+    }
+}
+
+fn states(scheduler: &mut RoundRobinScheduler) -> Vec<ProcessState> {
+    scheduler.list().iter().map(|p| p.state()).collect()
+}
+
+#[test]
+pub fn two_cores() {
+    let mut scheduler = RoundRobinScheduler::with_cores(
+        NonZeroUsize::new(5).unwrap(),
+        1,
+        NonZeroUsize::new(2).unwrap(),
+    );
+    scheduler.stop_on(0, StopReason::syscall(Syscall::Fork(0)));
+
+    assert_eq!(run_on(&mut scheduler, 0), Pid::new(1));
+    // the second core has nothing to run yet
+    assert_eq!(scheduler.next_on(1), None);
+    for remaining in [4, 3, 2] {
+        scheduler.stop_on(
+            0,
+            StopReason::Syscall {
+                syscall: Syscall::Fork(0),
+                remaining,
+            },
+        );
+    }
+
+    assert_eq!(run_on(&mut scheduler, 1), Pid::new(2));
+    assert_eq!(run_on(&mut scheduler, 0), Pid::new(1));
+    assert_eq!(
+        states(&mut scheduler),
+        vec![
+            ProcessState::Running,
+            ProcessState::Running,
+            ProcessState::Ready,
+            ProcessState::Ready
+        ]
+    );
+
+    // the processes of one core are preempted by the other ones
+    scheduler.stop_on(1, StopReason::Expired);
+    assert_eq!(run_on(&mut scheduler, 1), Pid::new(3));
+    scheduler.stop_on(0, StopReason::Expired);
+    assert_eq!(run_on(&mut scheduler, 0), Pid::new(4));
+    scheduler.stop_on(1, StopReason::Expired);
+    assert_eq!(run_on(&mut scheduler, 1), Pid::new(2));
+    assert_eq!(
+        states(&mut scheduler),
+        vec![
+            ProcessState::Ready,
+            ProcessState::Running,
+            ProcessState::Ready,
+            ProcessState::Running
+        ]
+    );
+}
+
+#[test]
+pub fn idle_core() {
+    let mut scheduler = RoundRobinScheduler::with_cores(
+        NonZeroUsize::new(5).unwrap(),
+        1,
+        NonZeroUsize::new(2).unwrap(),
+    );
+    scheduler.stop_on(0, StopReason::syscall(Syscall::Fork(0)));
+
+    assert_eq!(run_on(&mut scheduler, 1), Pid::new(1));
+    scheduler.stop_on(
+        1,
+        StopReason::Syscall {
+            syscall: Syscall::Sleep(3),
+            remaining: 4,
+        },
+    );
+    assert_eq!(
+        scheduler.next_on(0),
+        Some(SchedulingDecision::Sleep(NonZeroUsize::new(3).unwrap()))
+    );
+    assert_eq!(run_on(&mut scheduler, 0), Pid::new(1));
+    scheduler.stop_on(
+        0,
+        StopReason::Syscall {
+            syscall: Syscall::Exit,
+            remaining: 4,
+        },
+    );
+    assert_eq!(scheduler.next_on(1), Some(SchedulingDecision::Done));
+}
+
+#[test]
+pub fn cores_share_the_clock() {
+    let mut scheduler = RoundRobinScheduler::with_cores(
+        NonZeroUsize::new(5).unwrap(),
+        1,
+        NonZeroUsize::new(2).unwrap(),
+    );
+    scheduler.stop_on(0, StopReason::syscall(Syscall::Fork(0)));
+
+    assert_eq!(run_on(&mut scheduler, 0), Pid::new(1));
+    scheduler.stop_on(
+        0,
+        StopReason::Syscall {
+            syscall: Syscall::Fork(0),
+            remaining: 4,
+        },
+    );
+    // the two processes run at the same time, from time 1 to 5 and 6
+    assert_eq!(run_on(&mut scheduler, 1), Pid::new(2));
+    assert_eq!(run_on(&mut scheduler, 0), Pid::new(1));
+    scheduler.stop_on(0, StopReason::Expired);
+    scheduler.stop_on(1, StopReason::Expired);
+
+    assert_eq!(scheduler.statistics().total_time, 6);
+    let timings = scheduler
+        .list()
+        .iter()
+        .map(|p| p.timings())
+        .collect::<Vec<_>>();
+    assert_eq!(timings, vec![(6, 1, 4), (5, 0, 5)]);
+}
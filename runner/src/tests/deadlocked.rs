@@ -0,0 +1,60 @@
+use scheduler::{Pid, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason, Syscall};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+/// Process 1 forks `children` processes and lets them run.
+fn fork(children: usize) -> RoundRobinScheduler {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    for child in 0..children {
+        syscall(&mut scheduler, Syscall::Fork(0), 9 - child);
+    }
+    syscall(&mut scheduler, Syscall::Yield, 9 - children);
+    scheduler
+}
+
+#[test]
+pub fn two_process_event_cycle() {
+    let mut scheduler = fork(1);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Wait(1), 9);
+    assert!(scheduler.deadlocked().is_empty());
+
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Wait(2), 7);
+    assert_eq!(scheduler.deadlocked(), vec![Pid::new(1), Pid::new(2)]);
+    assert_eq!(scheduler.next(), SchedulingDecision::Deadlock);
+}
+
+#[test]
+pub fn three_process_cycle() {
+    let mut scheduler = fork(3);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::WaitPid(Pid::new(3)), 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(3));
+    syscall(&mut scheduler, Syscall::WaitPid(Pid::new(4)), 9);
+    assert!(scheduler.deadlocked().is_empty());
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(4));
+    syscall(&mut scheduler, Syscall::WaitPid(Pid::new(2)), 9);
+
+    // process 1 keeps running, but the others wait for each other
+    assert_eq!(
+        scheduler.deadlocked(),
+        vec![Pid::new(2), Pid::new(3), Pid::new(4)]
+    );
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+}
+
+#[test]
+pub fn signal_from_sleeping_process() {
+    let mut scheduler = fork(1);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Wait(1), 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Sleep(5), 7);
+
+    assert!(scheduler.deadlocked().is_empty());
+}
@@ -0,0 +1,107 @@
+use scheduler::{
+    make_scheduler, FifoQueue, Pid, QueueScheduler, Scheduler, SchedulerConfig, SchedulerKind,
+    SchedulingDecision, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+const TIMESLICE: NonZeroUsize = NonZeroUsize::new(5).unwrap();
+
+/// Every bundled scheduler, with a minimum remaining timeslice of 2.
+fn schedulers() -> Vec<(String, Box<dyn Scheduler>)> {
+    let config = SchedulerConfig::new(TIMESLICE, 2);
+    let queue = QueueScheduler::new(FifoQueue::new(), TIMESLICE, 2);
+    SchedulerKind::ALL
+        .into_iter()
+        .map(|kind| (kind.to_string(), make_scheduler(kind, config)))
+        .chain([("queue".to_string(), Box::new(queue) as Box<dyn Scheduler>)])
+        .collect()
+}
+
+/// Returns the next decision, which has to be the one peeked first.
+fn next(scheduler: &mut dyn Scheduler) -> SchedulingDecision {
+    let peeked = scheduler.peek_next();
+    let decision = scheduler.next();
+    assert_eq!(decision, peeked);
+    decision
+}
+
+#[test]
+pub fn sleeper_left() {
+    for (name, mut scheduler) in schedulers() {
+        scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+        let (_, timeslice) = dispatch(&mut scheduler);
+        syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+        syscall(&mut scheduler, Syscall::Sleep(10), timeslice - 2);
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(2), "{name}");
+        syscall(&mut scheduler, Syscall::Exit, 4);
+
+        // nothing is ready, but process 1 still sleeps
+        let decision = next(scheduler.as_mut());
+        assert!(
+            matches!(decision, SchedulingDecision::Sleep(_)),
+            "{name}: {decision}"
+        );
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(1), "{name}");
+        syscall(&mut scheduler, Syscall::Exit, 4);
+        assert_eq!(next(scheduler.as_mut()), SchedulingDecision::Done, "{name}");
+    }
+}
+
+#[test]
+pub fn waiter_left() {
+    for (name, mut scheduler) in schedulers() {
+        scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+        let (_, timeslice) = dispatch(&mut scheduler);
+        syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+        syscall(&mut scheduler, Syscall::Wait(1), timeslice - 2);
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(2), "{name}");
+        syscall(&mut scheduler, Syscall::Exit, 4);
+
+        // process 1 waits for an event that no process is left to signal
+        let decision = next(scheduler.as_mut());
+        assert_eq!(decision, SchedulingDecision::Deadlock, "{name}");
+        assert_eq!(scheduler.list().len(), 1, "{name}");
+    }
+}
+
+#[test]
+pub fn waiting_for_a_sleeper() {
+    for (name, mut scheduler) in schedulers() {
+        scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+        let (_, timeslice) = dispatch(&mut scheduler);
+        syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+        syscall(&mut scheduler, Syscall::WaitPid(Pid::new(2)), timeslice - 2);
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(2), "{name}");
+        syscall(&mut scheduler, Syscall::Sleep(3), 4);
+
+        // the sleeper wakes up and exits, then the process waiting for it
+        let decision = next(scheduler.as_mut());
+        assert!(
+            matches!(decision, SchedulingDecision::Sleep(_)),
+            "{name}: {decision}"
+        );
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(2), "{name}");
+        syscall(&mut scheduler, Syscall::Exit, 4);
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(1), "{name}");
+        syscall(&mut scheduler, Syscall::Exit, 4);
+        assert_eq!(next(scheduler.as_mut()), SchedulingDecision::Done, "{name}");
+    }
+}
+
+#[test]
+pub fn requeued_below_minimum() {
+    for (name, mut scheduler) in schedulers() {
+        scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+        dispatch(&mut scheduler);
+
+        // the only process is put back with a new timeslice, not dropped
+        syscall(&mut scheduler, Syscall::Signal(1), 1);
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(1), "{name}");
+        syscall(&mut scheduler, Syscall::Yield, 0);
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(1), "{name}");
+        syscall(&mut scheduler, Syscall::Exit, 4);
+        assert_eq!(next(scheduler.as_mut()), SchedulingDecision::Done, "{name}");
+    }
+}
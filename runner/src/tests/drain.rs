@@ -0,0 +1,36 @@
+use scheduler::{
+    Pid, ProcessState, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn drain_blocked() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::Fork(0), 3);
+    syscall(&mut scheduler, Syscall::Yield, 2);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Sleep(10), 4);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(3));
+    syscall(&mut scheduler, Syscall::Wait(1), 4);
+
+    assert_eq!(scheduler.drain_blocked(), vec![Pid::new(2), Pid::new(3)]);
+    let states = scheduler
+        .list()
+        .iter()
+        .map(|p| (p.pid(), p.state()))
+        .collect::<Vec<_>>();
+    assert_eq!(states, vec![(Pid::new(1), ProcessState::Ready)]);
+    assert_eq!(scheduler.statistics().completed, 0);
+
+    // the sleeping process does not wake up anymore
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Exit, 4);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+    assert!(scheduler.drain_blocked().is_empty());
+}
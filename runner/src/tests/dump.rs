@@ -0,0 +1,31 @@
+use scheduler::{Pid, RoundRobinScheduler, Scheduler, StopReason, Syscall};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn round_robin_dump() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(2), 4);
+    syscall(&mut scheduler, Syscall::Fork(1), 3);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Sleep(10), 2);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+
+    assert_eq!(
+        scheduler.to_string(),
+        "\
+time: 3
+  PID   STATE      PRIORITY REMAINING
+ready:
+  2     RUNNING    2        5
+  3     READY      1        5
+sleeping:
+  1     SLEEP      0        2
+waiting:
+"
+    );
+}
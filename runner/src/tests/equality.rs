@@ -0,0 +1,74 @@
+use scheduler::{
+    Pid, ProcessState, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason, Syscall,
+    SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+/// Returns the position of the first call where two runs differ.
+fn first_difference<T: Eq>(left: &[T], right: &[T]) -> Option<usize> {
+    (0..left.len().max(right.len())).find(|&i| left.get(i) != right.get(i))
+}
+
+/// Runs a process that forks and then sleeps, and returns every
+/// stop reason with its result and every decision.
+fn run() -> (
+    Vec<(StopReason, SyscallResult)>,
+    Vec<SchedulingDecision>,
+    Vec<ProcessState>,
+) {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(4).unwrap(), 1);
+    let mut stops = vec![];
+    let mut decisions = vec![];
+    let reasons = [
+        StopReason::syscall(Syscall::Fork(0)),
+        StopReason::Syscall {
+            syscall: Syscall::Fork(1),
+            remaining: 3,
+        },
+        StopReason::Syscall {
+            syscall: Syscall::Sleep(5),
+            remaining: 2,
+        },
+        StopReason::Expired,
+    ];
+    for reason in reasons {
+        stops.push((reason, scheduler.stop(reason)));
+        decisions.push(scheduler.next());
+    }
+    let states = scheduler.list().iter().map(|p| p.state()).collect();
+    (stops, decisions, states)
+}
+
+#[test]
+pub fn decisions_compare_directly() {
+    let (stops, decisions, states) = run();
+    assert_eq!(
+        decisions[0],
+        SchedulingDecision::Run {
+            pid: Pid::new(1),
+            timeslice: NonZeroUsize::new(4).unwrap(),
+        }
+    );
+    assert_eq!(stops[1].1, SyscallResult::Pid(Pid::new(2)));
+    // process 2 runs again while process 1 sleeps
+    let second = SchedulingDecision::Run {
+        pid: Pid::new(2),
+        timeslice: NonZeroUsize::new(4).unwrap(),
+    };
+    assert_eq!(decisions[2], second);
+    assert_eq!(decisions[3], decisions[2]);
+    assert_eq!(
+        states,
+        vec![ProcessState::Waiting { event: None }, ProcessState::Running]
+    );
+}
+
+#[test]
+pub fn identical_runs() {
+    let (stops, decisions, states) = run();
+    let (other_stops, other_decisions, other_states) = run();
+    assert_eq!(first_difference(&stops, &other_stops), None);
+    assert_eq!(first_difference(&decisions, &other_decisions), None);
+    assert_eq!(first_difference(&states, &other_states), None);
+    assert_eq!(first_difference(&decisions, &decisions[..2]), Some(2));
+}
@@ -0,0 +1,93 @@
+use scheduler::{
+    EventBus, Pid, ProcessState, RoundRobinScheduler, Scheduler, SchedulingDecision, SignalPolicy,
+    StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+fn scheduler() -> RoundRobinScheduler {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    scheduler
+}
+
+/// Process 1 waits for event 3, no process is left to signal it.
+fn waiting(scheduler: &mut RoundRobinScheduler) {
+    dispatch(scheduler);
+    syscall(scheduler, Syscall::Wait(3), 8);
+}
+
+/// Process 1 signals event 3.
+fn signal(scheduler: &mut RoundRobinScheduler) {
+    dispatch(scheduler);
+    syscall(scheduler, Syscall::Signal(3), 8);
+}
+
+#[test]
+pub fn signal_wakes_other_scheduler() {
+    let bus = EventBus::new();
+    let mut waiter = scheduler().with_event_bus(bus.clone());
+    let mut signaler = scheduler().with_event_bus(bus);
+
+    waiting(&mut waiter);
+    assert_eq!(waiter.peek_next(), SchedulingDecision::Deadlock);
+
+    signal(&mut signaler);
+    let expected = SchedulingDecision::Run {
+        pid: Pid::new(1),
+        timeslice: NonZeroUsize::new(10).unwrap(),
+    };
+    assert_eq!(waiter.peek_next(), expected);
+    assert_eq!(waiter.next(), expected);
+}
+
+#[test]
+pub fn no_bus() {
+    let mut waiter = scheduler();
+    let mut signaler = scheduler();
+
+    waiting(&mut waiter);
+    signal(&mut signaler);
+    assert_eq!(waiter.next(), SchedulingDecision::Deadlock);
+}
+
+#[test]
+pub fn signal_before_wait_is_lost() {
+    let bus = EventBus::new();
+    let mut waiter = scheduler().with_event_bus(bus.clone());
+    let mut signaler = scheduler().with_event_bus(bus);
+
+    signal(&mut signaler);
+    waiting(&mut waiter);
+    assert_eq!(waiter.next(), SchedulingDecision::Deadlock);
+}
+
+#[test]
+pub fn wake_one_on_other_scheduler() {
+    let bus = EventBus::new();
+    let mut waiter = scheduler()
+        .signal_policy(SignalPolicy::WakeOne)
+        .with_event_bus(bus.clone());
+    let mut signaler = scheduler().with_event_bus(bus);
+
+    // processes 2 and then 1 wait for event 3
+    dispatch(&mut waiter);
+    syscall(&mut waiter, Syscall::Fork(0), 9);
+    syscall(&mut waiter, Syscall::Yield, 8);
+    assert_eq!(dispatch(&mut waiter).0, Pid::new(2));
+    syscall(&mut waiter, Syscall::Wait(3), 9);
+    assert_eq!(dispatch(&mut waiter).0, Pid::new(1));
+    syscall(&mut waiter, Syscall::Wait(3), 7);
+
+    signal(&mut signaler);
+    assert_eq!(dispatch(&mut waiter).0, Pid::new(2));
+    let states = waiter.list().iter().map(|p| p.state()).collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        vec![
+            ProcessState::Waiting { event: Some(3) },
+            ProcessState::Running
+        ]
+    );
+}
@@ -0,0 +1,44 @@
+use scheduler::{EventTable, Pid};
+
+#[test]
+pub fn signal_in_wait_order() {
+    let mut events = EventTable::new();
+    assert!(events.is_empty());
+
+    events.wait(Pid::new(3), 1);
+    events.wait(Pid::new(1), 1);
+    events.wait(Pid::new(2), 2);
+    assert_eq!(events.waiting_count(), 3);
+    assert_eq!(
+        events.waiters(1).collect::<Vec<_>>(),
+        vec![Pid::new(3), Pid::new(1)]
+    );
+
+    assert_eq!(events.signal(1), vec![Pid::new(3), Pid::new(1)]);
+    assert!(events.signal(1).is_empty());
+    assert_eq!(events.waiting_count(), 1);
+    assert_eq!(events.signal(2), vec![Pid::new(2)]);
+    assert!(events.is_empty());
+}
+
+#[test]
+pub fn remove_waiter() {
+    let mut events = EventTable::new();
+    let pid = Pid::new(1);
+
+    // the process waits several times over its lifetime
+    events.wait(pid, 1);
+    events.wait(Pid::new(2), 1);
+    assert_eq!(events.signal(1), vec![pid, Pid::new(2)]);
+    events.wait(pid, 2);
+    events.wait(Pid::new(2), 2);
+
+    assert!(events.remove(pid));
+    assert!(!events.remove(pid));
+    assert_eq!(events.waiters(1).count(), 0);
+    assert_eq!(events.signal(2), vec![Pid::new(2)]);
+
+    events.wait(pid, 3);
+    assert!(events.remove(pid));
+    assert!(events.is_empty());
+}
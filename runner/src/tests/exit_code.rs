@@ -0,0 +1,31 @@
+use scheduler::{Pid, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason, Syscall};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn child_exits_while_parent_sleeps() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::Sleep(3), 3);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::ExitWith(3), 4);
+    assert_eq!(scheduler.exit_code(Pid::new(1)), None);
+
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(2).unwrap())
+    );
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    assert_eq!(scheduler.exit_code(Pid::new(2)), Some(3));
+
+    syscall(&mut scheduler, Syscall::Exit, 4);
+    assert_eq!(scheduler.exit_code(Pid::new(1)), Some(0));
+
+    // the exit codes are forgotten once there are no more processes
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+    assert_eq!(scheduler.exit_code(Pid::new(2)), None);
+}
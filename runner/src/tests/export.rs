@@ -0,0 +1,105 @@
+use scheduler::export::{write_stats_csv, write_timings_csv};
+use scheduler::{
+    round_robin, Pid, Process, ProcessState, RoundRobinScheduler, Scheduler, SchedulingDecision,
+    StopReason, Syscall, SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn round_robin_timings() {
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(4), 2);
+    syscall(&mut scheduler, Syscall::Wait(2), 0);
+    dispatch(&mut scheduler);
+    scheduler.stop(StopReason::Expired);
+
+    let mut csv = vec![];
+    write_timings_csv(&mut scheduler, &mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "pid,state,priority,total,syscalls,execution,extra\n\
+         1,EVENT 2,0,6,2,1,group=0\n\
+         2,READY,4,5,0,3,group=0\n"
+    );
+}
+
+#[test]
+pub fn round_robin_statistics() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(2), 1);
+    scheduler.next();
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, 0);
+
+    let mut csv = vec![];
+    write_stats_csv(&scheduler.statistics(), &mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "key,value\n\
+         total_time,5\n\
+         idle_time,2\n\
+         completed,1\n\
+         context_switches,1\n\
+         average_turnaround,5\n"
+    );
+}
+
+/// A process with details that have to be quoted in a CSV.
+struct Quoted;
+
+impl Process for Quoted {
+    fn pid(&self) -> Pid {
+        Pid::new(1)
+    }
+
+    fn state(&self) -> ProcessState {
+        ProcessState::Ready
+    }
+
+    fn timings(&self) -> (usize, usize, usize) {
+        (0, 0, 0)
+    }
+
+    fn priority(&self) -> i8 {
+        0
+    }
+
+    fn extra(&self) -> String {
+        "queue=1, note=\"new\"".to_string()
+    }
+}
+
+impl Scheduler for Quoted {
+    fn next(&mut self) -> SchedulingDecision {
+        SchedulingDecision::Done
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        SchedulingDecision::Done
+    }
+
+    fn stop(&mut self, _reason: StopReason) -> SyscallResult {
+        SyscallResult::Success
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        vec![self]
+    }
+}
+
+#[test]
+pub fn quoted_extra() {
+    let mut csv = vec![];
+    write_timings_csv(&mut Quoted, &mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "pid,state,priority,total,syscalls,execution,extra\n\
+         1,READY,0,0,0,0,\"queue=1, note=\"\"new\"\"\"\n"
+    );
+}
@@ -0,0 +1,36 @@
+use scheduler::{
+    make_scheduler, Pid, SchedulerConfig, SchedulerKind, SchedulingDecision, StopReason, Syscall,
+    SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+#[test]
+pub fn every_kind() {
+    let config =
+        SchedulerConfig::new(NonZeroUsize::new(3).unwrap(), 1).aging(NonZeroUsize::new(5).unwrap());
+    for kind in SchedulerKind::ALL {
+        assert_eq!(kind.name().parse(), Ok(kind));
+
+        let mut scheduler = make_scheduler(kind, config);
+        assert_eq!(
+            scheduler.stop(StopReason::syscall(Syscall::Fork(0))),
+            SyscallResult::Pid(Pid::new(1)),
+            "{}",
+            kind
+        );
+        assert!(
+            matches!(scheduler.next(), SchedulingDecision::Run { pid, .. } if pid == 1),
+            "{}",
+            kind
+        );
+    }
+}
+
+#[test]
+pub fn unknown_kind() {
+    let error = "lottery".parse::<SchedulerKind>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unknown scheduler `lottery`, expected one of: round-robin, priority, cfs, fcfs, srtf, stride"
+    );
+}
@@ -0,0 +1,171 @@
+use scheduler::{
+    FallbackScheduler, FcfsScheduler, Pid, RoundRobinScheduler, Scheduler, SchedulerError,
+    SchedulingDecision, StopReason, Syscall, SyscallResult, Tier,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+/// A first come first served tier, standing for a real-time tier,
+/// over a round robin tier for best effort processes.
+fn tiers() -> FallbackScheduler<FcfsScheduler, RoundRobinScheduler> {
+    FallbackScheduler::new(
+        FcfsScheduler::new(NonZeroUsize::new(5).unwrap()),
+        RoundRobinScheduler::new(NonZeroUsize::new(2).unwrap(), 1),
+    )
+}
+
+fn processes(scheduler: &mut impl Scheduler) -> Vec<(usize, Option<usize>)> {
+    scheduler
+        .list()
+        .iter()
+        .map(|p| (p.pid().get(), p.parent().map(Pid::get)))
+        .collect()
+}
+
+fn run(pid: usize, timeslice: usize) -> SchedulingDecision {
+    SchedulingDecision::Run {
+        pid: Pid::new(pid),
+        timeslice: NonZeroUsize::new(timeslice).unwrap(),
+    }
+}
+
+#[test]
+pub fn second_tier_runs_when_first_is_done() {
+    let mut scheduler = tiers();
+    assert_eq!(
+        scheduler.stop(StopReason::syscall(Syscall::Fork(0))),
+        SyscallResult::Pid(Pid::new(1))
+    );
+    assert_eq!(
+        scheduler.spawn(Tier::Second, 0),
+        SyscallResult::Pid(Pid::new(2))
+    );
+    assert_eq!(scheduler.tier(Pid::new(1)), Some(Tier::First));
+    assert_eq!(scheduler.tier(Pid::new(2)), Some(Tier::Second));
+
+    // the processes forked in the first tier stay in it
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Fork(0), 4),
+        SyscallResult::Pid(Pid::new(3))
+    );
+    assert_eq!(scheduler.tier(Pid::new(3)), Some(Tier::First));
+    syscall(&mut scheduler, Syscall::Sleep(3), 3);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(3));
+    syscall(&mut scheduler, Syscall::Exit, 4);
+
+    // the second tier waits while the first one sleeps
+    assert!(matches!(scheduler.next(), SchedulingDecision::Sleep(_)));
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Exit, 4);
+
+    assert_eq!(scheduler.peek_next(), run(2, 2));
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 2));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Fork(0), 0),
+        SyscallResult::Pid(Pid::new(4))
+    );
+    assert_eq!(scheduler.tier(Pid::new(4)), Some(Tier::Second));
+    assert_eq!(processes(&mut scheduler), vec![(2, None), (4, Some(2))]);
+
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(4));
+    syscall(&mut scheduler, Syscall::Exit, 1);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Exit, 1);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+}
+
+#[test]
+pub fn stops_go_to_the_running_tier() {
+    let mut scheduler = tiers();
+    scheduler.spawn(Tier::Second, 0);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    // the first tier runs first, whatever the order of the PIDs
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 5));
+    syscall(&mut scheduler, Syscall::Sleep(10), 4);
+    let (total, syscalls, _) = scheduler.list()[1].timings();
+    assert_eq!((total, syscalls), (1, 1));
+    assert_eq!(scheduler.list()[0].timings(), (0, 0, 0));
+}
+
+#[test]
+pub fn processes_of_other_tiers_are_out_of_reach() {
+    let mut scheduler = tiers();
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    scheduler.spawn(Tier::Second, 0);
+
+    dispatch(&mut scheduler);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(2)), 4),
+        SyscallResult::NoSuchProcess
+    );
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::WaitPid(Pid::new(2)), 3),
+        SyscallResult::NoSuchProcess
+    );
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(9)), 2),
+        SyscallResult::NoSuchProcess
+    );
+    assert_eq!(processes(&mut scheduler), vec![(1, None), (2, None)]);
+
+    // a process of the same tier is translated to its PID in the tier
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Fork(0), 1),
+        SyscallResult::Pid(Pid::new(3))
+    );
+    dispatch(&mut scheduler);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(3)), 4),
+        SyscallResult::Success
+    );
+    assert_eq!(processes(&mut scheduler), vec![(1, None), (2, None)]);
+}
+
+#[test]
+pub fn existing_processes_get_pids() {
+    let mut first = FcfsScheduler::new(NonZeroUsize::new(5).unwrap());
+    first.stop(StopReason::syscall(Syscall::Fork(0)));
+    let mut second = RoundRobinScheduler::new(NonZeroUsize::new(2).unwrap(), 1);
+    second.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut second);
+    syscall(&mut second, Syscall::Fork(0), 1);
+
+    let mut scheduler = FallbackScheduler::new(first, second);
+    assert_eq!(
+        processes(&mut scheduler),
+        vec![(1, None), (2, None), (3, Some(2))]
+    );
+    assert_eq!(scheduler.tier(Pid::new(3)), Some(Tier::Second));
+    assert_eq!(scheduler.tier(Pid::new(4)), None);
+}
+
+#[test]
+pub fn spawn_while_the_tier_runs() {
+    let mut scheduler = tiers();
+    scheduler.spawn(Tier::Second, 0);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+
+    // the tier would take the spawn for a fork of the running process
+    assert_eq!(
+        scheduler.spawn(Tier::Second, 0),
+        SyscallResult::Error(SchedulerError::SpawnWhileRunning)
+    );
+    assert_eq!(processes(&mut scheduler), vec![(1, None)]);
+    assert_eq!(scheduler.list()[0].timings(), (0, 0, 0));
+
+    // the other tier takes new processes while process 1 runs
+    assert_eq!(
+        scheduler.spawn(Tier::First, 0),
+        SyscallResult::Pid(Pid::new(2))
+    );
+    syscall(&mut scheduler, Syscall::Exit, 1);
+    assert_eq!(processes(&mut scheduler), vec![(2, None)]);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    assert_eq!(
+        scheduler.spawn(Tier::Second, 0),
+        SyscallResult::Pid(Pid::new(3))
+    );
+}
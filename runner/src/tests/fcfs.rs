@@ -0,0 +1,94 @@
+use scheduler::{
+    fcfs, Pid, Scheduler, SchedulerError, SchedulingDecision, StopReason, Syscall, SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn arrival_order() {
+    let mut scheduler = fcfs(NonZeroUsize::new(10).unwrap());
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 2);
+
+    // the process keeps running until it gives up the processor
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    scheduler.stop(StopReason::Expired);
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Sleep(1), timeslice - 5);
+
+    let mut order = vec![];
+    for _ in 0..2 {
+        let (pid, timeslice) = dispatch(&mut scheduler);
+        order.push(pid.get());
+        syscall(&mut scheduler, Syscall::Exit, timeslice - 1);
+    }
+    assert_eq!(order, vec![2, 3]);
+
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Exit, timeslice - 1);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+}
+
+#[test]
+pub fn woken_process_arrives_again() {
+    let mut scheduler = fcfs(NonZeroUsize::new(10).unwrap());
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 2);
+    syscall(&mut scheduler, Syscall::Sleep(2), timeslice - 3);
+
+    // process 1 wakes up while process 2 runs, it goes after process 3
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Wait(1), timeslice - 4);
+
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, Pid::new(3));
+    syscall(&mut scheduler, Syscall::Signal(1), timeslice - 1);
+    syscall(&mut scheduler, Syscall::Yield, timeslice - 2);
+
+    let order = (0..3)
+        .map(|_| {
+            let (pid, timeslice) = dispatch(&mut scheduler);
+            syscall(&mut scheduler, Syscall::Yield, timeslice - 1);
+            pid.get()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(order, vec![1, 2, 3]);
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Run {
+            pid: Pid::new(1),
+            timeslice: NonZeroUsize::new(10).unwrap()
+        }
+    );
+}
+
+#[test]
+pub fn sleep_too_long() {
+    let mut scheduler = fcfs(NonZeroUsize::new(6).unwrap());
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+
+    let error = SyscallResult::Error(SchedulerError::InvalidSleepDuration(usize::MAX));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Sleep(usize::MAX), timeslice - 1),
+        error
+    );
+    let wait = Syscall::WaitTimeout {
+        event: 1,
+        max: usize::MAX,
+    };
+    assert_eq!(syscall(&mut scheduler, wait, timeslice - 2), error);
+    // the process goes on running
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+}
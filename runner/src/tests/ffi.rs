@@ -0,0 +1,244 @@
+use scheduler::ffi::{
+    scheduler_free, scheduler_list, scheduler_new_round_robin, scheduler_next, scheduler_stop,
+    CDecision, CDecisionKind, CProcess, CProcessState, CSchedulerError, CStopReason, CSyscallKind,
+    CSyscallResult, CSyscallResultKind, SchedulerHandle, SCHEDULER_ERROR_INVALID_ARGUMENT,
+    SCHEDULER_ERROR_INVALID_HANDLE, SCHEDULER_ERROR_NULL_POINTER, SCHEDULER_LIST_ERROR,
+    SCHEDULER_OK,
+};
+use std::mem::MaybeUninit;
+use std::ptr;
+
+/// A system call stop reason, like the C harness fills it in.
+fn reason(syscall: CSyscallKind, arg0: usize, value: i32, remaining: usize) -> CStopReason {
+    CStopReason {
+        expired: 0,
+        syscall: syscall as u32,
+        arg0,
+        arg1: 0,
+        value,
+        remaining,
+    }
+}
+
+fn next(handle: *mut SchedulerHandle) -> CDecision {
+    let mut decision = MaybeUninit::<CDecision>::uninit();
+    let code = unsafe { scheduler_next(handle, decision.as_mut_ptr()) };
+    assert_eq!(code, SCHEDULER_OK);
+    unsafe { decision.assume_init() }
+}
+
+fn stop(handle: *mut SchedulerHandle, reason: CStopReason) -> CSyscallResult {
+    let mut result = MaybeUninit::<CSyscallResult>::uninit();
+    let code = unsafe { scheduler_stop(handle, reason, result.as_mut_ptr()) };
+    assert_eq!(code, SCHEDULER_OK);
+    unsafe { result.assume_init() }
+}
+
+fn run(pid: usize, time: usize) -> CDecision {
+    CDecision {
+        kind: CDecisionKind::Run,
+        pid,
+        time,
+    }
+}
+
+fn success() -> CSyscallResult {
+    CSyscallResult {
+        kind: CSyscallResultKind::Success,
+        pid: 0,
+        error: CSchedulerError::None,
+        value: 0,
+    }
+}
+
+fn child(pid: usize) -> CSyscallResult {
+    CSyscallResult {
+        kind: CSyscallResultKind::Pid,
+        pid,
+        ..success()
+    }
+}
+
+#[test]
+pub fn end_to_end() {
+    let handle = scheduler_new_round_robin(3, 1);
+    assert!(!handle.is_null());
+
+    assert_eq!(stop(handle, reason(CSyscallKind::Fork, 0, 0, 0)), child(1));
+    assert_eq!(next(handle), run(1, 3));
+    assert_eq!(stop(handle, reason(CSyscallKind::Fork, 0, 5, 2)), child(2));
+    assert_eq!(next(handle), run(1, 2));
+
+    let mut processes = [MaybeUninit::<CProcess>::uninit(); 4];
+    let count = unsafe { scheduler_list(handle, processes.as_mut_ptr().cast(), 4) };
+    assert_eq!(count, 2);
+    let processes = unsafe { [processes[0].assume_init(), processes[1].assume_init()] };
+    assert_eq!(
+        processes.map(|p| (p.pid, p.state, p.priority, p.parent)),
+        [
+            (1, CProcessState::Running, 0, 0),
+            (2, CProcessState::Ready, 5, 1)
+        ]
+    );
+
+    assert_eq!(stop(handle, reason(CSyscallKind::Wait, 7, 0, 1)), success());
+    let mut waiting = MaybeUninit::<CProcess>::uninit();
+    assert_eq!(
+        unsafe { scheduler_list(handle, waiting.as_mut_ptr(), 1) },
+        2
+    );
+    let waiting = unsafe { waiting.assume_init() };
+    assert_eq!((waiting.state, waiting.event), (CProcessState::Waiting, 7));
+
+    assert_eq!(next(handle), run(2, 3));
+    assert_eq!(
+        stop(handle, reason(CSyscallKind::Signal, 7, 0, 2)),
+        success()
+    );
+    assert_eq!(stop(handle, reason(CSyscallKind::Exit, 0, 0, 1)), success());
+    assert_eq!(next(handle), run(1, 3));
+    assert_eq!(stop(handle, reason(CSyscallKind::Exit, 0, 0, 2)), success());
+    assert_eq!(next(handle).kind, CDecisionKind::Done);
+
+    assert_eq!(scheduler_free(handle), SCHEDULER_OK);
+}
+
+#[test]
+pub fn expired_and_errors() {
+    let handle = scheduler_new_round_robin(2, 0);
+    stop(handle, reason(CSyscallKind::Fork, 0, 0, 0));
+    assert_eq!(next(handle), run(1, 2));
+    let expired = CStopReason {
+        expired: 1,
+        ..reason(CSyscallKind::Yield, 0, 0, 0)
+    };
+    assert_eq!(stop(handle, expired), success());
+    assert_eq!(next(handle), run(1, 2));
+    assert_eq!(
+        stop(handle, reason(CSyscallKind::Sleep, usize::MAX, 0, 1)),
+        CSyscallResult {
+            kind: CSyscallResultKind::Error,
+            error: CSchedulerError::InvalidSleepDuration,
+            value: usize::MAX,
+            ..success()
+        }
+    );
+    assert_eq!(
+        stop(handle, reason(CSyscallKind::Kill, 9, 0, 1)).kind,
+        CSyscallResultKind::NoSuchProcess
+    );
+    assert_eq!(scheduler_free(handle), SCHEDULER_OK);
+}
+
+#[test]
+pub fn zero_timeslice() {
+    assert!(scheduler_new_round_robin(0, 0).is_null());
+}
+
+#[test]
+pub fn invalid_handles() {
+    let mut decision = MaybeUninit::<CDecision>::uninit();
+    let mut result = MaybeUninit::<CSyscallResult>::uninit();
+    let fork = reason(CSyscallKind::Fork, 0, 0, 0);
+
+    let null = ptr::null_mut();
+    assert_eq!(
+        unsafe { scheduler_next(null, decision.as_mut_ptr()) },
+        SCHEDULER_ERROR_INVALID_HANDLE
+    );
+    assert_eq!(
+        unsafe { scheduler_stop(null, fork, result.as_mut_ptr()) },
+        SCHEDULER_ERROR_INVALID_HANDLE
+    );
+    assert_eq!(
+        unsafe { scheduler_list(null, ptr::null_mut(), 0) },
+        SCHEDULER_LIST_ERROR
+    );
+    assert_eq!(scheduler_free(null), SCHEDULER_ERROR_INVALID_HANDLE);
+
+    // a pointer that has never been returned by the library
+    let mut not_a_handle = 0u64;
+    let bogus = ptr::addr_of_mut!(not_a_handle).cast::<SchedulerHandle>();
+    assert_eq!(
+        unsafe { scheduler_next(bogus, decision.as_mut_ptr()) },
+        SCHEDULER_ERROR_INVALID_HANDLE
+    );
+    assert_eq!(scheduler_free(bogus), SCHEDULER_ERROR_INVALID_HANDLE);
+
+    // a handle that has been released
+    let handle = scheduler_new_round_robin(3, 1);
+    assert_eq!(scheduler_free(handle), SCHEDULER_OK);
+    assert_eq!(
+        unsafe { scheduler_stop(handle, fork, result.as_mut_ptr()) },
+        SCHEDULER_ERROR_INVALID_HANDLE
+    );
+    assert_eq!(scheduler_free(handle), SCHEDULER_ERROR_INVALID_HANDLE);
+}
+
+#[test]
+pub fn invalid_arguments() {
+    let handle = scheduler_new_round_robin(3, 1);
+    let mut result = MaybeUninit::<CSyscallResult>::uninit();
+    let fork = reason(CSyscallKind::Fork, 0, 0, 0);
+
+    assert_eq!(
+        unsafe { scheduler_next(handle, ptr::null_mut()) },
+        SCHEDULER_ERROR_NULL_POINTER
+    );
+    assert_eq!(
+        unsafe { scheduler_stop(handle, fork, ptr::null_mut()) },
+        SCHEDULER_ERROR_NULL_POINTER
+    );
+    assert_eq!(
+        unsafe { scheduler_list(handle, ptr::null_mut(), 1) },
+        SCHEDULER_LIST_ERROR
+    );
+
+    stop(handle, fork);
+    assert_eq!(unsafe { scheduler_list(handle, ptr::null_mut(), 0) }, 1);
+    next(handle);
+    assert_eq!(
+        unsafe {
+            scheduler_stop(
+                handle,
+                reason(CSyscallKind::Kill, 0, 0, 1),
+                result.as_mut_ptr(),
+            )
+        },
+        SCHEDULER_ERROR_INVALID_ARGUMENT
+    );
+    assert_eq!(scheduler_free(handle), SCHEDULER_OK);
+}
+
+#[test]
+pub fn invalid_stop_reasons() {
+    let handle = scheduler_new_round_robin(3, 0);
+    stop(handle, reason(CSyscallKind::Fork, 0, 0, 0));
+    next(handle);
+
+    let invalid = [
+        CStopReason {
+            expired: 2,
+            ..reason(CSyscallKind::Yield, 0, 0, 1)
+        },
+        CStopReason {
+            syscall: CSyscallKind::ExitWith as u32 + 1,
+            ..reason(CSyscallKind::Yield, 0, 0, 1)
+        },
+        reason(CSyscallKind::Fork, 0, 128, 1),
+        reason(CSyscallKind::SetPriority, 0, -129, 1),
+    ];
+    let mut result = MaybeUninit::<CSyscallResult>::uninit();
+    for reason in invalid {
+        assert_eq!(
+            unsafe { scheduler_stop(handle, reason, result.as_mut_ptr()) },
+            SCHEDULER_ERROR_INVALID_ARGUMENT,
+            "{reason:?}"
+        );
+    }
+
+    // the bounds of an int8_t are valid priorities
+    assert_eq!(stop(handle, reason(CSyscallKind::SetPriority, 0, -128, 2)), success());
+    assert_eq!(stop(handle, reason(CSyscallKind::Fork, 0, 127, 1)), child(2));
+    assert_eq!(scheduler_free(handle), SCHEDULER_OK);
+}
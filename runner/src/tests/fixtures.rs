@@ -0,0 +1,135 @@
+use processor::fixtures::{check, record, Fixture, FixtureError, SchedulerDescriptor};
+use processor::replay::{Difference, Divergence, Step};
+use processor::workload::Workload;
+use scheduler::{Pid, SchedulerConfig, SchedulerKind, SchedulingDecision};
+use std::env;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+const WORKLOAD: &str = "\
+fork prio=1
+    run 5
+    wait 1
+    run 2
+fork prio=2
+    run 3
+    sleep 4
+    run 1
+    signal 1
+run 4
+yield
+run 2
+sleep 9
+";
+
+fn workload() -> Workload {
+    WORKLOAD.parse().unwrap()
+}
+
+fn descriptor(kind: SchedulerKind) -> SchedulerDescriptor {
+    SchedulerDescriptor::new(kind, SchedulerConfig::new(NonZeroUsize::new(3).unwrap(), 1))
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("scheduler-fixture-{}.json", name))
+}
+
+#[test]
+pub fn record_and_check() {
+    let path = temp_path("clean");
+    let fixture = record(&workload(), descriptor(SchedulerKind::RoundRobin), &path).unwrap();
+
+    assert_eq!(Fixture::read(&path).unwrap(), fixture);
+    let diff = check(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(diff.is_clean(), "{diff}");
+}
+
+#[test]
+pub fn perturbed_fixture() {
+    let path = temp_path("perturbed");
+    let mut fixture = record(&workload(), descriptor(SchedulerKind::Cfs), &path).unwrap();
+
+    // the third decision of the fixture runs another process
+    let (index, recorded) = fixture
+        .recording
+        .steps
+        .iter()
+        .enumerate()
+        .filter_map(|(index, step)| match step {
+            Step::Next(decision @ SchedulingDecision::Run { .. }) => Some((index, *decision)),
+            _ => None,
+        })
+        .nth(2)
+        .unwrap();
+    let SchedulingDecision::Run { timeslice, .. } = recorded else {
+        unreachable!();
+    };
+    let perturbed = SchedulingDecision::Run {
+        pid: Pid::new(99),
+        timeslice,
+    };
+    fixture.recording.steps[index] = Step::Next(perturbed);
+    fixture.write(&path).unwrap();
+
+    let diff = check(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        diff.divergence,
+        Some(Divergence {
+            index,
+            difference: Difference::Pid {
+                expected: perturbed,
+                got: recorded
+            }
+        })
+    );
+    // the simulation itself has not changed
+    assert!(diff.metrics.is_empty());
+}
+
+#[test]
+pub fn changed_metrics() {
+    let path = temp_path("metrics");
+    let mut fixture = record(&workload(), descriptor(SchedulerKind::Fcfs), &path).unwrap();
+    fixture.metrics.makespan += 2;
+    fixture.write(&path).unwrap();
+
+    let diff = check(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(diff.divergence, None);
+    assert_eq!(diff.metrics.len(), 1);
+    assert_eq!(diff.metrics[0].delta(), -2.0);
+    assert!(diff.to_string().starts_with("makespan: "));
+}
+
+#[test]
+pub fn other_version() {
+    let path = temp_path("version");
+    let mut fixture = record(&workload(), descriptor(SchedulerKind::Fcfs), &path).unwrap();
+    fixture.version += 1;
+    fixture.write(&path).unwrap();
+
+    let error = check(&path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(error, FixtureError::Version(Some(2))));
+}
+
+/// Checks the fixtures stored in `fixtures/versions`, recorded with an
+/// earlier version of the schedulers, or stores new ones if `WRITE_OUTPUT`
+/// is set. Run it before merging a change to a scheduler, with
+/// `cargo test -- --ignored versions`.
+#[test]
+#[ignore]
+pub fn versions() {
+    for kind in SchedulerKind::ALL {
+        let path = format!("fixtures/versions/{kind}.json");
+        if env::var("WRITE_OUTPUT").is_ok() {
+            record(&workload(), descriptor(kind), &path).unwrap();
+        } else {
+            let diff = check(&path).unwrap();
+            assert!(diff.is_clean(), "the {kind} scheduler has changed:\n{diff}");
+        }
+    }
+}
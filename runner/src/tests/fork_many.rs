@@ -0,0 +1,45 @@
+use scheduler::{Pid, ProcessState, RoundRobinScheduler, Scheduler, StopReason, Syscall};
+use std::num::NonZeroUsize;
+
+use super::dispatch;
+
+#[test]
+pub fn five_children_at_once() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+
+    let children = scheduler.fork_many(5, 2, 7);
+    assert_eq!(children, (2..7).map(Pid::new).collect::<Vec<_>>());
+
+    // the parent is charged for a single system call
+    let processes = scheduler.list();
+    assert_eq!(processes.len(), 6);
+    assert_eq!(processes[0].timings(), (3, 1, 2));
+    for child in &processes[1..] {
+        assert_eq!(child.state(), ProcessState::Ready);
+        assert_eq!(child.parent(), Some(Pid::new(1)));
+        assert_eq!(child.priority(), 2);
+        assert_eq!(child.timings(), (0, 0, 0));
+    }
+
+    // the parent keeps running, then the children run in their order
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 7));
+    scheduler.stop(StopReason::expired());
+    for child in children {
+        assert_eq!(dispatch(&mut scheduler).0, child);
+        scheduler.stop(StopReason::expired());
+    }
+}
+
+#[test]
+pub fn past_the_limit() {
+    let mut scheduler =
+        RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1).max_processes(4);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+
+    let children = scheduler.fork_many(5, 0, 9);
+    assert_eq!(children, (2..5).map(Pid::new).collect::<Vec<_>>());
+    assert_eq!(scheduler.list().len(), 4);
+}
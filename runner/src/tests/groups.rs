@@ -0,0 +1,118 @@
+use scheduler::{
+    round_robin, AdoptSpec, AdoptState, Pid, ProcessState, Processes, RoundRobinScheduler,
+    Scheduler, SchedulingDecision, StopReason, Syscall, SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+fn processes(scheduler: &mut impl Scheduler) -> Vec<(usize, ProcessState, String)> {
+    let mut processes = scheduler
+        .list()
+        .iter()
+        .map(|p| (p.pid().get(), p.state(), p.extra()))
+        .collect::<Vec<_>>();
+    processes.sort_by_key(|(pid, _, _)| *pid);
+    processes
+}
+
+#[test]
+pub fn signal_one_group() {
+    let mut scheduler = round_robin(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 9);
+    syscall(&mut scheduler, Syscall::SetGroup(1), 8);
+    // the child inherits the group of its parent
+    syscall(&mut scheduler, Syscall::Fork(0), 7);
+    syscall(&mut scheduler, Syscall::Yield, 6);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Wait(5), 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(3));
+    syscall(&mut scheduler, Syscall::Wait(5), 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::SignalGroup(7, 5), 5),
+        SyscallResult::Success
+    );
+    syscall(&mut scheduler, Syscall::SignalGroup(1, 5), 4);
+    let waiting = ProcessState::Waiting { event: Some(5) };
+    assert_eq!(
+        processes(&mut scheduler),
+        vec![
+            (1, ProcessState::Running, "group=1".to_string()),
+            (2, waiting, "group=0".to_string()),
+            (3, ProcessState::Ready, "group=1".to_string()),
+        ]
+    );
+}
+
+#[test]
+pub fn signal_group_wakes_all_members() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    assert_eq!(scheduler.group(Pid::new(1)), Some(0));
+
+    // process 2 stays in group 0, processes 3 to 5 are forked in group 2
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 9);
+    syscall(&mut scheduler, Syscall::SetGroup(2), 8);
+    for remaining in [7, 6, 5] {
+        syscall(&mut scheduler, Syscall::Fork(0), remaining);
+    }
+    syscall(&mut scheduler, Syscall::SetGroup(0), 4);
+    syscall(&mut scheduler, Syscall::Wait(3), 3);
+    for pid in 2..6 {
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(pid));
+        syscall(&mut scheduler, Syscall::Wait(3), 9);
+    }
+    assert_eq!(scheduler.group(Pid::new(4)), Some(2));
+    assert_eq!(scheduler.group(Pid::new(6)), None);
+
+    scheduler.signal_group(2, 3);
+    let waiting = ProcessState::Waiting { event: Some(3) };
+    let ready = ProcessState::Ready;
+    let states = processes(&mut scheduler)
+        .into_iter()
+        .map(|(pid, state, _)| (pid, state))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        vec![(1, waiting), (2, waiting), (3, ready), (4, ready), (5, ready)]
+    );
+    for pid in 3..6 {
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(pid));
+        syscall(&mut scheduler, Syscall::Exit, 9);
+    }
+    assert_eq!(scheduler.next(), SchedulingDecision::Deadlock);
+}
+
+#[test]
+pub fn adopt_into_group() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let spec = AdoptSpec {
+        pid: None,
+        priority: 0,
+        state: AdoptState::Ready,
+        timings: (0, 0, 0),
+        group: 4,
+    };
+    let pid = scheduler.adopt(spec).unwrap();
+    assert_eq!(scheduler.group(pid), Some(4));
+
+    // the adopted process is woken up by a signal to its group only
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Yield, 9);
+    assert_eq!(dispatch(&mut scheduler).0, pid);
+    syscall(&mut scheduler, Syscall::Wait(1), 9);
+    scheduler.signal_group(0, 1);
+    assert_eq!(
+        scheduler.get(pid).unwrap().state(),
+        ProcessState::Waiting { event: Some(1) }
+    );
+    scheduler.signal_group(4, 1);
+    assert_eq!(scheduler.get(pid).unwrap().state(), ProcessState::Ready);
+}
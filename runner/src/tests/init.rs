@@ -0,0 +1,79 @@
+use scheduler::{
+    round_robin, Pid, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason, Syscall, SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn first_fork() {
+    // the time left of a running process is ignored when there is none
+    for remaining in [0, 2, usize::MAX] {
+        let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+
+        let result = scheduler.stop(StopReason::Syscall {
+            syscall: Syscall::Fork(0),
+            remaining,
+        });
+
+        assert_eq!(result, SyscallResult::Pid(Pid::new(1)));
+        assert_eq!(scheduler.statistics().total_time, 0);
+        let processes = scheduler.list();
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].pid(), 1);
+        assert_eq!(processes[0].timings(), (0, 0, 0));
+        assert_eq!(
+            scheduler.next(),
+            SchedulingDecision::Run {
+                pid: Pid::new(1),
+                timeslice: NonZeroUsize::new(3).unwrap()
+            }
+        );
+    }
+}
+
+#[test]
+pub fn exit_last_process() {
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, timeslice - 1);
+
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+}
+
+#[test]
+pub fn exit_with_ready_child() {
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+    let (pid, _) = dispatch(&mut scheduler);
+    assert_eq!(pid, 1);
+    syscall(&mut scheduler, Syscall::Exit, timeslice - 2);
+
+    assert_eq!(scheduler.next(), SchedulingDecision::Panic);
+}
+
+#[test]
+pub fn exit_with_sleeping_child() {
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+    dispatch(&mut scheduler);
+    scheduler.stop(StopReason::expired());
+
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, 2);
+    syscall(&mut scheduler, Syscall::Sleep(5), timeslice - 1);
+
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, 1);
+    syscall(&mut scheduler, Syscall::Exit, timeslice - 1);
+
+    assert_eq!(scheduler.next(), SchedulingDecision::Panic);
+}
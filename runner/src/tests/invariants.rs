@@ -0,0 +1,113 @@
+use scheduler::testing::{decode, play_to_end, Action, Harness, Op, Violation};
+use scheduler::{
+    make_scheduler, Pid, Process, RoundRobinScheduler, Scheduler, SchedulerConfig, SchedulerKind,
+    SchedulingDecision, StopReason, SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+#[test]
+pub fn round_robin() {
+    for (timeslice, minimum_remaining) in [(1, 0), (3, 1), (5, 3)] {
+        let timeslice = NonZeroUsize::new(timeslice).unwrap();
+        if let Err(failure) =
+            Harness::new().check(|| RoundRobinScheduler::new(timeslice, minimum_remaining))
+        {
+            panic!("{}", failure);
+        }
+    }
+}
+
+#[test]
+pub fn every_kind() {
+    for kind in SchedulerKind::ALL {
+        for (timeslice, minimum_remaining) in [(1, 0), (3, 1), (5, 3)] {
+            let config =
+                SchedulerConfig::new(NonZeroUsize::new(timeslice).unwrap(), minimum_remaining)
+                    .aging(NonZeroUsize::new(4).unwrap());
+            if let Err(failure) = Harness::new().check(|| make_scheduler(kind, config)) {
+                panic!("{} {:?}: {}", kind, config, failure);
+            }
+        }
+    }
+}
+
+/// A round robin scheduler that is done instead of sleeping
+/// when all its processes sleep.
+struct DoneWhenIdle(RoundRobinScheduler);
+
+impl Scheduler for DoneWhenIdle {
+    fn next(&mut self) -> SchedulingDecision {
+        match self.0.next() {
+            SchedulingDecision::Sleep(_) => SchedulingDecision::Done,
+            decision => decision,
+        }
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        self.0.peek_next()
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        self.0.stop(reason)
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.0.list()
+    }
+}
+
+#[test]
+pub fn shrinks_to_minimal_workload() {
+    let failure = Harness::new()
+        .check(|| DoneWhenIdle(RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1)))
+        .unwrap_err();
+
+    assert_eq!(
+        failure.violation,
+        Violation::DoneWithProcesses(vec![Pid::new(1)])
+    );
+    assert_eq!(failure.actions, vec![Action::new(1, Op::Sleep(1))]);
+    assert_eq!(
+        failure.to_string(),
+        format!(
+            "the scheduler is done but lists [1] (seed {}), after the calls:\n  \
+             stop: Syscall Fork(0), remaining 0 -> Pid(1)\n  \
+             next: Run 1 for 3 slices\n  \
+             stop: Syscall Sleep(1), remaining 2 -> Success\n  \
+             next: Done, no more processes\n",
+            failure.seed
+        )
+    );
+}
+
+#[test]
+pub fn decoded_workload_finishes() {
+    let actions = decode(&[
+        1, 0, 0, // fork
+        3, 4, 255, // wait for event 1, using more than the timeslice
+        2, 20, 1, // sleep for 5
+        1, 0, 1, // fork
+        3, 1, 0, // wait for event 1
+        5, 0, 0, // yield
+        7, // ignored
+    ]);
+    assert_eq!(
+        actions,
+        vec![
+            Action::new(1, Op::Fork(0)),
+            Action::new(256, Op::Wait(1)),
+            Action::new(2, Op::Sleep(5)),
+            Action::new(2, Op::Fork(0)),
+            Action::new(1, Op::Wait(1)),
+            Action::new(1, Op::Yield),
+        ]
+    );
+
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+    // the waiting processes are signaled and every process exits
+    assert_eq!(
+        play_to_end(&mut scheduler, &actions),
+        Ok(SchedulingDecision::Done)
+    );
+    assert_eq!(scheduler.validate(), Ok(()));
+}
@@ -0,0 +1,94 @@
+use scheduler::{
+    round_robin, ProcessSnapshot, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason,
+    Syscall, TimelineScheduler,
+};
+use serde_json::{json, Value};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn process_snapshot() {
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(2)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Wait(7), 1);
+
+    let snapshots = ProcessSnapshot::all(&mut scheduler);
+    let init: Value = serde_json::from_str(&snapshots[0].to_json_string()).unwrap();
+    assert_eq!(init["pid"], 1);
+    assert_eq!(init["state"], json!({ "Waiting": { "event": 7 } }));
+    assert_eq!(init["priority"], 2);
+    assert_eq!(init["timings"], json!([2, 2, 0]));
+    assert_eq!(init["extra"], "group=0");
+
+    let child: Value = serde_json::from_str(&snapshots[1].to_json_string()).unwrap();
+    assert_eq!(child["state"], "Ready");
+    assert_eq!(child["timings"], json!([1, 0, 0]));
+}
+
+#[test]
+pub fn timeline_and_statistics() {
+    let mut scheduler =
+        TimelineScheduler::new(RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1));
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(2), 1);
+    scheduler.next();
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, 0);
+
+    let timeline: Value = serde_json::from_str(&scheduler.timeline().to_json_string()).unwrap();
+    assert_eq!(
+        timeline,
+        json!([
+            { "start": 0, "end": 2, "what": { "Running": 1 } },
+            { "start": 2, "end": 4, "what": "Sleeping" },
+            { "start": 4, "end": 5, "what": { "Running": 1 } },
+        ])
+    );
+
+    let statistics = scheduler.into_inner().statistics();
+    let statistics: Value = serde_json::from_str(&statistics.to_json_string()).unwrap();
+    assert_eq!(statistics["total_time"], 5);
+    assert_eq!(statistics["idle_time"], 2);
+    assert_eq!(statistics["completed"], 1);
+}
+
+#[test]
+pub fn round_robin_checkpoint() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    for priority in 1..4 {
+        syscall(&mut scheduler, Syscall::Fork(priority), 3 - priority as usize);
+    }
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(4), 1);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Wait(3), 2);
+
+    let checkpoint = serde_json::to_string(&scheduler).unwrap();
+    let mut restored: RoundRobinScheduler = serde_json::from_str(&checkpoint).unwrap();
+    assert_eq!(
+        ProcessSnapshot::all(&mut restored),
+        ProcessSnapshot::all(&mut scheduler)
+    );
+
+    // both schedulers take the same decisions from here on
+    for _ in 0..6 {
+        let decision = scheduler.next();
+        assert_eq!(restored.next(), decision);
+        if let SchedulingDecision::Run { pid, .. } = decision {
+            let reason = if pid == 3 {
+                StopReason::syscall(Syscall::Signal(3))
+            } else {
+                StopReason::Expired
+            };
+            assert_eq!(restored.stop(reason), scheduler.stop(reason));
+        }
+    }
+    assert_eq!(restored.statistics(), scheduler.statistics());
+}
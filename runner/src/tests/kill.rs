@@ -0,0 +1,232 @@
+use scheduler::{
+    round_robin, CfsScheduler, Pid, ProcessState, RoundRobinScheduler, Scheduler,
+    SchedulerSnapshot, SchedulingDecision, StopReason, Syscall, SyscallResult, TargetPolicy,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+fn pids(scheduler: &mut impl Scheduler) -> Vec<usize> {
+    let mut pids = scheduler
+        .list()
+        .iter()
+        .map(|p| p.pid().get())
+        .collect::<Vec<_>>();
+    pids.sort();
+    pids
+}
+
+#[test]
+pub fn kill_sleeping_process() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::Yield, 3);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Sleep(2), 4);
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(2)), 2),
+        SyscallResult::Success
+    );
+    assert_eq!(pids(&mut scheduler), vec![1]);
+
+    // the killed process does not wake up anymore
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+    syscall(&mut scheduler, Syscall::Sleep(10), 1);
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(10).unwrap())
+    );
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+}
+
+#[test]
+pub fn kill_missing_process() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(2)), 4),
+        SyscallResult::NoSuchProcess
+    );
+    assert_eq!(scheduler.list()[0].timings(), (1, 1, 0));
+}
+
+#[test]
+pub fn kill_self() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(1)), 4),
+        SyscallResult::Success
+    );
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+}
+
+#[test]
+pub fn kill_init() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1)
+        .target_policy(TargetPolicy::AllowAll);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::Yield, 3);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Kill(Pid::new(1)), 4);
+    assert_eq!(scheduler.next(), SchedulingDecision::Panic);
+}
+
+#[test]
+pub fn kill_waiting_process() {
+    let mut scheduler = CfsScheduler::new(NonZeroUsize::new(4).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 3);
+    syscall(&mut scheduler, Syscall::Yield, 2);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Wait(1), 1);
+
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(2)), 1),
+        SyscallResult::Success
+    );
+    assert_eq!(pids(&mut scheduler), vec![1]);
+
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Exit, 0);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+}
+
+/// Process 1 runs, 2 is ready, 3 sleeps and 4 waits for an event.
+fn processes_in_every_state() -> RoundRobinScheduler {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 9);
+    syscall(&mut scheduler, Syscall::Fork(0), 8);
+    syscall(&mut scheduler, Syscall::Fork(0), 7);
+    syscall(&mut scheduler, Syscall::Yield, 6);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Yield, 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(3));
+    syscall(&mut scheduler, Syscall::Sleep(100), 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(4));
+    syscall(&mut scheduler, Syscall::Wait(1), 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    scheduler
+}
+
+#[test]
+pub fn kill_in_every_state() {
+    for (pid, state) in [
+        (1, ProcessState::Running),
+        (2, ProcessState::Ready),
+        (3, ProcessState::Waiting { event: None }),
+        (4, ProcessState::Waiting { event: Some(1) }),
+    ] {
+        let mut scheduler = processes_in_every_state();
+        let timings = scheduler.list()[0].timings();
+        let process = scheduler.list()[pid - 1].state();
+        assert_eq!(process, state);
+
+        assert_eq!(scheduler.kill(Pid::new(pid)), SyscallResult::Success);
+        let mut left = pids(&mut scheduler);
+        left.push(pid);
+        left.sort();
+        assert_eq!(left, vec![1, 2, 3, 4]);
+        if pid != 1 {
+            // killing a process takes no time
+            assert_eq!(scheduler.list()[0].timings(), timings);
+        }
+    }
+}
+
+#[test]
+pub fn kill_running_process() {
+    let mut scheduler = processes_in_every_state();
+    syscall(&mut scheduler, Syscall::Yield, 5);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+
+    // the next ready process is dispatched, as if process 2 had exited
+    scheduler.kill(Pid::new(2));
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    assert_eq!(scheduler.kill(Pid::new(2)), SyscallResult::NoSuchProcess);
+
+    scheduler.kill(Pid::new(1));
+    assert_eq!(scheduler.next(), SchedulingDecision::Panic);
+}
+
+#[test]
+pub fn kill_queued_process() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::Fork(0), 3);
+
+    // process 2 is at the front of the ready queue once process 1 expires
+    assert_eq!(scheduler.kill(Pid::new(2)), SyscallResult::Success);
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(scheduler.peek_next(), scheduler.clone().next());
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(3));
+    assert_eq!(scheduler.validate(), Ok(()));
+}
+
+#[test]
+pub fn stale_queue_entries() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::Fork(0), 3);
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    scheduler.stop(StopReason::Expired);
+
+    // processes 2 and 3 are removed from the process table, but they are
+    // left at the front of the ready queue
+    let mut state = serde_json::to_value(scheduler.snapshot()).unwrap();
+    state["processes"]
+        .as_array_mut()
+        .unwrap()
+        .retain(|process| process["pid"] != 2 && process["pid"] != 3);
+    let mut scheduler =
+        RoundRobinScheduler::restore(serde_json::from_value::<SchedulerSnapshot>(state).unwrap());
+
+    assert_eq!(scheduler.peek_next(), scheduler.clone().next());
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(4));
+    assert_eq!(scheduler.validate(), Ok(()));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+}
+
+#[test]
+pub fn kill_self_is_exit() {
+    let stop_with = |syscall_made: Syscall| {
+        let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+        scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+        dispatch(&mut scheduler);
+        syscall(&mut scheduler, Syscall::Fork(0), 4);
+        syscall(&mut scheduler, Syscall::Yield, 3);
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+        syscall(&mut scheduler, syscall_made, 2);
+        scheduler
+    };
+    let exited = stop_with(Syscall::Exit);
+    let killed = stop_with(Syscall::Kill(Pid::new(2)));
+
+    // the process is counted as completed, with exit code 0
+    assert_eq!(killed.statistics(), exited.statistics());
+    assert_eq!(killed.statistics().completed, 1);
+    assert_eq!(killed.exit_code(Pid::new(2)), Some(0));
+}
@@ -0,0 +1,81 @@
+use scheduler::{Pid, RoundRobinScheduler, Scheduler, StopReason, Syscall};
+use std::env;
+use std::num::NonZeroUsize;
+use std::time::Instant;
+
+use super::{dispatch, syscall};
+
+/// The numbers of live processes that the load test compares.
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// The decisions timed in every round, and the number of rounds.
+const DECISIONS: usize = 5_000;
+const ROUNDS: usize = 9;
+
+/// How much slower a decision may get from a size to the next one, ten
+/// times larger. A scan over the processes would make it about ten times
+/// slower, while the noise of a loaded machine stays well below this.
+const GROWTH: f64 = 4.0;
+
+/// A round robin scheduler with `live` processes, all ready.
+fn scheduler(live: usize) -> RoundRobinScheduler {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(4).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+    scheduler.fork_many(live - 1, 0, timeslice - 1);
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(scheduler.list().len(), live);
+    scheduler
+}
+
+/// Takes `DECISIONS` decisions and returns the time of one of them, in
+/// nanoseconds. A quarter of the processes that run exit after forking
+/// a replacement, so the number of live processes does not change.
+fn time_decisions(scheduler: &mut RoundRobinScheduler) -> f64 {
+    let start = Instant::now();
+    for decision in 0..DECISIONS {
+        let (pid, timeslice) = dispatch(scheduler);
+        if decision % 4 == 0 && pid != Pid::new(1) {
+            syscall(scheduler, Syscall::Fork(0), timeslice - 1);
+            syscall(scheduler, Syscall::Exit, timeslice - 2);
+        } else {
+            scheduler.stop(StopReason::Expired);
+        }
+    }
+    start.elapsed().as_nanos() as f64 / DECISIONS as f64
+}
+
+/// The median of the times of one decision over `ROUNDS` rounds, which
+/// leaves out the rounds slowed down by the rest of the machine.
+fn median_decision(live: usize) -> f64 {
+    let mut scheduler = scheduler(live);
+    let mut times = (0..ROUNDS)
+        .map(|_| time_decisions(&mut scheduler))
+        .collect::<Vec<_>>();
+    times.sort_by(f64::total_cmp);
+    times[ROUNDS / 2]
+}
+
+/// Checks that the time of a decision does not grow with the number of
+/// live processes as a scan over them would.
+///
+/// The test takes a while, so it only runs when `LOAD_TEST` is set:
+/// `LOAD_TEST=1 cargo test --release load`.
+#[test]
+pub fn decisions_scale() {
+    if env::var("LOAD_TEST").is_err() {
+        return;
+    }
+    let medians = SIZES.map(median_decision);
+    for (sizes, times) in SIZES.windows(2).zip(medians.windows(2)) {
+        let growth = times[1] / times[0];
+        assert!(
+            growth < GROWTH,
+            "a decision takes {:.0} ns with {} processes and {:.0} ns with {}",
+            times[0],
+            sizes[0],
+            times[1],
+            sizes[1]
+        );
+    }
+}
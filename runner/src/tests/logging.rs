@@ -0,0 +1,75 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use scheduler::{round_robin, LoggingScheduler, Scheduler, StopReason, Syscall};
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use super::{dispatch, syscall};
+
+/// A logger that keeps the messages of the scheduler.
+struct Capture(Mutex<Vec<(Level, String)>>);
+
+impl Log for Capture {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target().starts_with("scheduler")
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let message = record.args().to_string();
+            self.0.lock().unwrap().push((record.level(), message));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(vec![]));
+
+#[test]
+pub fn two_processes() {
+    log::set_logger(&CAPTURE).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    let mut scheduler = LoggingScheduler::new(round_robin(NonZeroUsize::new(3).unwrap(), 1));
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 1);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(2), 0);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, 1);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, 2);
+    scheduler.next();
+
+    let messages = CAPTURE.0.lock().unwrap();
+    let expected = [
+        (
+            Level::Trace,
+            "time 0: Syscall Fork(0), remaining 0, no running process",
+        ),
+        (Level::Debug, "time 0: Run 1 for 3 slices"),
+        (
+            Level::Trace,
+            "time 2: Syscall Fork(0), remaining 1, process 1 RUNNING -> RUNNING",
+        ),
+        (Level::Debug, "time 2: Run 1 for 1 slices"),
+        (
+            Level::Trace,
+            "time 3: Syscall Sleep(2), remaining 0, process 1 RUNNING -> SLEEP",
+        ),
+        (Level::Debug, "time 3: Run 2 for 3 slices"),
+        (
+            Level::Trace,
+            "time 5: Syscall Exit, remaining 1, process 2 RUNNING -> EXITED",
+        ),
+        (Level::Debug, "time 5: Run 1 for 3 slices"),
+        (
+            Level::Trace,
+            "time 6: Syscall Exit, remaining 2, process 1 RUNNING -> EXITED",
+        ),
+        (Level::Debug, "time 6: Done, no more processes"),
+    ]
+    .map(|(level, message)| (level, message.to_string()));
+    assert_eq!(messages[..], expected);
+}
@@ -0,0 +1,30 @@
+use scheduler::{Pid, RoundRobinScheduler, Scheduler, StopReason, Syscall, SyscallResult};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn fork_past_the_limit() {
+    let mut scheduler =
+        RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1).max_processes(2);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Fork(0), 4),
+        SyscallResult::Pid(Pid::new(2))
+    );
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Fork(0), 3),
+        SyscallResult::MaxProcessesReached
+    );
+    assert_eq!(scheduler.list().len(), 2);
+
+    // the failed fork still takes a time unit
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 3));
+    syscall(&mut scheduler, Syscall::Kill(Pid::new(2)), 2);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Fork(0), 1),
+        SyscallResult::Pid(Pid::new(3))
+    );
+}
@@ -4,7 +4,7 @@ use scheduler::cfs;
 use scheduler::priority_queue;
 #[cfg(not(any(feature = "priority-queue", feature = "cfs")))]
 use scheduler::round_robin;
-use scheduler::Scheduler;
+use scheduler::{Pid, Scheduler, SchedulingDecision, StopReason, Syscall, SyscallResult};
 
 use std::env;
 use std::fs;
@@ -13,11 +13,79 @@ use processor::format_logs;
 use processor::Log;
 use std::num::NonZeroUsize;
 
+mod accounting;
+mod adaptive;
+mod adopt;
+mod builder;
+mod burst;
+mod cfs;
+mod checked;
+mod cli;
+mod clone;
+mod compare;
+mod cores;
 mod deadlock;
+mod deadlocked;
+mod done;
+mod drain;
+mod dump;
+mod equality;
+mod event_bus;
+mod events;
+mod exit_code;
+mod export;
+mod factory;
+mod fallback;
+mod fcfs;
+mod fixtures;
+mod fork_many;
+mod ffi;
+mod groups;
+mod init;
+mod invariants;
+mod json;
+mod kill;
+mod load;
+mod logging;
+mod max_processes;
+mod no_std;
+mod observer;
+mod orphans;
 mod panic;
+mod pcb;
+mod peek;
+mod permissions;
+mod priority;
+mod process_table;
+mod processes;
+#[cfg(feature = "python")]
+mod python;
+mod queue;
+mod quantum;
+mod random_calls;
+mod replay;
+mod signal_policy;
 mod simple;
+mod simulator;
+mod snapshot;
+mod srtf;
+mod stride;
+mod threads;
+mod timings;
+mod timeline;
+mod trace;
+mod tui;
+mod validate;
+mod sleep;
+mod stats;
 mod wait_and_signal;
+mod wait_pid;
+mod wait_timeout;
+mod waiters;
+mod warmup;
 mod workers;
+mod workload;
+mod yield_now;
 
 fn write_logs(folder: &str, name: &str, logs: &str) {
     let (timeslice, remaining, cpu_slices) = arguments();
@@ -40,7 +108,7 @@ fn read_logs(folder: &str, name: &str) -> String {
 }
 
 fn run(folder: &str, name: &str, logs: &[Log]) {
-    let output = format_logs(&logs);
+    let output = format_logs(logs);
 
     if env::var("WRITE_OUTPUT").is_ok() {
         write_logs(folder, name, &output);
@@ -53,6 +121,21 @@ fn run(folder: &str, name: &str, logs: &[Log]) {
     }
 }
 
+/// Asks the scheduler for the next decision and returns the PID and
+/// the timeslice of the process that it has scheduled.
+fn dispatch(scheduler: &mut impl Scheduler) -> (Pid, usize) {
+    match scheduler.next() {
+        SchedulingDecision::Run { pid, timeslice } => (pid, timeslice.get()),
+        decision => panic!("expected a process to be scheduled, got {decision}"),
+    }
+}
+
+/// Informs the scheduler that the running process has issued `syscall`
+/// with `remaining` time units left from its timeslice.
+fn syscall(scheduler: &mut impl Scheduler, syscall: Syscall, remaining: usize) -> SyscallResult {
+    scheduler.stop(StopReason::Syscall { syscall, remaining })
+}
+
 fn arguments() -> (usize, usize, usize) {
     let timeslice = env::var("TIMESLICE")
         .unwrap_or("3".to_string())
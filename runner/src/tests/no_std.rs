@@ -0,0 +1,31 @@
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// Builds the scheduler library with `features`, without the default ones.
+fn build_without_std(features: &str) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let status = Command::new(env::var("CARGO").unwrap_or_else(|_| String::from("cargo")))
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(root.join("scheduler").join("Cargo.toml"))
+        .arg("--no-default-features")
+        .arg("--features")
+        .arg(features)
+        // a separate directory, so that the build does not wait for the one running the tests
+        .arg("--target-dir")
+        .arg(root.join("target").join("no_std"))
+        .status()
+        .expect("cargo should start");
+    assert!(status.success(), "the build with `{}` has failed", features);
+}
+
+#[test]
+pub fn builds_without_std() {
+    build_without_std("");
+}
+
+#[test]
+pub fn builds_without_std_with_features() {
+    build_without_std("serde,log");
+}
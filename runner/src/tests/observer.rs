@@ -0,0 +1,68 @@
+use processor::simulator::Simulator;
+use scheduler::{
+    round_robin, ObservedEvent, ObservedScheduler, Pid, ProcessState, StopReason, Syscall,
+    VecObserver,
+};
+use std::num::NonZeroUsize;
+
+#[test]
+pub fn workload_events() {
+    let mut scheduler = ObservedScheduler::new(round_robin(NonZeroUsize::new(3).unwrap(), 1));
+    let observer = VecObserver::new();
+    scheduler.add_observer(Box::new(observer.clone()));
+
+    let workload = "\
+fork
+    run 1
+sleep 4
+";
+    Simulator::new(Box::new(scheduler), workload.parse().unwrap())
+        .run()
+        .unwrap();
+
+    let (init, child) = (Pid::new(1), Pid::new(2));
+    let sleeping = ProcessState::Waiting { event: None };
+    let timeslice = |timeslice| NonZeroUsize::new(timeslice).unwrap();
+    let stop = |pid, syscall, remaining, now| ObservedEvent::Stop {
+        pid,
+        reason: StopReason::Syscall { syscall, remaining },
+        now,
+    };
+    let state_change = |pid, old, new| ObservedEvent::StateChange { pid, old, new };
+    let dispatch = |pid, slice, now| ObservedEvent::Dispatch {
+        pid,
+        timeslice: timeslice(slice),
+        now,
+    };
+    assert_eq!(
+        observer.events(),
+        vec![
+            stop(None, Syscall::Fork(0), 0, 0),
+            ObservedEvent::Fork {
+                parent: None,
+                child: init
+            },
+            state_change(init, ProcessState::Ready, ProcessState::Running),
+            dispatch(init, 3, 0),
+            stop(Some(init), Syscall::Fork(0), 2, 1),
+            ObservedEvent::Fork {
+                parent: Some(init),
+                child
+            },
+            dispatch(init, 2, 1),
+            stop(Some(init), Syscall::Sleep(4), 1, 2),
+            state_change(init, ProcessState::Running, sleeping),
+            state_change(child, ProcessState::Ready, ProcessState::Running),
+            dispatch(child, 3, 2),
+            stop(Some(child), Syscall::Exit, 1, 4),
+            ObservedEvent::Exit { pid: child },
+            // the scheduler sleeps until process 1 wakes up
+            state_change(init, sleeping, ProcessState::Ready),
+            state_change(init, ProcessState::Ready, ProcessState::Running),
+            // with the time it had left before sleeping
+            dispatch(init, 1, 6),
+            stop(Some(init), Syscall::Exit, 0, 7),
+            ObservedEvent::Exit { pid: init },
+        ]
+    );
+}
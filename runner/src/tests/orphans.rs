@@ -0,0 +1,55 @@
+use scheduler::{Pid, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason, Syscall};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+/// Builds the tree 1 -> 2 -> 3 -> 4, process 2 exits and process 1 runs next.
+fn exit_middle(scheduler: &mut RoundRobinScheduler) {
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(scheduler);
+    syscall(scheduler, Syscall::Fork(0), 4);
+    syscall(scheduler, Syscall::Yield, 3);
+    assert_eq!(dispatch(scheduler).0, Pid::new(2));
+    syscall(scheduler, Syscall::Fork(0), 4);
+    syscall(scheduler, Syscall::Yield, 3);
+    assert_eq!(dispatch(scheduler).0, Pid::new(1));
+    syscall(scheduler, Syscall::Yield, 2);
+    assert_eq!(dispatch(scheduler).0, Pid::new(3));
+    syscall(scheduler, Syscall::Fork(0), 4);
+    syscall(scheduler, Syscall::Yield, 3);
+    assert_eq!(dispatch(scheduler).0, Pid::new(2));
+    syscall(scheduler, Syscall::Exit, 2);
+}
+
+fn parents(scheduler: &mut RoundRobinScheduler) -> Vec<(usize, Option<usize>)> {
+    let mut parents = scheduler
+        .list()
+        .iter()
+        .map(|p| (p.pid().get(), p.parent().map(Pid::get)))
+        .collect::<Vec<_>>();
+    parents.sort();
+    parents
+}
+
+#[test]
+pub fn reparent_orphans() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    exit_middle(&mut scheduler);
+
+    assert_eq!(
+        parents(&mut scheduler),
+        vec![(1, None), (3, Some(1)), (4, Some(3))]
+    );
+    assert!(matches!(scheduler.next(), SchedulingDecision::Run { .. }));
+}
+
+#[test]
+pub fn kill_orphans() {
+    let mut scheduler =
+        RoundRobinScheduler::with_kill_orphans(NonZeroUsize::new(5).unwrap(), 1, true);
+    exit_middle(&mut scheduler);
+
+    assert_eq!(parents(&mut scheduler), vec![(1, None)]);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+}
@@ -0,0 +1,33 @@
+use scheduler::{Pid, Process, ProcessControlBlock, ProcessState};
+
+#[test]
+pub fn charges() {
+    let mut pcb = ProcessControlBlock::new(Pid::new(2), ProcessState::Ready, 1, (5, 1, 2));
+    pcb.charge_execution(3);
+    pcb.charge_syscall();
+    pcb.charge_waiting(4);
+    assert_eq!(pcb.timings(), (9, 2, 5));
+
+    // the clock of a scheduler stops at its largest value, so do the timings
+    pcb.charge_execution(usize::MAX);
+    pcb.charge_waiting(usize::MAX);
+    assert_eq!(pcb.timings(), (usize::MAX, 2, usize::MAX));
+}
+
+#[test]
+pub fn process() {
+    let mut pcb = ProcessControlBlock::new(Pid::new(2), ProcessState::Ready, 1, (0, 0, 0));
+    assert_eq!(pcb.parent(), None);
+    assert_eq!(pcb.created(), 0);
+
+    pcb.set_state(ProcessState::Waiting { event: Some(3) });
+    pcb.set_priority(-2);
+    pcb.set_parent(Some(Pid::new(1)));
+    pcb.set_created(7);
+    assert_eq!(pcb.pid(), 2);
+    assert_eq!(pcb.state(), ProcessState::Waiting { event: Some(3) });
+    assert_eq!(pcb.priority(), -2);
+    assert_eq!(pcb.parent(), Some(Pid::new(1)));
+    assert_eq!(pcb.created(), 7);
+    assert_eq!(pcb.extra(), "");
+}
@@ -0,0 +1,99 @@
+use processor::simulator::Simulator;
+use scheduler::{
+    cfs, fcfs, priority_queue, round_robin, Pid, Process, Scheduler, SchedulingDecision,
+    SrtfScheduler, StopReason, StrideScheduler, Syscall, SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::syscall;
+
+/// A scheduler that checks that every decision is the one it has peeked.
+struct Peeking<S: Scheduler>(S);
+
+impl<S: Scheduler> Scheduler for Peeking<S> {
+    fn next(&mut self) -> SchedulingDecision {
+        let peeked = self.0.peek_next();
+        // peeking again does not change anything
+        assert_eq!(self.0.peek_next(), peeked);
+        let decision = self.0.next();
+        assert_eq!(decision, peeked);
+        decision
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        self.0.peek_next()
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        self.0.stop(reason)
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.0.list()
+    }
+}
+
+const WORKLOAD: &str = "\
+run 3
+fork prio=2
+    wait 1
+    run 5
+    signal 2
+fork prio=1
+    sleep 2
+    run 7
+    yield
+    run 1
+sleep 4
+signal 1
+run 1
+wait 2
+sleep 30
+";
+
+fn simulate(scheduler: impl Scheduler + 'static) {
+    let simulation = Simulator::new(Box::new(Peeking(scheduler)), WORKLOAD.parse().unwrap())
+        .run()
+        .unwrap();
+    assert_eq!(simulation.decisions.last(), Some(&SchedulingDecision::Done));
+}
+
+#[test]
+pub fn peek_round_robin() {
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 2);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let peeked = scheduler.peek_next();
+    assert_eq!(
+        peeked,
+        SchedulingDecision::Run {
+            pid: Pid::new(1),
+            timeslice: NonZeroUsize::new(3).unwrap(),
+        }
+    );
+    assert_eq!(scheduler.next(), peeked);
+
+    // process 1 has too little of its timeslice left, process 2 is next
+    syscall(&mut scheduler, Syscall::Fork(0), 1);
+    let peeked = scheduler.peek_next();
+    assert_eq!(
+        peeked,
+        SchedulingDecision::Run {
+            pid: Pid::new(2),
+            timeslice: NonZeroUsize::new(3).unwrap(),
+        }
+    );
+    assert_eq!(scheduler.next(), peeked);
+}
+
+#[test]
+pub fn peek_matches_next() {
+    let timeslice = NonZeroUsize::new(3).unwrap();
+    simulate(round_robin(timeslice, 1));
+    simulate(round_robin(timeslice, 2));
+    simulate(priority_queue(timeslice, 1));
+    simulate(cfs(timeslice, 1));
+    simulate(fcfs(timeslice));
+    simulate(SrtfScheduler::new(timeslice));
+    simulate(StrideScheduler::new(timeslice));
+}
@@ -0,0 +1,117 @@
+use scheduler::{
+    Pid, RoundRobinScheduler, Scheduler, SchedulerError, SchedulerEvent, StopReason, Syscall,
+    SyscallResult, TargetPolicy,
+};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use super::{dispatch, syscall};
+
+/// A scheduler where process 1 has forked process 2, which has forked
+/// processes 3 and 4, and process 3 has forked process 5.
+fn tree(policy: TargetPolicy) -> RoundRobinScheduler {
+    let mut scheduler =
+        RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1).target_policy(policy);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    for (parent, child) in [(1, 2), (2, 3), (2, 4), (3, 5)] {
+        run(&mut scheduler, parent);
+        assert_eq!(
+            syscall(&mut scheduler, Syscall::Fork(0), 4),
+            SyscallResult::Pid(Pid::new(child))
+        );
+        scheduler.stop(StopReason::Expired);
+    }
+    scheduler
+}
+
+/// Dispatches processes until `pid` runs, the others use up their timeslice.
+fn run(scheduler: &mut RoundRobinScheduler, pid: usize) {
+    while dispatch(scheduler).0 != pid {
+        scheduler.stop(StopReason::Expired);
+    }
+}
+
+#[test]
+pub fn kill_parent() {
+    let mut scheduler = tree(TargetPolicy::default());
+    let events = Arc::new(Mutex::new(vec![]));
+    let trace = events.clone();
+    scheduler.set_trace(Box::new(move |event| trace.lock().unwrap().push(*event)));
+
+    run(&mut scheduler, 3);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(2)), 4),
+        SyscallResult::Error(SchedulerError::PermissionDenied(Pid::new(2)))
+    );
+    assert!(matches!(
+        events.lock().unwrap().last(),
+        Some(&SchedulerEvent::PermissionDenied { pid, target, .. })
+            if pid == 3 && target == 2
+    ));
+    assert_eq!(scheduler.list().len(), 5);
+}
+
+#[test]
+pub fn kill_sibling() {
+    let mut scheduler = tree(TargetPolicy::default());
+
+    run(&mut scheduler, 4);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(3)), 4),
+        SyscallResult::Error(SchedulerError::PermissionDenied(Pid::new(3)))
+    );
+}
+
+#[test]
+pub fn kill_grandchild() {
+    let mut scheduler = tree(TargetPolicy::default());
+
+    run(&mut scheduler, 2);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(5)), 4),
+        SyscallResult::Success
+    );
+    run(&mut scheduler, 1);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(4)), 4),
+        SyscallResult::Success
+    );
+    assert_eq!(scheduler.list().len(), 3);
+}
+
+#[test]
+pub fn kill_anyone() {
+    let mut scheduler = tree(TargetPolicy::AllowAll);
+
+    run(&mut scheduler, 5);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(4)), 4),
+        SyscallResult::Success
+    );
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(2)), 3),
+        SyscallResult::Success
+    );
+}
+
+#[test]
+pub fn kill_only_self() {
+    let mut scheduler = tree(TargetPolicy::SelfOnly);
+
+    run(&mut scheduler, 1);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(2)), 4),
+        SyscallResult::Error(SchedulerError::PermissionDenied(Pid::new(2)))
+    );
+    run(&mut scheduler, 3);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(3)), 4),
+        SyscallResult::Success
+    );
+    // an unknown process is not a permission matter
+    run(&mut scheduler, 2);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Kill(Pid::new(9)), 4),
+        SyscallResult::NoSuchProcess
+    );
+}
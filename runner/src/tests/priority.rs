@@ -0,0 +1,145 @@
+use scheduler::{
+    round_robin, CfsScheduler, Pid, PriorityRoundRobinScheduler, Scheduler, SchedulerError,
+    StopReason, Syscall, SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn round_robin_stores_priority() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::SetPriority(3), 4),
+        SyscallResult::Success
+    );
+    assert_eq!(scheduler.list()[0].priority(), 3);
+
+    // the process keeps the processor
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 4));
+}
+
+#[test]
+pub fn cfs_lowered_priority() {
+    let mut scheduler = CfsScheduler::new(NonZeroUsize::new(4).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(5)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(5), 3);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 3));
+
+    // out of range priorities are clamped
+    syscall(&mut scheduler, Syscall::SetPriority(-3), 2);
+    let priorities = scheduler
+        .list()
+        .iter()
+        .map(|p| p.priority())
+        .collect::<Vec<_>>();
+    assert_eq!(priorities, vec![0, 5]);
+
+    // the rest of the timeslice is charged with the new weight
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(scheduler.list()[0].extra(), "vruntime=14");
+
+    // the process with the lowered priority is demoted
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 2));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+}
+
+/// Starts process 1 and process 2 with priority 5 and process 3 with priority 0,
+/// then keeps preempting the processes with priority 5. Returns the number of
+/// dispatches before process 3 runs, or [`None`] if it does not run at all.
+fn dispatches_until_low_priority_runs(
+    scheduler: &mut PriorityRoundRobinScheduler,
+) -> Option<usize> {
+    scheduler.stop(StopReason::syscall(Syscall::Fork(5)));
+    dispatch(scheduler);
+    syscall(scheduler, Syscall::Fork(5), 1);
+    dispatch(scheduler);
+    syscall(scheduler, Syscall::Fork(0), 0);
+
+    (1..=50).find(|_| {
+        let (pid, _) = dispatch(scheduler);
+        scheduler.stop(StopReason::Expired);
+        pid == 3
+    })
+}
+
+#[test]
+pub fn priority_round_robin_starves_without_aging() {
+    let mut scheduler = PriorityRoundRobinScheduler::new(NonZeroUsize::new(2).unwrap(), 1);
+    assert_eq!(dispatches_until_low_priority_runs(&mut scheduler), None);
+}
+
+#[test]
+pub fn priority_round_robin_aging() {
+    let mut scheduler = PriorityRoundRobinScheduler::with_aging(
+        NonZeroUsize::new(2).unwrap(),
+        1,
+        NonZeroUsize::new(4).unwrap(),
+    );
+
+    // process 3 gets a priority of 5 after waiting for 20 time units
+    assert_eq!(dispatches_until_low_priority_runs(&mut scheduler), Some(11));
+
+    // the raised priority drops back once the process has run
+    assert_eq!(scheduler.list()[2].priority(), 0);
+    assert_ne!(dispatch(&mut scheduler).0, Pid::new(3));
+    scheduler.stop(StopReason::Expired);
+    assert_ne!(dispatch(&mut scheduler).0, Pid::new(3));
+}
+
+#[test]
+pub fn priority_round_robin_lowered_priority() {
+    let mut scheduler = PriorityRoundRobinScheduler::new(NonZeroUsize::new(4).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(3)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(2), 3);
+
+    // still the highest priority, the process keeps the processor
+    syscall(&mut scheduler, Syscall::SetPriority(2), 2);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+
+    // out of range priorities are clamped, process 2 now goes first and
+    // process 1 keeps the time left from its timeslice
+    syscall(&mut scheduler, Syscall::SetPriority(-1), 1);
+    let priorities = scheduler
+        .list()
+        .iter()
+        .map(|p| p.priority())
+        .collect::<Vec<_>>();
+    assert_eq!(priorities, vec![0, 2]);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 4));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 4));
+
+    // process 1 only runs once process 2 is gone
+    syscall(&mut scheduler, Syscall::Exit, 3);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 1));
+}
+
+#[test]
+pub fn sleep_too_long() {
+    let mut scheduler = PriorityRoundRobinScheduler::new(NonZeroUsize::new(6).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+
+    let error = SyscallResult::Error(SchedulerError::InvalidSleepDuration(usize::MAX));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Sleep(usize::MAX), timeslice - 1),
+        error
+    );
+    let wait = Syscall::WaitTimeout {
+        event: 1,
+        max: usize::MAX,
+    };
+    assert_eq!(syscall(&mut scheduler, wait, timeslice - 2), error);
+    // the process goes on running
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+}
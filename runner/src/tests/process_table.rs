@@ -0,0 +1,65 @@
+use scheduler::{Pid, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason, Syscall};
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+
+use super::{dispatch, syscall};
+
+const PROCESSES: usize = 100_000;
+
+#[test]
+pub fn fork_and_exit_all_at_once() {
+    let start = Instant::now();
+    let timeslice = NonZeroUsize::new(2 * PROCESSES).unwrap();
+    let mut scheduler = RoundRobinScheduler::new(timeslice, 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let (_, timeslice) = dispatch(&mut scheduler);
+    for forked in 0..PROCESSES {
+        syscall(&mut scheduler, Syscall::Fork(0), timeslice - forked - 1);
+    }
+    syscall(&mut scheduler, Syscall::Sleep(1), timeslice - PROCESSES - 1);
+    assert_eq!(scheduler.list().len(), PROCESSES + 1);
+    let (visits, slots) = scheduler.table_usage();
+    assert!(visits < 10 * PROCESSES, "{visits} processes visited");
+    assert_eq!(slots, PROCESSES + 1);
+
+    for child in 2..PROCESSES + 2 {
+        let (pid, timeslice) = dispatch(&mut scheduler);
+        assert_eq!(pid, child);
+        syscall(&mut scheduler, Syscall::Exit, timeslice - 1);
+    }
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, 1);
+    syscall(&mut scheduler, Syscall::Exit, timeslice - 1);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+
+    // every system call looks up a few processes, whatever their number
+    let (visits, _) = scheduler.table_usage();
+    assert!(visits < 50 * PROCESSES, "{visits} processes visited");
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+pub fn fork_and_exit_one_by_one() {
+    let start = Instant::now();
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    for child in 2..PROCESSES + 2 {
+        let (pid, timeslice) = dispatch(&mut scheduler);
+        assert_eq!(pid, 1);
+        syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+        // init wakes up with nothing left and gets a new timeslice
+        syscall(&mut scheduler, Syscall::Sleep(1), 0);
+        let (pid, timeslice) = dispatch(&mut scheduler);
+        assert_eq!(pid, child);
+        syscall(&mut scheduler, Syscall::Exit, timeslice - 1);
+    }
+
+    // the slot of every child is reused by the next one
+    let (visits, slots) = scheduler.table_usage();
+    assert_eq!(slots, 2);
+    assert!(visits < 50 * PROCESSES, "{visits} processes visited");
+    assert_eq!(scheduler.list()[0].pid(), Pid::new(1));
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
@@ -0,0 +1,137 @@
+use scheduler::{
+    AdoptSpec, AdoptState, CfsScheduler, FcfsScheduler, FifoQueue, Pid,
+    PriorityRoundRobinScheduler, Process, ProcessSnapshot, Processes, QueueScheduler,
+    RoundRobinScheduler, Scheduler, SrtfScheduler, StateKind, StopReason, StrideScheduler, Syscall,
+    SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+const TIMESLICE: NonZeroUsize = NonZeroUsize::new(10).unwrap();
+
+fn pids<'a>(processes: impl Iterator<Item = &'a dyn Process>) -> Vec<usize> {
+    processes.map(|p| p.pid().get()).collect()
+}
+
+/// Leaves the scheduler with one process of every kind of state: PID 1
+/// forks four processes, kills the first one and waits for an event, the
+/// next process that runs sleeps and the one after it keeps running.
+/// Returns the PIDs of the sleeping, the running and the ready processes.
+fn every_state(scheduler: &mut impl Scheduler) -> (usize, usize, usize) {
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (pid, timeslice) = dispatch(scheduler);
+    assert_eq!(pid, 1);
+    for (forked, child) in (2..6).enumerate() {
+        assert_eq!(
+            syscall(scheduler, Syscall::Fork(0), timeslice - forked - 1),
+            SyscallResult::Pid(Pid::new(child))
+        );
+        if child == 2 {
+            syscall(scheduler, Syscall::Kill(Pid::new(2)), timeslice - 2);
+        }
+    }
+    syscall(scheduler, Syscall::Wait(1), timeslice - 6);
+
+    let (sleeping, timeslice) = dispatch(scheduler);
+    syscall(scheduler, Syscall::Sleep(100), timeslice - 1);
+    let (running, _) = dispatch(scheduler);
+    let ready = (3..6)
+        .find(|&pid| pid != sleeping.get() && pid != running.get())
+        .unwrap();
+    (sleeping.get(), running.get(), ready)
+}
+
+fn check(mut scheduler: impl Scheduler + Processes) {
+    let (sleeping, running, ready) = every_state(&mut scheduler);
+    let listed = ProcessSnapshot::all(&mut scheduler);
+
+    // the same processes as the list, in the same order
+    let processes = scheduler
+        .processes()
+        .map(ProcessSnapshot::from)
+        .collect::<Vec<_>>();
+    assert_eq!(processes, listed);
+    assert_eq!(pids(scheduler.processes()), [1, 3, 4, 5]);
+
+    let in_state = |kind| pids(scheduler.processes_in_state(kind));
+    assert_eq!(in_state(StateKind::Waiting), [1]);
+    assert_eq!(in_state(StateKind::Sleeping), [sleeping]);
+    assert_eq!(in_state(StateKind::Running), [running]);
+    assert_eq!(in_state(StateKind::Ready), [ready]);
+
+    for process in &listed {
+        let found = scheduler.get(process.pid).map(ProcessSnapshot::from);
+        assert_eq!(found.as_ref(), Some(process));
+    }
+    assert!(scheduler.get(Pid::new(2)).is_none());
+    assert!(scheduler.get(Pid::new(6)).is_none());
+}
+
+#[test]
+pub fn round_robin() {
+    check(RoundRobinScheduler::new(TIMESLICE, 1));
+}
+
+#[test]
+pub fn priority_round_robin() {
+    check(PriorityRoundRobinScheduler::new(TIMESLICE, 1));
+}
+
+#[test]
+pub fn cfs() {
+    check(CfsScheduler::new(TIMESLICE, 1));
+}
+
+#[test]
+pub fn fcfs() {
+    check(FcfsScheduler::new(TIMESLICE));
+}
+
+#[test]
+pub fn srtf() {
+    check(SrtfScheduler::new(TIMESLICE));
+}
+
+#[test]
+pub fn stride() {
+    check(StrideScheduler::new(TIMESLICE));
+}
+
+#[test]
+pub fn queue() {
+    check(QueueScheduler::new(FifoQueue::new(), TIMESLICE, 1));
+}
+
+#[test]
+pub fn adopted_processes_sorted_by_pid() {
+    let mut round_robin = RoundRobinScheduler::new(TIMESLICE, 1);
+    let mut cfs = CfsScheduler::new(TIMESLICE, 1);
+    for pid in [10, 5, 7] {
+        let spec = AdoptSpec {
+            pid: Some(Pid::new(pid)),
+            priority: 0,
+            state: AdoptState::Ready,
+            timings: (0, 0, 0),
+            group: 0,
+        };
+        round_robin.adopt(spec).unwrap();
+        cfs.adopt(spec).unwrap();
+    }
+    assert_eq!(pids(round_robin.processes()), [5, 7, 10]);
+    assert_eq!(pids(cfs.processes()), [5, 7, 10]);
+}
+
+#[test]
+pub fn round_robin_timings_up_to_date() {
+    let mut scheduler = RoundRobinScheduler::new(TIMESLICE, 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+    syscall(&mut scheduler, Syscall::Sleep(5), timeslice - 4);
+
+    // the time is credited to the processes without listing them
+    let timings = scheduler.processes().map(|p| p.timings());
+    assert_eq!(timings.collect::<Vec<_>>(), [(4, 2, 2), (3, 0, 0)]);
+    assert_eq!(scheduler.get(Pid::new(2)).unwrap().timings(), (3, 0, 0));
+}
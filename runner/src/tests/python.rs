@@ -0,0 +1,139 @@
+use scheduler::python::{
+    decision_to_parts, result_kind, state_to_parts, stop_reason_from_parts, syscall_from_parts,
+    syscall_to_parts,
+};
+use scheduler::{
+    Pid, ProcessState, SchedulerError, SchedulingDecision, StopReason, Syscall, SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+#[test]
+pub fn every_syscall() {
+    let syscalls = [
+        (Syscall::Fork(-3), "fork", vec![-3]),
+        (Syscall::Sleep(4), "sleep", vec![4]),
+        (Syscall::Wait(2), "wait", vec![2]),
+        (
+            Syscall::WaitTimeout { event: 2, max: 7 },
+            "wait_timeout",
+            vec![2, 7],
+        ),
+        (Syscall::Signal(2), "signal", vec![2]),
+        (Syscall::Yield, "yield", vec![]),
+        (Syscall::SetGroup(1), "set_group", vec![1]),
+        (Syscall::SignalGroup(1, 2), "signal_group", vec![1, 2]),
+        (Syscall::SetPriority(5), "set_priority", vec![5]),
+        (Syscall::WaitPid(Pid::new(3)), "wait_pid", vec![3]),
+        (Syscall::Kill(Pid::new(3)), "kill", vec![3]),
+        (Syscall::Exit, "exit", vec![]),
+        (Syscall::ExitWith(-1), "exit_with", vec![-1]),
+    ];
+    for (syscall, name, args) in syscalls {
+        assert_eq!(syscall_to_parts(syscall), (name, args.clone()));
+        assert_eq!(syscall_from_parts(name, &args), Ok(syscall));
+    }
+}
+
+#[test]
+pub fn invalid_syscalls() {
+    assert_eq!(
+        syscall_from_parts("sleep", &[]),
+        Err(String::from(
+            "the `sleep` system call takes 1 arguments, not 0"
+        ))
+    );
+    assert_eq!(
+        syscall_from_parts("wait", &[-1]),
+        Err(String::from("the argument -1 must not be negative"))
+    );
+    assert_eq!(
+        syscall_from_parts("fork", &[200]),
+        Err(String::from("the priority 200 is out of range"))
+    );
+    assert_eq!(
+        syscall_from_parts("kill", &[0]),
+        Err(String::from("there is no process with PID 0"))
+    );
+    assert_eq!(
+        syscall_from_parts("exit_with", &[i64::MAX]),
+        Err(format!("the exit code {} is out of range", i64::MAX))
+    );
+    assert_eq!(
+        syscall_from_parts("spawn", &[1]),
+        Err(String::from("unknown system call `spawn`"))
+    );
+}
+
+#[test]
+pub fn every_decision() {
+    let decisions = [
+        (
+            SchedulingDecision::Run {
+                pid: Pid::new(2),
+                timeslice: NonZeroUsize::new(3).unwrap(),
+            },
+            "run",
+            vec![("pid", 2), ("timeslice", 3)],
+        ),
+        (
+            SchedulingDecision::Sleep(NonZeroUsize::new(5).unwrap()),
+            "sleep",
+            vec![("amount", 5)],
+        ),
+        (SchedulingDecision::Deadlock, "deadlock", vec![]),
+        (SchedulingDecision::Panic, "panic", vec![]),
+        (SchedulingDecision::Done, "done", vec![]),
+    ];
+    for (decision, kind, fields) in decisions {
+        assert_eq!(decision_to_parts(decision), (kind, fields));
+    }
+}
+
+#[test]
+pub fn every_result_and_state() {
+    let results = [
+        (SyscallResult::Pid(Pid::new(2)), "pid"),
+        (SyscallResult::Success, "success"),
+        (SyscallResult::NoRunningProcess, "no_running_process"),
+        (SyscallResult::NoSuchProcess, "no_such_process"),
+        (SyscallResult::MaxProcessesReached, "max_processes_reached"),
+        (SyscallResult::Error(SchedulerError::PidOverflow), "error"),
+    ];
+    for (result, kind) in results {
+        assert_eq!(result_kind(result), kind);
+    }
+
+    let states = [
+        (ProcessState::Ready, ("ready", None)),
+        (ProcessState::Running, ("running", None)),
+        (
+            ProcessState::Waiting { event: Some(4) },
+            ("waiting", Some(4)),
+        ),
+        (ProcessState::Waiting { event: None }, ("sleeping", None)),
+    ];
+    for (state, parts) in states {
+        assert_eq!(state_to_parts(state), parts);
+    }
+}
+
+#[test]
+pub fn stop_reasons() {
+    assert_eq!(
+        stop_reason_from_parts("expired", None),
+        Ok(StopReason::Expired)
+    );
+    assert_eq!(
+        stop_reason_from_parts("syscall", Some(("sleep", &[4], 2))),
+        Ok(StopReason::Syscall {
+            syscall: Syscall::Sleep(4),
+            remaining: 2
+        })
+    );
+    assert!(stop_reason_from_parts("syscall", None).is_err());
+    assert!(stop_reason_from_parts("expired", Some(("yield", &[], 1))).is_err());
+    assert_eq!(
+        stop_reason_from_parts("preempted", None),
+        Err(String::from("unknown stop reason `preempted`"))
+    );
+}
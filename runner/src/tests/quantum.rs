@@ -0,0 +1,200 @@
+use scheduler::{
+    round_robin, Pid, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn full_quantum_after_expiry_leftover_after_sleep() {
+    let mut scheduler = round_robin(NonZeroUsize::new(4).unwrap(), 2);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let pid_1 = Pid::new(1);
+    let pid_2 = Pid::new(2);
+
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 4));
+    syscall(&mut scheduler, Syscall::Fork(0), 3);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 3));
+    syscall(&mut scheduler, Syscall::Sleep(2), 2);
+
+    // pid 1 wakes up while pid 2 runs, with what it had left
+    assert_eq!(dispatch(&mut scheduler), (pid_2, 4));
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 2));
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (pid_2, 4));
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 4));
+}
+
+#[test]
+pub fn sleep_keeps_remaining() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+    syscall(&mut scheduler, Syscall::Sleep(2), 3);
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(2).unwrap())
+    );
+
+    // the process finishes the 3 time units it had left, not a new timeslice
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 3));
+    syscall(&mut scheduler, Syscall::Exit, 0);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+    assert_eq!(scheduler.statistics().total_time, 2 + 2 + 3);
+}
+
+#[test]
+pub fn timeslice_larger_than_remaining() {
+    let mut scheduler = round_robin(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let pid_1 = Pid::new(1);
+
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 10));
+    syscall(&mut scheduler, Syscall::Signal(1), 2);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 2));
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 10));
+}
+
+#[test]
+pub fn single_process_below_minimum() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 3);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+    syscall(&mut scheduler, Syscall::Sleep(0), 4);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+    syscall(&mut scheduler, Syscall::Signal(1), 2);
+
+    // 2 time units are below the minimum, the process gets a new timeslice
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+    // and it is queued only once
+    assert_eq!(scheduler.to_string().matches("RUNNING").count(), 1);
+}
+
+#[test]
+pub fn resume_with_leftover() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 3);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let pid_1 = Pid::new(1);
+    let pid_2 = Pid::new(2);
+
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 10));
+    syscall(&mut scheduler, Syscall::Fork(0), 9);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 9));
+    syscall(&mut scheduler, Syscall::Signal(1), 7);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 7));
+    syscall(&mut scheduler, Syscall::Yield, 7);
+
+    // pid 1 gives up the 7 time units it has left for a new timeslice
+    assert_eq!(dispatch(&mut scheduler), (pid_2, 10));
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 10));
+    // as much as the minimum is still enough to keep running
+    syscall(&mut scheduler, Syscall::Signal(1), 3);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 3));
+    syscall(&mut scheduler, Syscall::Signal(1), 2);
+    assert_eq!(dispatch(&mut scheduler), (pid_2, 10));
+}
+
+#[test]
+pub fn set_timeslice() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 3);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let pid_1 = Pid::new(1);
+    let burst = scheduler.add_process(0, NonZeroUsize::new(6).unwrap()).unwrap();
+    let pid_3 = Pid::new(3);
+
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 10));
+    syscall(&mut scheduler, Syscall::Fork(0), 9);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 9));
+
+    // the running process keeps what is left of its timeslice
+    scheduler.set_timeslice(NonZeroUsize::new(4).unwrap());
+    syscall(&mut scheduler, Syscall::Signal(1), 7);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 7));
+    scheduler.stop(StopReason::expired());
+
+    // the added process keeps its own quantum, pid 3 had not started its timeslice
+    assert_eq!(dispatch(&mut scheduler), (burst, 6));
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (pid_3, 4));
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 4));
+
+    // the minimum remaining timeslice is lowered to the timeslice
+    scheduler.set_timeslice(NonZeroUsize::new(2).unwrap());
+    syscall(&mut scheduler, Syscall::Signal(1), 2);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 2));
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (burst, 6));
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (pid_3, 2));
+    assert_eq!(scheduler.validate(), Ok(()));
+}
+
+fn slices(slices: &[usize]) -> Vec<NonZeroUsize> {
+    slices.iter().map(|&slice| NonZeroUsize::new(slice).unwrap()).collect()
+}
+
+#[test]
+pub fn priority_slices() {
+    let mut scheduler = RoundRobinScheduler::with_priority_slices(slices(&[2, 5]), 3);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let pid_1 = Pid::new(1);
+    let pid_2 = Pid::new(2);
+
+    // a process keeps running with its whole timeslice, even if it is
+    // shorter than the minimum remaining timeslice
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 2));
+    syscall(&mut scheduler, Syscall::Fork(1), 2);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 2));
+    scheduler.stop(StopReason::expired());
+
+    assert_eq!(dispatch(&mut scheduler), (pid_2, 5));
+    syscall(&mut scheduler, Syscall::Signal(1), 3);
+    assert_eq!(dispatch(&mut scheduler), (pid_2, 3));
+    syscall(&mut scheduler, Syscall::Signal(1), 2);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 2));
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (pid_2, 5));
+}
+
+#[test]
+pub fn priority_slices_out_of_range() {
+    let mut scheduler = RoundRobinScheduler::with_priority_slices(slices(&[2, 3, 4]), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(-3)));
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+    syscall(&mut scheduler, Syscall::Fork(7), 1);
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 4));
+}
+
+#[test]
+pub fn priority_slices_after_set_priority() {
+    let mut scheduler = RoundRobinScheduler::with_priority_slices(slices(&[2, 3, 6]), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let pid_1 = Pid::new(1);
+
+    // the new timeslice comes with the next full one
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 2));
+    syscall(&mut scheduler, Syscall::SetPriority(2), 1);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 1));
+    scheduler.stop(StopReason::expired());
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 6));
+    syscall(&mut scheduler, Syscall::SetPriority(1), 5);
+    syscall(&mut scheduler, Syscall::Yield, 4);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 3));
+    syscall(&mut scheduler, Syscall::Yield, 0);
+    assert_eq!(dispatch(&mut scheduler), (pid_1, 3));
+}
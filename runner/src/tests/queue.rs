@@ -0,0 +1,166 @@
+use processor::simulator::{Simulation, Simulator};
+use scheduler::{
+    FifoQueue, Pid, PriorityQueue, Process, Processes, QueueScheduler, ReadyQueue,
+    RoundRobinScheduler, Scheduler, SchedulerError, SchedulingDecision, StopReason, Syscall,
+    SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+const TIMESLICE: NonZeroUsize = NonZeroUsize::new(3).unwrap();
+
+const WORKLOADS: [&str; 3] = [
+    "\
+run 3
+fork prio=2
+    wait 1
+    run 5
+    signal 2
+fork prio=1
+    sleep 2
+    run 7
+    yield
+    run 1
+sleep 4
+signal 1
+run 1
+wait 2
+sleep 30
+",
+    "\
+fork
+    run 8
+    sleep 3
+    run 2
+fork
+    run 1
+    yield
+    run 6
+run 10
+yield
+run 2
+exit
+",
+    "\
+run 1
+fork
+    sleep 0
+    run 4
+fork
+    fork
+        run 9
+    run 2
+sleep 1
+run 7
+",
+];
+
+/// Checks that every decision is the one that has been peeked.
+struct Peeking<S: Scheduler>(S);
+
+impl<S: Scheduler> Scheduler for Peeking<S> {
+    fn next(&mut self) -> SchedulingDecision {
+        let peeked = self.0.peek_next();
+        let decision = self.0.next();
+        assert_eq!(decision, peeked);
+        decision
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        self.0.peek_next()
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        self.0.stop(reason)
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.0.list()
+    }
+}
+
+fn simulate(scheduler: impl Scheduler + 'static, workload: &str) -> Simulation {
+    Simulator::new(Box::new(Peeking(scheduler)), workload.parse().unwrap())
+        .run()
+        .unwrap()
+}
+
+#[test]
+pub fn fifo_queue_is_round_robin() {
+    for workload in WORKLOADS {
+        let expected = simulate(RoundRobinScheduler::new(TIMESLICE, 2), workload);
+        let simulation = simulate(
+            QueueScheduler::new(FifoQueue::new(), TIMESLICE, 2),
+            workload,
+        );
+        assert_eq!(simulation.decisions, expected.decisions);
+        // the round robin scheduler also shows the process groups
+        let processes = |simulation: &Simulation| {
+            let processes = simulation.processes.iter();
+            processes
+                .map(|p| (p.pid, p.state, p.priority, p.timings))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(processes(&simulation), processes(&expected));
+        assert_eq!(simulation.elapsed, expected.elapsed);
+    }
+}
+
+#[test]
+pub fn priority_queue_runs_higher_priority_first() {
+    let mut scheduler = QueueScheduler::new(PriorityQueue::new(), TIMESLICE, 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    for priority in [1, 3, 1, 2] {
+        syscall(&mut scheduler, Syscall::Fork(priority), 2);
+    }
+    assert_eq!(scheduler.queue().len(), 4);
+    syscall(&mut scheduler, Syscall::Sleep(100), 2);
+
+    // the same priority runs in the order of the forks
+    for pid in [3, 5, 2, 4] {
+        assert_eq!(dispatch(&mut scheduler).0, pid);
+        syscall(&mut scheduler, Syscall::Exit, 2);
+    }
+    assert!(scheduler.queue().is_empty());
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(96).unwrap())
+    );
+    assert_eq!(dispatch(&mut scheduler).0, 1);
+}
+
+#[test]
+pub fn priority_queue_requeues_with_the_new_priority() {
+    let mut scheduler = QueueScheduler::new(PriorityQueue::new(), TIMESLICE, 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(1), 2);
+    syscall(&mut scheduler, Syscall::SetPriority(2), 1);
+    scheduler.stop(StopReason::expired());
+
+    // process 1 now has a higher priority than process 2
+    assert_eq!(dispatch(&mut scheduler).0, 1);
+    assert_eq!(scheduler.get(Pid::new(1)).unwrap().priority(), 2);
+}
+
+#[test]
+pub fn sleep_too_long() {
+    let mut scheduler = QueueScheduler::new(FifoQueue::new(), TIMESLICE, 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+
+    let error = SyscallResult::Error(SchedulerError::InvalidSleepDuration(usize::MAX));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Sleep(usize::MAX), timeslice - 1),
+        error
+    );
+    let wait = Syscall::WaitTimeout {
+        event: 1,
+        max: usize::MAX,
+    };
+    assert_eq!(syscall(&mut scheduler, wait, timeslice - 2), error);
+    // the process goes on running
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+}
@@ -0,0 +1,156 @@
+use scheduler::{
+    AdoptSpec, AdoptState, Pid, RoundRobinScheduler, Scheduler, SchedulerError, SchedulingDecision,
+    SignalPolicy, StopReason, Syscall, SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+/// A xorshift generator, so that the calls are the same on every run.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A small number most of the time, sometimes one that is as large as it can be.
+    fn amount(&mut self) -> usize {
+        match self.next() % 8 {
+            0 => usize::MAX,
+            1 => usize::MAX - self.next() as usize % 4,
+            _ => self.next() as usize % 8,
+        }
+    }
+
+    fn pid(&mut self) -> Pid {
+        Pid::new(self.amount().max(1))
+    }
+
+    fn syscall(&mut self) -> Syscall {
+        match self.next() % 13 {
+            0 => Syscall::Fork(self.next() as i8),
+            1 => Syscall::Sleep(self.amount()),
+            2 => Syscall::Wait(self.amount()),
+            3 => Syscall::WaitTimeout {
+                event: self.amount(),
+                max: self.amount(),
+            },
+            4 => Syscall::Signal(self.amount()),
+            5 => Syscall::Yield,
+            6 => Syscall::SetGroup(self.amount()),
+            7 => Syscall::SignalGroup(self.amount(), self.amount()),
+            8 => Syscall::SetPriority(self.next() as i8),
+            9 => Syscall::WaitPid(self.pid()),
+            10 => Syscall::Kill(self.pid()),
+            11 => Syscall::Exit,
+            _ => Syscall::ExitWith(self.next() as i32),
+        }
+    }
+}
+
+/// Makes random calls to the scheduler, none of them may panic
+/// or leave the scheduler in an inconsistent state.
+fn call_randomly(scheduler: &mut RoundRobinScheduler, random: &mut Random) {
+    for _ in 0..200 {
+        match random.next() % 6 {
+            0 => {
+                scheduler.next();
+            }
+            1 => {
+                scheduler.peek_next();
+            }
+            2 => {
+                scheduler.stop(StopReason::Expired);
+            }
+            3 => {
+                scheduler.list();
+                scheduler.deadlocked();
+            }
+            _ => {
+                scheduler.stop(StopReason::Syscall {
+                    syscall: random.syscall(),
+                    remaining: random.amount(),
+                });
+            }
+        }
+        assert_eq!(scheduler.validate(), Ok(()));
+    }
+}
+
+#[test]
+pub fn round_robin_does_not_panic() {
+    for seed in 1..200 {
+        let mut random = Random(seed);
+        let timeslice = NonZeroUsize::new(random.amount().max(1)).unwrap();
+        let minimum_remaining = random.amount();
+        let signal_policy = match seed % 2 {
+            0 => SignalPolicy::WakeAll,
+            _ => SignalPolicy::WakeOne,
+        };
+        let mut scheduler = RoundRobinScheduler::new(timeslice, minimum_remaining)
+            .signal_policy(signal_policy)
+            .kill_orphans(seed % 3 == 0);
+        call_randomly(&mut scheduler, &mut random);
+    }
+}
+
+#[test]
+pub fn sleep_too_long() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(3), 4);
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(3).unwrap())
+    );
+    dispatch(&mut scheduler);
+
+    let error = SchedulerError::InvalidSleepDuration(usize::MAX);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Sleep(usize::MAX), 3),
+        SyscallResult::Error(error)
+    );
+    assert_eq!(
+        syscall(
+            &mut scheduler,
+            Syscall::WaitTimeout {
+                event: 1,
+                max: usize::MAX
+            },
+            2
+        ),
+        SyscallResult::Error(error)
+    );
+    assert_eq!(
+        error.to_string(),
+        format!("cannot sleep for {} time units", usize::MAX)
+    );
+    // the process goes on running
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+}
+
+#[test]
+pub fn no_pid_left() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    scheduler
+        .adopt(AdoptSpec {
+            pid: Some(Pid::new(usize::MAX)),
+            priority: 0,
+            state: AdoptState::Ready,
+            timings: (0, 0, 0),
+            group: 0,
+        })
+        .unwrap();
+    dispatch(&mut scheduler);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Fork(0), 4),
+        SyscallResult::Error(SchedulerError::PidOverflow)
+    );
+    assert_eq!(scheduler.list().len(), 2);
+}
@@ -0,0 +1,115 @@
+use processor::replay::{replay, Difference, Divergence, Recording, ReplayOutcome};
+use processor::simulator::Simulator;
+use scheduler::{fcfs, round_robin, Pid, Scheduler, SchedulingDecision};
+use std::env;
+use std::fs;
+use std::num::NonZeroUsize;
+
+const WORKERS: &str = "\
+fork prio=1
+    run 5
+    wait 1
+    run 2
+fork prio=2
+    run 3
+    sleep 4
+    run 1
+    signal 1
+run 4
+yield
+run 2
+sleep 9
+";
+
+const SLEEPERS: &str = "\
+fork
+    sleep 3
+    run 1
+fork
+    sleep 1
+    run 4
+sleep 2
+run 3
+sleep 6
+";
+
+fn round_robin_scheduler() -> Box<dyn Scheduler> {
+    Box::new(round_robin(NonZeroUsize::new(3).unwrap(), 1))
+}
+
+fn record(scheduler: Box<dyn Scheduler>, workload: &str) -> Recording {
+    Simulator::new(scheduler, workload.parse().unwrap())
+        .run()
+        .unwrap()
+        .recording
+}
+
+/// Replays the recording stored in `fixtures/<name>.json` against the
+/// round robin scheduler, or stores a new one if `WRITE_OUTPUT` is set.
+fn golden(name: &str, workload: &str) {
+    let path = format!("fixtures/{name}.json");
+    if env::var("WRITE_OUTPUT").is_ok() {
+        let recording = record(round_robin_scheduler(), workload);
+        fs::write(&path, serde_json::to_string_pretty(&recording).unwrap()).unwrap();
+    } else {
+        let recording: Recording =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let outcome = replay(&mut *round_robin_scheduler(), &recording);
+        if let ReplayOutcome::Diverged(divergence) = outcome {
+            panic!("the round robin schedule has changed, {divergence}");
+        }
+    }
+}
+
+#[test]
+pub fn round_robin_workers() {
+    golden("round_robin_workers", WORKERS);
+}
+
+#[test]
+pub fn round_robin_sleepers() {
+    golden("round_robin_sleepers", SLEEPERS);
+}
+
+#[test]
+pub fn another_timeslice() {
+    let recording = record(round_robin_scheduler(), WORKERS);
+    let mut scheduler = round_robin(NonZeroUsize::new(4).unwrap(), 1);
+
+    let ReplayOutcome::Diverged(divergence) = replay(&mut scheduler, &recording) else {
+        panic!("the timeslice has not been compared");
+    };
+    let run = |timeslice| SchedulingDecision::Run {
+        pid: Pid::new(1),
+        timeslice: NonZeroUsize::new(timeslice).unwrap(),
+    };
+    assert_eq!(
+        divergence,
+        Divergence {
+            index: 1,
+            difference: Difference::Timeslice {
+                expected: run(3),
+                got: run(4)
+            }
+        }
+    );
+    assert_eq!(
+        divergence.to_string(),
+        "step 1: another timeslice, expected `Run 1 for 3 slices`, got `Run 1 for 4 slices`"
+    );
+}
+
+#[test]
+pub fn another_process() {
+    let recording = record(round_robin_scheduler(), WORKERS);
+    let mut scheduler = fcfs(NonZeroUsize::new(3).unwrap());
+
+    let ReplayOutcome::Diverged(divergence) = replay(&mut scheduler, &recording) else {
+        panic!("the schedules should differ");
+    };
+    assert!(matches!(divergence.difference, Difference::Pid { .. }));
+    assert_eq!(
+        replay(&mut *round_robin_scheduler(), &recording),
+        ReplayOutcome::Identical
+    );
+}
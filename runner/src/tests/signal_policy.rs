@@ -0,0 +1,196 @@
+use scheduler::{
+    Pid, ProcessState, RoundRobinScheduler, Scheduler, SchedulerBuilder, SchedulingDecision,
+    SignalPolicy, StopReason, Syscall, SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+/// Process 3 and then process 2 wait for event 1, process 1 runs.
+fn waiters(signal_policy: SignalPolicy) -> RoundRobinScheduler {
+    let mut scheduler =
+        RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1).signal_policy(signal_policy);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 9);
+    syscall(&mut scheduler, Syscall::Fork(0), 8);
+    syscall(&mut scheduler, Syscall::Yield, 7);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Yield, 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(3));
+    syscall(&mut scheduler, Syscall::Wait(1), 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Yield, 6);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Wait(1), 8);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    scheduler
+}
+
+fn states(scheduler: &mut impl Scheduler) -> Vec<ProcessState> {
+    scheduler.list().iter().map(|p| p.state()).collect()
+}
+
+#[test]
+pub fn wake_all() {
+    let mut scheduler = waiters(SignalPolicy::WakeAll);
+    syscall(&mut scheduler, Syscall::Signal(1), 5);
+    assert_eq!(
+        states(&mut scheduler),
+        vec![
+            ProcessState::Running,
+            ProcessState::Ready,
+            ProcessState::Ready
+        ]
+    );
+}
+
+#[test]
+pub fn wake_one_in_wait_order() {
+    let mut scheduler = waiters(SignalPolicy::WakeOne);
+    let waiting = ProcessState::Waiting { event: Some(1) };
+
+    syscall(&mut scheduler, Syscall::Signal(1), 5);
+    assert_eq!(
+        states(&mut scheduler),
+        vec![ProcessState::Running, waiting, ProcessState::Ready]
+    );
+
+    syscall(&mut scheduler, Syscall::Signal(1), 4);
+    assert_eq!(
+        states(&mut scheduler),
+        vec![
+            ProcessState::Running,
+            ProcessState::Ready,
+            ProcessState::Ready
+        ]
+    );
+}
+
+#[test]
+pub fn signal_before_wait() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1)
+        .signal_policy(SignalPolicy::WakeOne);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Signal(1), 9);
+    syscall(&mut scheduler, Syscall::Signal(1), 8);
+
+    // both signals are kept, so the first two waits return right away
+    syscall(&mut scheduler, Syscall::Wait(1), 7);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 7));
+    syscall(&mut scheduler, Syscall::Wait(1), 6);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 6));
+    syscall(&mut scheduler, Syscall::Wait(1), 5);
+    assert_eq!(scheduler.next(), SchedulingDecision::Deadlock);
+}
+
+#[test]
+pub fn wake_all_forgets_signals() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Signal(1), 9);
+    syscall(&mut scheduler, Syscall::Wait(1), 8);
+    assert_eq!(scheduler.next(), SchedulingDecision::Deadlock);
+}
+
+/// Process 1 runs, process 2 waits for event 1.
+fn one_waiter(level_triggered: bool) -> RoundRobinScheduler {
+    let mut scheduler = RoundRobinScheduler::with_level_triggered_signals(
+        NonZeroUsize::new(10).unwrap(),
+        1,
+        level_triggered,
+    );
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 9);
+    syscall(&mut scheduler, Syscall::Yield, 8);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Wait(1), 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    scheduler
+}
+
+#[test]
+pub fn signal_with_waiter() {
+    for level_triggered in [false, true] {
+        let mut scheduler = one_waiter(level_triggered);
+        assert_eq!(
+            syscall(&mut scheduler, Syscall::Signal(1), 7),
+            SyscallResult::Success
+        );
+        assert_eq!(
+            states(&mut scheduler),
+            vec![ProcessState::Running, ProcessState::Ready]
+        );
+
+        // the signal has been used by the waiting process
+        syscall(&mut scheduler, Syscall::Wait(1), 6);
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+        syscall(&mut scheduler, Syscall::Wait(1), 9);
+        assert_eq!(scheduler.next(), SchedulingDecision::Deadlock);
+    }
+}
+
+#[test]
+pub fn edge_triggered_signal_without_waiter() {
+    let mut scheduler =
+        RoundRobinScheduler::with_level_triggered_signals(NonZeroUsize::new(10).unwrap(), 1, false);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Signal(1), 9),
+        SyscallResult::Success
+    );
+    syscall(&mut scheduler, Syscall::Wait(1), 8);
+    assert_eq!(scheduler.next(), SchedulingDecision::Deadlock);
+}
+
+#[test]
+pub fn level_triggered_signal_without_waiter() {
+    let mut scheduler =
+        RoundRobinScheduler::with_level_triggered_signals(NonZeroUsize::new(10).unwrap(), 1, true);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Signal(1), 9),
+        SyscallResult::Success
+    );
+    syscall(&mut scheduler, Syscall::Signal(1), 8);
+
+    // the event stays signaled until a wait, the second signal counts once
+    syscall(&mut scheduler, Syscall::Wait(1), 7);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 7));
+    assert_eq!(states(&mut scheduler), vec![ProcessState::Running]);
+    syscall(&mut scheduler, Syscall::Wait(1), 6);
+    assert_eq!(scheduler.next(), SchedulingDecision::Deadlock);
+}
+
+#[test]
+pub fn level_triggered_from_builder() {
+    let mut scheduler = SchedulerBuilder::new()
+        .timeslice(10)
+        .minimum_remaining(1)
+        .level_triggered_signals(true)
+        .build_round_robin()
+        .unwrap();
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Signal(1), 9);
+    syscall(&mut scheduler, Syscall::WaitTimeout { event: 1, max: 5 }, 8);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 8));
+
+    assert!(SchedulerBuilder::new()
+        .timeslice(10)
+        .level_triggered_signals(true)
+        .build_cfs()
+        .is_err());
+}
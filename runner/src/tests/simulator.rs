@@ -0,0 +1,82 @@
+use processor::simulator::{SimulationError, Simulator};
+use scheduler::{round_robin, Pid, SchedulingDecision};
+use std::num::NonZeroUsize;
+
+fn simulator(workload: &str) -> Simulator {
+    let scheduler = round_robin(NonZeroUsize::new(2).unwrap(), 1);
+    Simulator::new(Box::new(scheduler), workload.parse().unwrap())
+}
+
+fn run(pid: usize, timeslice: usize) -> SchedulingDecision {
+    SchedulingDecision::Run {
+        pid: Pid::new(pid),
+        timeslice: NonZeroUsize::new(timeslice).unwrap(),
+    }
+}
+
+#[test]
+pub fn round_robin_decisions() {
+    let simulation = simulator(
+        "\
+run 2
+fork
+    run 4
+    wait 1
+    signal 2
+sleep 6
+signal 1
+wait 2
+",
+    )
+    .with_timeline()
+    .run()
+    .unwrap();
+
+    assert_eq!(
+        simulation.decisions,
+        vec![
+            run(1, 2),
+            run(1, 2),
+            run(1, 1),
+            run(2, 2),
+            run(2, 2),
+            run(2, 2),
+            SchedulingDecision::Sleep(NonZeroUsize::new(1).unwrap()),
+            run(1, 2),
+            run(1, 1),
+            run(2, 2),
+            run(2, 1),
+            run(1, 2),
+            SchedulingDecision::Done,
+        ]
+    );
+    assert_eq!(simulation.elapsed, 15);
+    assert!(simulation.processes.is_empty());
+
+    let (init, child) = (Pid::new(1), Pid::new(2));
+    assert_eq!(
+        simulation.timeline.unwrap().gantt(),
+        vec![
+            (init, 0, 4),
+            (child, 4, 9),
+            (init, 10, 12),
+            (child, 12, 14),
+            (init, 14, 15)
+        ]
+    );
+}
+
+#[test]
+pub fn init_exits_first() {
+    let simulation = simulator("fork\n    run 5\nrun 1").run().unwrap();
+    assert_eq!(simulation.decisions.last(), Some(&SchedulingDecision::Panic));
+    assert_eq!(simulation.processes.len(), 1);
+    assert!(simulation.timeline.is_none());
+}
+
+#[test]
+pub fn acts_after_exit() {
+    let error = simulator("fork\n    exit\n    run 1\nwait 1").run().unwrap_err();
+    assert_eq!(error, SimulationError::ActsAfterExit(Pid::new(2)));
+    assert_eq!(error.to_string(), "process 2 has operations after its exit");
+}
@@ -0,0 +1,116 @@
+use scheduler::{
+    cfs, fcfs, priority_queue, round_robin, Pid, Scheduler, SchedulingDecision, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn sleep_zero() {
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(0), timeslice - 1);
+
+    assert!(matches!(
+        scheduler.next(),
+        SchedulingDecision::Run { pid, .. } if pid == 1
+    ));
+}
+
+#[test]
+pub fn sleep_zero_with_ready_process() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(0), timeslice - 2);
+
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    assert!(scheduler
+        .list()
+        .iter()
+        .all(|p| p.state() != scheduler::ProcessState::Waiting { event: None }));
+}
+
+#[test]
+pub fn sleep_zero_rotates_fairly() {
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+
+    // pid 1 computes a little and sleeps for 0, pid 2 only computes
+    let mut order = vec![];
+    for _ in 0..6 {
+        let (pid, timeslice) = dispatch(&mut scheduler);
+        order.push((pid, timeslice));
+        if pid == 1 {
+            syscall(&mut scheduler, Syscall::Sleep(0), timeslice - 2);
+        } else {
+            scheduler.stop(StopReason::expired());
+        }
+    }
+
+    let pid_1 = Pid::new(1);
+    let pid_2 = Pid::new(2);
+    assert_eq!(
+        order,
+        vec![(pid_1, 2), (pid_2, 3), (pid_1, 3), (pid_2, 3), (pid_1, 3), (pid_2, 3)]
+    );
+    assert!(scheduler
+        .list()
+        .iter()
+        .all(|p| p.state() != scheduler::ProcessState::Waiting { event: None }));
+}
+
+/// Lets the only process sleep and exit once it has woken up.
+fn lone_sleeper(mut scheduler: impl Scheduler) {
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(4), timeslice - 1);
+
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(4).unwrap())
+    );
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Exit, timeslice - 1);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+}
+
+#[test]
+pub fn only_sleepers_left() {
+    let timeslice = NonZeroUsize::new(3).unwrap();
+    lone_sleeper(round_robin(timeslice, 1));
+    lone_sleeper(priority_queue(timeslice, 1));
+    lone_sleeper(cfs(timeslice, 1));
+    lone_sleeper(fcfs(timeslice));
+}
+
+#[test]
+pub fn sleeper_wakes_while_others_are_ready() {
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    syscall(&mut scheduler, Syscall::Fork(0), 1);
+    syscall(&mut scheduler, Syscall::Sleep(1), 0);
+
+    let mut order = vec![];
+    for _ in 0..4 {
+        order.push(dispatch(&mut scheduler).0);
+        scheduler.stop(StopReason::expired());
+    }
+    // the sleep has ended while process 2 was running, so process 1 runs
+    // while processes 2 and 3 are still ready instead of waiting for the
+    // ready queue to drain
+    assert_eq!(order[..2], [2, 3].map(Pid::new));
+    assert!(order.contains(&Pid::new(1)), "{order:?}");
+}
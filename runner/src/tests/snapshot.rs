@@ -0,0 +1,99 @@
+use scheduler::{
+    RoundRobinScheduler, Scheduler, SchedulerSnapshot, SchedulingDecision, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+/// What the next dispatched process does, `None` to use its whole timeslice.
+type Action = Option<Syscall>;
+
+const FIRST: [Action; 6] = [
+    Some(Syscall::Fork(0)),
+    Some(Syscall::Fork(2)),
+    None,
+    Some(Syscall::Wait(1)),
+    Some(Syscall::Sleep(4)),
+    Some(Syscall::Fork(1)),
+];
+
+const SECOND: [Action; 8] = [
+    None,
+    Some(Syscall::Signal(1)),
+    Some(Syscall::Yield),
+    None,
+    Some(Syscall::Exit),
+    Some(Syscall::Sleep(2)),
+    None,
+    Some(Syscall::Exit),
+];
+
+/// Plays the actions and returns all the decisions of the scheduler.
+fn play(scheduler: &mut RoundRobinScheduler, actions: &[Action]) -> Vec<SchedulingDecision> {
+    let mut decisions = vec![];
+    for action in actions {
+        let timeslice = loop {
+            let decision = scheduler.next();
+            decisions.push(decision);
+            match decision {
+                SchedulingDecision::Run { timeslice, .. } => break timeslice.get(),
+                SchedulingDecision::Sleep(_) => continue,
+                _ => return decisions,
+            }
+        };
+        let reason = match action {
+            Some(syscall) => StopReason::Syscall {
+                syscall: *syscall,
+                remaining: timeslice / 2,
+            },
+            None => StopReason::Expired,
+        };
+        scheduler.stop(reason);
+    }
+    decisions
+}
+
+fn timings(scheduler: &mut RoundRobinScheduler) -> Vec<(usize, (usize, usize, usize))> {
+    scheduler
+        .list()
+        .iter()
+        .map(|p| (p.pid().get(), p.timings()))
+        .collect()
+}
+
+fn scheduler() -> RoundRobinScheduler {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(4).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    scheduler
+}
+
+#[test]
+pub fn restore_takes_the_same_decisions() {
+    let mut original = scheduler();
+    play(&mut original, &FIRST);
+    let snapshot = original.snapshot();
+    let expected = play(&mut original, &SECOND);
+
+    let mut restored = RoundRobinScheduler::restore(snapshot);
+    assert_eq!(play(&mut restored, &SECOND), expected);
+    assert_eq!(timings(&mut restored), timings(&mut original));
+    assert_eq!(restored.statistics(), original.statistics());
+}
+
+#[test]
+pub fn restore_from_json() {
+    let mut original = scheduler();
+    play(&mut original, &FIRST);
+    let json = serde_json::to_string(&original.snapshot()).unwrap();
+    let expected = play(&mut original, &SECOND);
+
+    let snapshot = serde_json::from_str::<SchedulerSnapshot>(&json).unwrap();
+    let mut restored = RoundRobinScheduler::restore(snapshot);
+    assert_eq!(play(&mut restored, &SECOND), expected);
+    assert_eq!(timings(&mut restored), timings(&mut original));
+}
+
+#[test]
+pub fn zero_timeslice_in_json() {
+    let json = serde_json::to_string(&scheduler().snapshot()).unwrap();
+    let json = json.replace("\"timeslice\":4", "\"timeslice\":0");
+    assert!(serde_json::from_str::<SchedulerSnapshot>(&json).is_err());
+}
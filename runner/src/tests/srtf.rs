@@ -0,0 +1,84 @@
+use scheduler::{
+    Pid, Scheduler, SchedulerError, SchedulingDecision, SrtfScheduler, StopReason, Syscall,
+    SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn short_job_preempts() {
+    let mut scheduler = SrtfScheduler::new(NonZeroUsize::new(20).unwrap());
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    scheduler.set_remaining(Pid::new(1), 10);
+
+    // the long job runs until it is done
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 10));
+    syscall(&mut scheduler, Syscall::Fork(0), 7);
+    scheduler.set_remaining(Pid::new(2), 2);
+
+    // the short job that has been forked runs first
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 2));
+    assert_eq!(scheduler.list()[0].state(), scheduler::ProcessState::Ready);
+    assert_eq!(scheduler.list()[0].extra(), "remaining=7");
+    syscall(&mut scheduler, Syscall::Exit, 1);
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 7));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(scheduler.list()[0].extra(), "remaining=0");
+}
+
+#[test]
+pub fn ties_break_by_pid() {
+    let mut scheduler = SrtfScheduler::new(NonZeroUsize::new(5).unwrap());
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::Fork(0), 3);
+    scheduler.set_remaining(Pid::new(1), 9);
+    scheduler.set_remaining(Pid::new(3), 4);
+    scheduler.set_remaining(Pid::new(2), 4);
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 4));
+    scheduler.stop(StopReason::Expired);
+    // process 2 is done, but it has not exited yet
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 1));
+}
+
+#[test]
+pub fn sleeper_wakes_up_shorter() {
+    let mut scheduler = SrtfScheduler::new(NonZeroUsize::new(20).unwrap());
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 19);
+    scheduler.set_remaining(Pid::new(1), 1);
+    scheduler.set_remaining(Pid::new(2), 8);
+    syscall(&mut scheduler, Syscall::Sleep(3), 18);
+
+    // process 2 is granted time only until process 1 wakes up
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 3));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 1));
+    syscall(&mut scheduler, Syscall::Exit, 0);
+    assert_eq!(scheduler.next(), SchedulingDecision::Panic);
+}
+
+#[test]
+pub fn sleep_too_long() {
+    let mut scheduler = SrtfScheduler::new(NonZeroUsize::new(6).unwrap());
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+
+    let error = SyscallResult::Error(SchedulerError::InvalidSleepDuration(usize::MAX));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Sleep(usize::MAX), timeslice - 1),
+        error
+    );
+    let wait = Syscall::WaitTimeout {
+        event: 1,
+        max: usize::MAX,
+    };
+    assert_eq!(syscall(&mut scheduler, wait, timeslice - 2), error);
+    // the process goes on running
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+}
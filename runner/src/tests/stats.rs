@@ -0,0 +1,114 @@
+use scheduler::{
+    Aggregate, Pid, ProcessStats, RoundRobinScheduler, Scheduler, SchedulingDecision, StopReason,
+    Syscall,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn hand_computed() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    // process 1 forks process 2 at 2, then runs until 5
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+    syscall(&mut scheduler, Syscall::Fork(0), 3);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 3));
+    scheduler.stop(StopReason::Expired);
+
+    // process 2 runs from 5 and exits at 9
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 5));
+    syscall(&mut scheduler, Syscall::Exit, 1);
+    let stats = scheduler.stats();
+    assert_eq!(
+        stats.process(Pid::new(1)),
+        Some(&ProcessStats {
+            pid: Pid::new(1),
+            arrival: 0,
+            turnaround: None,
+            waiting: 4,
+            response: Some(0),
+            dispatches: 2,
+            syscalls: 1,
+        })
+    );
+
+    // process 1 runs from 9 and exits at 12
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+    syscall(&mut scheduler, Syscall::Exit, 2);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+
+    // the exited processes are kept
+    let stats = scheduler.stats();
+    assert_eq!(
+        stats.processes,
+        [
+            ProcessStats {
+                pid: Pid::new(1),
+                arrival: 0,
+                turnaround: Some(12),
+                waiting: 4,
+                response: Some(0),
+                dispatches: 3,
+                syscalls: 2,
+            },
+            ProcessStats {
+                pid: Pid::new(2),
+                arrival: 2,
+                turnaround: Some(7),
+                waiting: 3,
+                response: Some(3),
+                dispatches: 1,
+                syscalls: 1,
+            },
+        ]
+    );
+    assert_eq!(stats.turnaround, Aggregate { mean: 9.5, max: 12 });
+    assert_eq!(stats.waiting, Aggregate { mean: 3.5, max: 4 });
+    assert_eq!(stats.response, Aggregate { mean: 1.5, max: 3 });
+    assert_eq!(stats.dispatches, Aggregate { mean: 2.0, max: 3 });
+    assert_eq!(stats.syscalls, Aggregate { mean: 1.5, max: 2 });
+}
+
+#[test]
+pub fn killed_while_running() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::Yield, 3);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Kill(Pid::new(2)), 2);
+
+    // the process has been killed at 5, 4 time units after it has been forked
+    let stats = scheduler.stats();
+    assert_eq!(stats.process(Pid::new(2)).unwrap().turnaround, Some(4));
+    assert_eq!(stats.turnaround, Aggregate { mean: 4.0, max: 4 });
+    assert_eq!(stats.response, Aggregate { mean: 0.5, max: 1 });
+}
+
+#[test]
+pub fn killed_while_ready() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::Kill(Pid::new(2)), 3);
+
+    // the process is kept although it has never run
+    assert_eq!(
+        scheduler.stats().process(Pid::new(2)),
+        Some(&ProcessStats {
+            pid: Pid::new(2),
+            arrival: 1,
+            turnaround: Some(1),
+            waiting: 1,
+            response: None,
+            dispatches: 0,
+            syscalls: 0,
+        })
+    );
+    syscall(&mut scheduler, Syscall::Exit, 2);
+    assert_eq!(scheduler.stats().processes.len(), 2);
+}
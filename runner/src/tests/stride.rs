@@ -0,0 +1,120 @@
+use scheduler::{
+    Pid, Scheduler, SchedulerError, StopReason, StrideScheduler, Syscall, SyscallResult,
+};
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+/// Process 1 has one ticket and forks process 2, which has two tickets.
+fn one_to_two() -> StrideScheduler {
+    let mut scheduler = StrideScheduler::new(NonZeroUsize::new(3).unwrap());
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 3));
+    syscall(&mut scheduler, Syscall::Fork(1), 2);
+    scheduler.stop(StopReason::Expired);
+    scheduler
+}
+
+#[test]
+pub fn shares_follow_tickets() {
+    let mut scheduler = one_to_two();
+    let mut dispatches = HashMap::<Pid, usize>::new();
+    for _ in 0..300 {
+        let (pid, _) = dispatch(&mut scheduler);
+        *dispatches.entry(pid).or_default() += 1;
+        scheduler.stop(StopReason::Expired);
+    }
+
+    assert_eq!(dispatches[&Pid::new(1)], 100);
+    assert_eq!(dispatches[&Pid::new(2)], 200);
+    assert_eq!(scheduler.list()[0].timings().2, 2 + 100 * 3);
+    assert_eq!(scheduler.list()[1].timings().2, 200 * 3);
+}
+
+#[test]
+pub fn time_left_from_timeslice() {
+    let mut scheduler = one_to_two();
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 3));
+    syscall(&mut scheduler, Syscall::SetPriority(0), 2);
+    // the process goes on without growing its pass again
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 3));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 3));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 3));
+}
+
+#[test]
+pub fn fork_starts_from_lowest_pass() {
+    let mut scheduler = one_to_two();
+    for _ in 0..30 {
+        dispatch(&mut scheduler);
+        scheduler.stop(StopReason::Expired);
+    }
+    assert_eq!(
+        scheduler.list()[0].extra(),
+        format!("tickets=1 pass={}", 11 << 20)
+    );
+    assert_eq!(
+        scheduler.list()[1].extra(),
+        format!("tickets=2 pass={}", 22 << 19)
+    );
+
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    assert_eq!(
+        scheduler.list()[2].extra(),
+        format!("tickets=1 pass={}", 11 << 20)
+    );
+    scheduler.stop(StopReason::Expired);
+
+    // process 3 takes turns with the others instead of catching up
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(3));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+}
+
+#[test]
+pub fn sleeper_does_not_catch_up() {
+    let mut scheduler = one_to_two();
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Sleep(12), 2);
+    for _ in 0..5 {
+        assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+        scheduler.stop(StopReason::Expired);
+    }
+
+    // process 2 has woken up with the pass of process 1
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+}
+
+#[test]
+pub fn sleep_too_long() {
+    let mut scheduler = StrideScheduler::new(NonZeroUsize::new(6).unwrap());
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+
+    let error = SyscallResult::Error(SchedulerError::InvalidSleepDuration(usize::MAX));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::Sleep(usize::MAX), timeslice - 1),
+        error
+    );
+    let wait = Syscall::WaitTimeout {
+        event: 1,
+        max: usize::MAX,
+    };
+    assert_eq!(syscall(&mut scheduler, wait, timeslice - 2), error);
+    // the process goes on running
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+}
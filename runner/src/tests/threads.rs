@@ -0,0 +1,69 @@
+use processor::compare::Factory;
+use processor::simulator::{Simulation, Simulator};
+use scheduler::{
+    CfsScheduler, CheckedScheduler, EventBus, FcfsScheduler, LoggingScheduler, ObservedScheduler,
+    PriorityRoundRobinScheduler, RoundRobinScheduler, Scheduler, SchedulingDecision, SrtfScheduler,
+    StrideScheduler, TimelineScheduler,
+};
+use std::num::NonZeroUsize;
+use std::thread;
+
+fn assert_send<T: Send>() {}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+pub fn schedulers_are_send_and_sync() {
+    assert_send_sync::<RoundRobinScheduler>();
+    assert_send_sync::<PriorityRoundRobinScheduler>();
+    assert_send_sync::<CfsScheduler>();
+    assert_send_sync::<FcfsScheduler>();
+    assert_send_sync::<SrtfScheduler>();
+    assert_send_sync::<StrideScheduler>();
+    assert_send_sync::<TimelineScheduler<RoundRobinScheduler>>();
+    assert_send_sync::<LoggingScheduler<RoundRobinScheduler>>();
+    assert_send_sync::<ObservedScheduler<RoundRobinScheduler>>();
+    assert_send_sync::<CheckedScheduler<RoundRobinScheduler>>();
+    assert_send_sync::<EventBus>();
+    assert_send::<Box<dyn Scheduler>>();
+    assert_send::<Simulator>();
+    assert_send_sync::<Factory>();
+}
+
+const WORKLOAD: &str = "\
+run 2
+fork
+    run 5
+    sleep 3
+    run 1
+fork
+    run 4
+run 1
+sleep 30
+run 3
+";
+
+fn simulate(timeslice: usize) -> Simulation {
+    let scheduler = RoundRobinScheduler::new(NonZeroUsize::new(timeslice).unwrap(), 1);
+    Simulator::new(Box::new(scheduler), WORKLOAD.parse().unwrap())
+        .run()
+        .unwrap()
+}
+
+#[test]
+pub fn simulations_in_threads() {
+    let simulations = thread::scope(|scope| {
+        let threads = (1..=100)
+            .map(|i| scope.spawn(move || simulate(i % 7 + 1)))
+            .collect::<Vec<_>>();
+        threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    for (i, simulation) in (1..=100).zip(simulations) {
+        assert_eq!(simulation.decisions.last(), Some(&SchedulingDecision::Done));
+        assert_eq!(simulation, simulate(i % 7 + 1), "simulation {}", i);
+    }
+}
@@ -0,0 +1,70 @@
+use scheduler::{
+    round_robin, Activity, Pid, Scheduler, SchedulingDecision, Segment, StopReason, Syscall,
+    TimelineScheduler,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn round_robin_timeline() {
+    let mut scheduler = TimelineScheduler::new(round_robin(NonZeroUsize::new(3).unwrap(), 1));
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(5), 1);
+    dispatch(&mut scheduler);
+    scheduler.stop(StopReason::Expired);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, 2);
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(1).unwrap())
+    );
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, 0);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+
+    let (init, child) = (Pid::new(1), Pid::new(2));
+    assert_eq!(
+        scheduler.timeline().segments(),
+        [
+            Segment { start: 0, end: 2, what: Activity::Running(init) },
+            Segment { start: 2, end: 6, what: Activity::Running(child) },
+            Segment { start: 6, end: 7, what: Activity::Sleeping },
+            Segment { start: 7, end: 8, what: Activity::Running(init) },
+        ]
+    );
+    assert_eq!(
+        scheduler.timeline().to_string(),
+        "    0     2  RUN 1\n    2     6  RUN 2\n    6     7  SLEEP\n    7     8  RUN 1\n"
+    );
+}
+
+#[test]
+pub fn gantt_intervals() {
+    let mut scheduler = TimelineScheduler::new(round_robin(NonZeroUsize::new(2).unwrap(), 1));
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 1);
+    dispatch(&mut scheduler);
+    scheduler.stop(StopReason::Expired);
+    dispatch(&mut scheduler);
+    scheduler.stop(StopReason::Expired);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(4), 1);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, 1);
+    scheduler.next();
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, 0);
+
+    // the sleep between 6 and 9 is not an interval
+    let (init, child) = (Pid::new(1), Pid::new(2));
+    assert_eq!(
+        scheduler.timeline().gantt(),
+        vec![(init, 0, 2), (child, 2, 4), (init, 4, 5), (child, 5, 6), (init, 9, 10)]
+    );
+}
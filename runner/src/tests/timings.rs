@@ -0,0 +1,364 @@
+use scheduler::{
+    cfs, fcfs, priority_queue, round_robin, Pid, RoundRobinScheduler, Scheduler, SchedulerStats,
+    SchedulingDecision, SrtfScheduler, StopReason, StrideScheduler, Syscall,
+};
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+/// What the scheduled process does with its timeslice.
+enum Action {
+    /// Execute for a number of time units and then issue a system call.
+    Call(usize, Syscall),
+    /// Execute until the timeslice expires.
+    Expire,
+}
+
+/// Runs the actions on a round robin scheduler and returns the
+/// timings of the processes that are still alive.
+fn timings(actions: &[Action]) -> Vec<(Pid, (usize, usize, usize))> {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    for action in actions {
+        let timeslice = loop {
+            match scheduler.next() {
+                SchedulingDecision::Run { timeslice, .. } => break timeslice.get(),
+                SchedulingDecision::Sleep(_) => continue,
+                decision => panic!("expected a process to be scheduled, got {decision}"),
+            }
+        };
+        match *action {
+            Action::Call(executed, syscall) => {
+                scheduler.stop(StopReason::Syscall {
+                    syscall,
+                    remaining: timeslice - executed - 1,
+                });
+            }
+            Action::Expire => {
+                scheduler.stop(StopReason::expired());
+            }
+        }
+    }
+
+    let mut timings = scheduler
+        .list()
+        .iter()
+        .map(|p| (p.pid(), p.timings()))
+        .collect::<Vec<_>>();
+    timings.sort_by_key(|(pid, _)| *pid);
+    timings
+}
+
+#[test]
+pub fn charge_consumed_time() {
+    use Action::*;
+
+    let cases = vec![
+        (
+            "fork, expire and exit",
+            vec![Call(2, Syscall::Fork(0)), Expire, Call(1, Syscall::Exit)],
+            vec![(Pid::new(1), (7, 1, 4))],
+        ),
+        (
+            "two forks and expiries",
+            vec![
+                Call(0, Syscall::Fork(0)),
+                Call(1, Syscall::Fork(0)),
+                Expire,
+                Expire,
+                Expire,
+            ],
+            vec![
+                (Pid::new(1), (15, 2, 3)),
+                (Pid::new(2), (14, 0, 5)),
+                (Pid::new(3), (12, 0, 5)),
+            ],
+        ),
+        (
+            "sleep and expire",
+            vec![Call(2, Syscall::Sleep(4)), Expire],
+            vec![(Pid::new(1), (9, 1, 4))],
+        ),
+    ];
+
+    for (name, actions, expected) in cases {
+        assert_eq!(timings(&actions), expected, "{name}");
+    }
+}
+
+#[test]
+pub fn syscall_at_slice_end() {
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 0);
+
+    // pid 1 has used its whole timeslice, so pid 2 runs first
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), timeslice));
+    scheduler.stop(StopReason::expired());
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), timeslice));
+    syscall(&mut scheduler, Syscall::Signal(1), timeslice - 1);
+
+    let mut timings = scheduler
+        .list()
+        .iter()
+        .map(|p| (p.pid(), p.timings()))
+        .collect::<Vec<_>>();
+    timings.sort_by_key(|(pid, _)| *pid);
+    assert_eq!(timings, vec![(Pid::new(1), (7, 2, 2)), (Pid::new(2), (4, 0, 3))]);
+}
+
+#[test]
+pub fn syscall_at_slice_end_single_process() {
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Signal(1), 0);
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), timeslice));
+    scheduler.stop(StopReason::expired());
+
+    let list = scheduler.list();
+    assert_eq!(list[0].timings(), (6, 1, 5));
+}
+
+#[test]
+pub fn cpu_time() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 2);
+    scheduler.next();
+    scheduler.stop(StopReason::expired());
+
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    assert_eq!(pid, 2);
+    syscall(&mut scheduler, Syscall::Exit, timeslice - 2);
+
+    let execution = scheduler.list()[0].timings().2;
+    assert_eq!(scheduler.cpu_time(Pid::new(1)), Some(execution));
+    assert_eq!(scheduler.cpu_time(Pid::new(1)), Some(2));
+    assert_eq!(scheduler.cpu_time(Pid::new(2)), None);
+    assert_eq!(scheduler.cpu_time(Pid::new(3)), None);
+}
+
+#[test]
+pub fn context_switches() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    assert_eq!(scheduler.context_switches(), 0);
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    dispatch(&mut scheduler);
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(scheduler.context_switches(), 1);
+
+    // every slice boundary switches to the other process
+    for _ in 0..5 {
+        dispatch(&mut scheduler);
+        scheduler.stop(StopReason::Expired);
+    }
+    assert_eq!(scheduler.context_switches(), 6);
+}
+
+#[test]
+pub fn statistics() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    syscall(&mut scheduler, Syscall::Sleep(4), 1);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Exit, 1);
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(2).unwrap())
+    );
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, 0);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+
+    // process 2 lives from 1 to 4, process 1 from 0 to 7
+    assert_eq!(
+        scheduler.statistics(),
+        SchedulerStats {
+            total_time: 7,
+            idle_time: 2,
+            completed: 2,
+            context_switches: 3,
+            average_turnaround: 5.0,
+        }
+    );
+}
+
+#[test]
+pub fn expiry_credits_waiters() {
+    let mut scheduler = round_robin(NonZeroUsize::new(4).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 3);
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    // the waiters are credited with the 2 time units left, not a full timeslice
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    scheduler.stop(StopReason::Expired);
+
+    let timings = scheduler
+        .list()
+        .iter()
+        .map(|p| p.timings())
+        .collect::<Vec<_>>();
+    assert_eq!(timings, vec![(8, 2, 2), (7, 0, 4), (6, 0, 0)]);
+}
+
+#[test]
+pub fn just_forked() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    // the child has not been charged for the fork of its parent
+    let child = scheduler.list()[1];
+    assert_eq!(child.pid(), Pid::new(2));
+    assert_eq!(child.timings(), (0, 0, 0));
+
+    // the parent keeps what is left of its timeslice, the child gets a full one
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+    scheduler.stop(StopReason::Expired);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), 5));
+}
+
+#[test]
+pub fn fork_credits_every_waiter() {
+    let mut scheduler = round_robin(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    for remaining in [9, 8, 7] {
+        syscall(&mut scheduler, Syscall::Fork(0), remaining);
+    }
+    let before = scheduler
+        .list()
+        .iter()
+        .map(|p| p.timings())
+        .collect::<Vec<_>>();
+    assert_eq!(before, vec![(3, 3, 0), (2, 0, 0), (1, 0, 0), (0, 0, 0)]);
+
+    // the parent has run for 4 time units before this fork, and so has
+    // every queued process waited, whatever its place in the queue
+    syscall(&mut scheduler, Syscall::Fork(0), 3);
+    let after = scheduler
+        .list()
+        .iter()
+        .map(|p| p.timings())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        after,
+        vec![(7, 4, 3), (6, 0, 0), (5, 0, 0), (4, 0, 0), (0, 0, 0)]
+    );
+
+    // the parent keeps what is left of its timeslice
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 3));
+}
+
+/// Issues a mix of system calls and expiries on `scheduler`, and checks
+/// that every process has its system calls counted once.
+fn count_syscalls(mut scheduler: impl Scheduler) {
+    use Action::*;
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let actions = [
+        Call(0, Syscall::Fork(0)),
+        Call(0, Syscall::Fork(0)),
+        Expire,
+        Call(0, Syscall::Wait(2)),
+        Call(0, Syscall::Signal(2)),
+        Expire,
+        Call(0, Syscall::Sleep(0)),
+        Call(0, Syscall::Yield),
+        Call(0, Syscall::SetPriority(1)),
+        Expire,
+        Call(0, Syscall::Signal(5)),
+        Expire,
+    ];
+    let mut issued = HashMap::new();
+    for action in actions {
+        let (pid, timeslice) = dispatch(&mut scheduler);
+        match action {
+            Call(executed, syscall) => {
+                *issued.entry(pid).or_insert(0) += 1;
+                scheduler.stop(StopReason::Syscall {
+                    syscall,
+                    remaining: timeslice - executed - 1,
+                });
+            }
+            Expire => {
+                scheduler.stop(StopReason::Expired);
+            }
+        }
+    }
+
+    for process in scheduler.list() {
+        let expected = issued.get(&process.pid()).copied().unwrap_or(0);
+        assert_eq!(process.timings().1, expected, "process {}", process.pid());
+    }
+}
+
+#[test]
+pub fn syscall_count() {
+    let timeslice = NonZeroUsize::new(3).unwrap();
+    count_syscalls(round_robin(timeslice, 1));
+    count_syscalls(priority_queue(timeslice, 1));
+    count_syscalls(cfs(timeslice, 1));
+    count_syscalls(fcfs(timeslice));
+    count_syscalls(SrtfScheduler::new(timeslice));
+    count_syscalls(StrideScheduler::new(timeslice));
+}
+
+/// Makes the running process sleep after it has executed for two time
+/// units and checks that the ready process has waited for the whole time.
+fn credit_sleep(mut scheduler: impl Scheduler) {
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+
+    let (_, timeslice) = dispatch(&mut scheduler);
+    assert!(timeslice >= 3);
+    let waited = |scheduler: &mut dyn Scheduler| {
+        scheduler
+            .list()
+            .iter()
+            .map(|p| (p.pid(), p.timings().0))
+            .collect::<HashMap<_, _>>()
+    };
+    let before = waited(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(10), timeslice - 3);
+    let after = waited(&mut scheduler);
+
+    // the sleeper is charged and the other process credited the same time
+    assert_eq!(after.len(), 2);
+    for (pid, total) in after {
+        assert_eq!(total - before[&pid], 3, "process {}", pid);
+    }
+}
+
+#[test]
+pub fn sleep_credits_ready_process() {
+    let timeslice = NonZeroUsize::new(10).unwrap();
+    credit_sleep(round_robin(timeslice, 1));
+    credit_sleep(priority_queue(timeslice, 1));
+    credit_sleep(cfs(timeslice, 1));
+    credit_sleep(fcfs(timeslice));
+    credit_sleep(SrtfScheduler::new(timeslice));
+    credit_sleep(StrideScheduler::new(timeslice));
+}
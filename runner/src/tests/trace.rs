@@ -0,0 +1,49 @@
+use scheduler::{round_robin, Pid, Scheduler, SchedulerEvent, SchedulingDecision, StopReason, Syscall};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn round_robin_events() {
+    let events = Arc::new(Mutex::new(vec![]));
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+    let trace = events.clone();
+    scheduler.set_trace(Box::new(move |event| trace.lock().unwrap().push(*event)));
+
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Sleep(5), 1);
+    dispatch(&mut scheduler);
+    scheduler.stop(StopReason::Expired);
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, 2);
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(1).unwrap())
+    );
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Exit, 0);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+
+    let (init, child) = (Pid::new(1), Pid::new(2));
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            SchedulerEvent::Fork { pid: init, time: 0 },
+            SchedulerEvent::Dispatch { pid: init, time: 0 },
+            SchedulerEvent::Fork { pid: child, time: 1 },
+            SchedulerEvent::Dispatch { pid: init, time: 1 },
+            SchedulerEvent::Block { pid: init, time: 2 },
+            SchedulerEvent::Dispatch { pid: child, time: 2 },
+            SchedulerEvent::Preempt { pid: child, time: 5 },
+            SchedulerEvent::Dispatch { pid: child, time: 5 },
+            SchedulerEvent::Exit { pid: child, time: 6 },
+            SchedulerEvent::Wake { pid: init, time: 7 },
+            SchedulerEvent::Dispatch { pid: init, time: 7 },
+            SchedulerEvent::Exit { pid: init, time: 8 },
+        ]
+    );
+}
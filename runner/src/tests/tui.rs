@@ -0,0 +1,169 @@
+use processor::tui::{
+    gantt_strip, queue_lines, render, status_line, timing_lines, Player, ProcessView, ViewModel,
+};
+use processor::workload::Workload;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use scheduler::{
+    make_scheduler, ObservedEvent, Pid, ProcessState, SchedulerConfig, SchedulerKind,
+    SchedulingDecision, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+const WORKLOAD: &str = "\
+run 3
+fork
+    run 2
+    wait 1
+sleep 4
+signal 1
+sleep 5
+exit
+";
+
+fn player() -> Player {
+    let config = SchedulerConfig::new(NonZeroUsize::new(2).unwrap(), 1);
+    Player::simulate(
+        make_scheduler(SchedulerKind::RoundRobin, config),
+        Workload::parse(WORKLOAD).unwrap(),
+    )
+    .unwrap()
+}
+
+/// The text of every row of `buffer`.
+fn rows(buffer: &Buffer) -> Vec<String> {
+    buffer
+        .content()
+        .chunks(buffer.area.width as usize)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+        .collect()
+}
+
+#[test]
+pub fn view_from_events() {
+    let pid = Pid::new;
+    let timeslice = NonZeroUsize::new(3).unwrap();
+    let mut view = ViewModel::new();
+    let events = [
+        ObservedEvent::Fork {
+            parent: None,
+            child: pid(1),
+        },
+        ObservedEvent::StateChange {
+            pid: pid(1),
+            old: ProcessState::Ready,
+            new: ProcessState::Running,
+        },
+        ObservedEvent::Dispatch {
+            pid: pid(1),
+            timeslice,
+            now: 0,
+        },
+        ObservedEvent::Stop {
+            pid: Some(pid(1)),
+            reason: StopReason::syscall(Syscall::Fork(0)),
+            now: 1,
+        },
+        ObservedEvent::Fork {
+            parent: Some(pid(1)),
+            child: pid(2),
+        },
+        ObservedEvent::Stop {
+            pid: Some(pid(1)),
+            reason: StopReason::syscall(Syscall::Wait(4)),
+            now: 2,
+        },
+        ObservedEvent::StateChange {
+            pid: pid(1),
+            old: ProcessState::Running,
+            new: ProcessState::Waiting { event: Some(4) },
+        },
+    ];
+    for event in events.iter() {
+        view.apply(event);
+    }
+
+    assert_eq!(view.time, 2);
+    assert_eq!(view.running, None);
+    assert_eq!(view.ready, vec![pid(2)]);
+    assert_eq!(view.waiting, vec![(pid(1), 4)]);
+    assert_eq!(view.gantt, vec![(pid(1), 0, 2)]);
+    assert_eq!(
+        view.processes[0],
+        ProcessView {
+            pid: pid(1),
+            state: Some(ProcessState::Waiting { event: Some(4) }),
+            created: 0,
+            running: 2,
+            syscalls: 2,
+        }
+    );
+    assert_eq!(view.processes[1].created, 1);
+
+    assert_eq!(
+        queue_lines(&view),
+        vec![
+            "running   -",
+            "ready     2",
+            "sleeping  -",
+            "waiting   1(event 4)",
+        ]
+    );
+    assert_eq!(gantt_strip(&view, 4), "11");
+    assert_eq!(
+        timing_lines(&view, 4),
+        vec![
+            "   1 EVENT 4   [####] 2/2 syscalls 2",
+            "   2 READY     [----] 0/1 syscalls 0",
+        ]
+    );
+}
+
+#[test]
+pub fn step_through_simulation() {
+    let mut player = player();
+    assert_eq!(player.view(), &ViewModel::new());
+
+    // every step ends with a dispatch
+    player.step();
+    assert_eq!(player.view().dispatches, 1);
+    assert_eq!(player.view().running, Some(Pid::new(1)));
+    assert_eq!(player.view().outcome, None);
+    player.step();
+    assert_eq!(player.view().dispatches, 2);
+    assert_eq!(player.view().time, 2);
+
+    player.run_to_end();
+    assert!(player.is_done());
+    let view = player.view();
+    assert_eq!(view.outcome, Some(SchedulingDecision::Done));
+    assert!(view.processes.iter().all(|p| p.state.is_none()));
+    assert!(view.ready.is_empty() && view.waiting.is_empty());
+    assert!(status_line(view).ends_with("Done, no more processes"));
+
+    // the idle time while process 1 sleeps is left empty
+    let strip = gantt_strip(view, view.time);
+    assert_eq!(strip.len(), view.time);
+    assert!(strip.contains('.'));
+    assert_eq!(
+        strip.chars().filter(|&c| c == '2').count(),
+        view.processes[1].running
+    );
+}
+
+#[test]
+pub fn render_view() {
+    let mut player = player();
+    player.step();
+    let area = Rect::new(0, 0, 80, 16);
+    let mut buffer = Buffer::empty(area);
+    render(player.view(), area, &mut buffer);
+
+    let rows = rows(&buffer);
+    assert!(rows[0].contains(" scheduler-sim "));
+    assert!(rows[1].contains("time 0  dispatches 1"));
+    assert!(rows.iter().any(|row| row.contains("running   1")));
+    assert!(rows.iter().any(|row| row.contains("   1 RUNNING")));
+    assert!(rows.iter().any(|row| row.contains(" gantt ")));
+    assert!(rows[15].starts_with("space step  r run to the end  q quit"));
+}
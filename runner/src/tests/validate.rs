@@ -0,0 +1,161 @@
+use scheduler::{
+    Pid, RoundRobinScheduler, Scheduler, SchedulerSnapshot, SchedulingDecision, StopReason, Syscall,
+};
+use serde_json::{json, Value};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+/// Process 1 runs, process 2 is ready, process 3 sleeps and process 4
+/// waits for event 1 with a timeout, checking the state after every step.
+fn scheduler() -> RoundRobinScheduler {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(5).unwrap(), 1);
+    let steps: [&dyn Fn(&mut RoundRobinScheduler); 9] = [
+        &|s| {
+            s.stop(StopReason::syscall(Syscall::Fork(0)));
+        },
+        &|s| {
+            dispatch(s);
+        },
+        &|s| {
+            syscall(s, Syscall::Fork(0), 4);
+            syscall(s, Syscall::Fork(0), 3);
+            syscall(s, Syscall::Fork(0), 2);
+        },
+        &|s| {
+            s.stop(StopReason::Expired);
+        },
+        &|s| assert_eq!(dispatch(s).0, Pid::new(2)),
+        &|s| {
+            s.stop(StopReason::Expired);
+        },
+        &|s| {
+            assert_eq!(dispatch(s).0, Pid::new(3));
+            syscall(s, Syscall::Sleep(20), 3);
+        },
+        &|s| {
+            assert_eq!(dispatch(s).0, Pid::new(4));
+            syscall(s, Syscall::WaitTimeout { event: 1, max: 30 }, 4);
+        },
+        &|s| assert_eq!(dispatch(s).0, Pid::new(1)),
+    ];
+    for step in steps {
+        step(&mut scheduler);
+        assert_eq!(scheduler.validate(), Ok(()));
+    }
+    scheduler
+}
+
+/// Returns the scheduler with its state changed by `corrupt`.
+fn corrupted(corrupt: impl Fn(&mut Value)) -> RoundRobinScheduler {
+    let mut state = serde_json::to_value(scheduler().snapshot()).unwrap();
+    corrupt(&mut state);
+    RoundRobinScheduler::restore(serde_json::from_value::<SchedulerSnapshot>(state).unwrap())
+}
+
+/// The process with `pid` in the serialized state.
+fn process(state: &mut Value, pid: usize) -> &mut Value {
+    state["processes"]
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|p| p["pid"] == pid)
+        .unwrap()
+}
+
+#[test]
+pub fn valid_until_done() {
+    let mut scheduler = scheduler();
+    let steps: [&dyn Fn(&mut RoundRobinScheduler); 8] = [
+        &|s| {
+            syscall(s, Syscall::Signal(1), 4);
+            syscall(s, Syscall::WaitPid(Pid::new(3)), 3);
+        },
+        &|s| assert_eq!(dispatch(s).0, Pid::new(2)),
+        &|s| {
+            syscall(s, Syscall::Exit, 4);
+        },
+        &|s| assert_eq!(dispatch(s).0, Pid::new(4)),
+        &|s| {
+            syscall(s, Syscall::Exit, 4);
+            assert!(matches!(s.next(), SchedulingDecision::Sleep(_)));
+        },
+        &|s| {
+            assert_eq!(dispatch(s).0, Pid::new(3));
+            syscall(s, Syscall::Exit, 4);
+        },
+        &|s| {
+            assert_eq!(dispatch(s).0, Pid::new(1));
+            syscall(s, Syscall::Exit, 4);
+        },
+        &|s| assert_eq!(s.next(), SchedulingDecision::Done),
+    ];
+    for step in steps {
+        step(&mut scheduler);
+        assert_eq!(scheduler.validate(), Ok(()));
+    }
+}
+
+#[test]
+pub fn more_execution_than_total() {
+    let scheduler = corrupted(|state| process(state, 2)["timings"][2] = json!(1000));
+    assert_eq!(
+        scheduler.validate(),
+        Err(String::from(
+            "process 2 has run for 1000 time units out of 12"
+        ))
+    );
+}
+
+#[test]
+pub fn more_remaining_than_quantum() {
+    let scheduler = corrupted(|state| process(state, 2)["remaining"] = json!(6));
+    assert_eq!(
+        scheduler.validate(),
+        Err(String::from(
+            "process 2 has 6 time units left out of a quantum of 5"
+        ))
+    );
+}
+
+#[test]
+pub fn time_not_accounted() {
+    let scheduler = corrupted(|state| state["time"] = json!(100));
+    assert!(scheduler
+        .validate()
+        .unwrap_err()
+        .ends_with("but the time is 100"));
+}
+
+#[test]
+pub fn queued_twice() {
+    let scheduler = corrupted(|state| {
+        state["sleep_queue"].as_array_mut().unwrap().push(json!(2));
+    });
+    assert_eq!(
+        scheduler.validate(),
+        Err(String::from(
+            "process 2 is both in the ready queue and in the sleep queue"
+        ))
+    );
+}
+
+#[test]
+pub fn queued_but_missing() {
+    let scheduler = corrupted(|state| {
+        state["ready_queue"].as_array_mut().unwrap().push(json!(9));
+    });
+    assert_eq!(
+        scheduler.validate(),
+        Err(String::from("process 9 in the ready queue does not exist"))
+    );
+}
+
+#[test]
+pub fn ready_in_sleep_queue() {
+    let scheduler = corrupted(|state| process(state, 3)["state"] = json!("Ready"));
+    assert_eq!(
+        scheduler.validate(),
+        Err(String::from("process 3 in the sleep queue is READY"))
+    );
+}
@@ -0,0 +1,94 @@
+use scheduler::{
+    make_scheduler, round_robin, FifoQueue, Pid, ProcessState, QueueScheduler, Scheduler,
+    SchedulerConfig, SchedulerError, SchedulerKind, SchedulingDecision, StopReason, Syscall,
+    SyscallResult,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn wait_for_running_child() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::WaitPid(Pid::new(2)), 3),
+        SyscallResult::Success
+    );
+
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    assert_eq!(
+        scheduler.list()[0].state(),
+        ProcessState::Waiting { event: None }
+    );
+    syscall(&mut scheduler, Syscall::Exit, 4);
+
+    // the parent resumes with a new timeslice
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+}
+
+#[test]
+pub fn wait_for_exited_child() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::Yield, 3);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Exit, 4);
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::WaitPid(Pid::new(2)), 2),
+        SyscallResult::Success
+    );
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 2));
+    assert_eq!(
+        syscall(&mut scheduler, Syscall::WaitPid(Pid::new(3)), 1),
+        SyscallResult::NoSuchProcess
+    );
+}
+
+#[test]
+pub fn wait_for_each_other() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::WaitPid(Pid::new(2)), 3);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::WaitPid(Pid::new(1)), 4);
+
+    assert_eq!(scheduler.next(), SchedulingDecision::Deadlock);
+}
+
+#[test]
+pub fn wait_for_self() {
+    let timeslice = NonZeroUsize::new(5).unwrap();
+    let config = SchedulerConfig::new(timeslice, 1);
+    let schedulers = SchedulerKind::ALL
+        .map(|kind| (kind.name(), make_scheduler(kind, config)))
+        .into_iter()
+        .chain([(
+            "queue",
+            Box::new(QueueScheduler::new(FifoQueue::new(), timeslice, 1)) as Box<dyn Scheduler>,
+        )]);
+    for (name, mut scheduler) in schedulers {
+        scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+        let (pid, timeslice) = dispatch(&mut scheduler);
+
+        assert_eq!(
+            syscall(&mut scheduler, Syscall::WaitPid(pid), timeslice - 1),
+            SyscallResult::Error(SchedulerError::WaitForSelf),
+            "{}",
+            name
+        );
+        // the process has not been blocked
+        assert_eq!(dispatch(&mut scheduler).0, pid, "{}", name);
+    }
+}
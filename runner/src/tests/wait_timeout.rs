@@ -0,0 +1,82 @@
+use scheduler::{
+    round_robin, Pid, ProcessState, Scheduler, SchedulingDecision, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+fn state(scheduler: &mut impl Scheduler, pid: usize) -> ProcessState {
+    scheduler
+        .list()
+        .iter()
+        .find(|p| p.pid() == pid)
+        .map(|p| p.state())
+        .unwrap()
+}
+
+#[test]
+pub fn signal_before_timeout() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::WaitTimeout { event: 1, max: 10 }, 3);
+    assert_eq!(state(&mut scheduler, 1), ProcessState::Waiting { event: Some(1) });
+
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Signal(1), 4);
+    syscall(&mut scheduler, Syscall::Sleep(20), 3);
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+    syscall(&mut scheduler, Syscall::Sleep(1), 4);
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(1).unwrap())
+    );
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 4));
+    syscall(&mut scheduler, Syscall::Wait(2), 3);
+
+    // the timeout has been cancelled by the signal, only process 2 sleeps
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(17).unwrap())
+    );
+}
+
+#[test]
+pub fn timeout_before_signal() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::WaitTimeout { event: 1, max: 3 }, 3);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Wait(2), 4);
+
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(2).unwrap())
+    );
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), 5));
+    assert_eq!(state(&mut scheduler, 2), ProcessState::Waiting { event: Some(2) });
+
+    syscall(&mut scheduler, Syscall::Signal(2), 4);
+    syscall(&mut scheduler, Syscall::Exit, 3);
+    assert_eq!(scheduler.next(), SchedulingDecision::Panic);
+}
+
+#[test]
+pub fn wait_without_timeout() {
+    let mut scheduler = round_robin(NonZeroUsize::new(5).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 4);
+    syscall(&mut scheduler, Syscall::Wait(1), 3);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Wait(2), 4);
+
+    assert_eq!(scheduler.next(), SchedulingDecision::Deadlock);
+}
@@ -0,0 +1,35 @@
+use scheduler::{Pid, RoundRobinScheduler, Scheduler, StopReason, Syscall};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn waiters_by_event() {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+    assert!(scheduler.waiters(1).is_empty());
+
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 9);
+    syscall(&mut scheduler, Syscall::Fork(0), 8);
+    syscall(&mut scheduler, Syscall::Fork(0), 7);
+    syscall(&mut scheduler, Syscall::Yield, 6);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(2));
+    syscall(&mut scheduler, Syscall::Wait(1), 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(3));
+    syscall(&mut scheduler, Syscall::Wait(2), 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(4));
+    syscall(&mut scheduler, Syscall::Wait(1), 9);
+    assert_eq!(dispatch(&mut scheduler).0, Pid::new(1));
+
+    assert_eq!(scheduler.waiters(1), vec![Pid::new(2), Pid::new(4)]);
+    assert_eq!(scheduler.waiters(2), vec![Pid::new(3)]);
+    assert!(scheduler.waiters(3).is_empty());
+
+    syscall(&mut scheduler, Syscall::Signal(2), 5);
+    assert!(scheduler.waiters(2).is_empty());
+    assert_eq!(scheduler.waiters(1), vec![Pid::new(2), Pid::new(4)]);
+
+    syscall(&mut scheduler, Syscall::Signal(1), 4);
+    assert!(scheduler.waiters(1).is_empty());
+}
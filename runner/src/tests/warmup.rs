@@ -0,0 +1,89 @@
+use scheduler::{
+    RoundRobinScheduler, Scheduler, SchedulerStats, SchedulingDecision, StopReason, Syscall,
+};
+use std::num::NonZeroUsize;
+
+use super::{dispatch, syscall};
+
+/// Runs two children one after the other, with idle time in between,
+/// and returns the statistics once every process has exited. Process 1
+/// sleeps with too little left to keep, so it wakes up with a new
+/// timeslice.
+fn statistics(warmup: usize) -> SchedulerStats {
+    let mut scheduler = RoundRobinScheduler::new(NonZeroUsize::new(3).unwrap(), 2);
+    scheduler.set_warmup(warmup);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    // time 0, pid 2 is created at 1 and exits at 3
+    dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    syscall(&mut scheduler, Syscall::Sleep(5), 1);
+    assert_eq!(dispatch(&mut scheduler).0, 2);
+    syscall(&mut scheduler, Syscall::Exit, 2);
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(4).unwrap())
+    );
+
+    // time 7, pid 3 is created at 8 and exits at 10
+    assert_eq!(dispatch(&mut scheduler).0, 1);
+    syscall(&mut scheduler, Syscall::Fork(0), 2);
+    syscall(&mut scheduler, Syscall::Sleep(2), 1);
+    assert_eq!(dispatch(&mut scheduler).0, 3);
+    syscall(&mut scheduler, Syscall::Exit, 2);
+    assert_eq!(
+        scheduler.next(),
+        SchedulingDecision::Sleep(NonZeroUsize::new(1).unwrap())
+    );
+
+    // time 11, pid 1 exits at 12
+    assert_eq!(dispatch(&mut scheduler).0, 1);
+    syscall(&mut scheduler, Syscall::Exit, 2);
+    assert_eq!(scheduler.next(), SchedulingDecision::Done);
+    scheduler.statistics()
+}
+
+#[test]
+pub fn without_warmup() {
+    assert_eq!(
+        statistics(0),
+        SchedulerStats {
+            total_time: 12,
+            idle_time: 5,
+            completed: 3,
+            context_switches: 5,
+            average_turnaround: 16.0 / 3.0,
+        }
+    );
+}
+
+#[test]
+pub fn warmup_excludes_first_time_units() {
+    let all = statistics(0);
+    let settled = statistics(5);
+
+    // until time 5: the dispatches at 0 and 2, the exit of pid 2 at 3
+    // after 2 time units of turnaround, and 2 of the 4 idle time units
+    // that start at 3
+    assert_eq!(settled.total_time, all.total_time - 5);
+    assert_eq!(settled.idle_time, all.idle_time - 2);
+    assert_eq!(settled.context_switches, all.context_switches - 2);
+    assert_eq!(settled.completed, all.completed - 1);
+    assert_eq!(settled.average_turnaround, (2.0 + 12.0) / 2.0);
+}
+
+#[test]
+pub fn warmup_longer_than_run() {
+    let settled = statistics(100);
+
+    assert_eq!(
+        settled,
+        SchedulerStats {
+            total_time: 0,
+            idle_time: 0,
+            completed: 0,
+            context_switches: 0,
+            average_turnaround: 0.0,
+        }
+    );
+}
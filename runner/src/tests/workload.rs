@@ -0,0 +1,122 @@
+use processor::workload::{Op, ParseError, ParseErrorKind, Workload};
+use std::num::NonZeroUsize;
+
+fn run(time: usize) -> Op {
+    Op::Run(NonZeroUsize::new(time).unwrap())
+}
+
+fn error(text: &str) -> ParseError {
+    Workload::parse(text).unwrap_err()
+}
+
+#[test]
+pub fn nested_forks() {
+    let workload: Workload = "\
+# the first process
+run 3
+fork prio=2
+    run 2
+
+    fork
+      wait 1
+    exit
+fork prio=-1
+sleep 10
+signal 1
+yield
+"
+    .parse()
+    .unwrap();
+
+    assert_eq!(
+        workload.ops,
+        vec![
+            run(3),
+            Op::Fork {
+                priority: 2,
+                ops: vec![
+                    run(2),
+                    Op::Fork {
+                        priority: 0,
+                        ops: vec![Op::Wait(1)]
+                    },
+                    Op::Exit
+                ]
+            },
+            Op::Fork {
+                priority: -1,
+                ops: vec![]
+            },
+            Op::Sleep(10),
+            Op::Signal(1),
+            Op::Yield,
+        ]
+    );
+}
+
+#[test]
+pub fn empty_workload() {
+    assert_eq!(Workload::parse("\n# nothing\n").unwrap(), Workload::default());
+}
+
+#[test]
+pub fn invalid_arguments() {
+    assert_eq!(
+        error("run 1\nsleep -1"),
+        ParseError {
+            line: 2,
+            kind: ParseErrorKind::InvalidNumber("-1".to_string())
+        }
+    );
+    assert_eq!(
+        error("run 0").kind,
+        ParseErrorKind::InvalidNumber("0".to_string())
+    );
+    assert_eq!(error("wait").kind, ParseErrorKind::MissingArgument);
+    assert_eq!(
+        error("signal 1 2").kind,
+        ParseErrorKind::UnexpectedArgument("2".to_string())
+    );
+    assert_eq!(
+        error("exit now").kind,
+        ParseErrorKind::UnexpectedArgument("now".to_string())
+    );
+    assert_eq!(
+        error("fork priority=1").kind,
+        ParseErrorKind::UnexpectedArgument("priority=1".to_string())
+    );
+    assert_eq!(
+        error("fork prio=300").kind,
+        ParseErrorKind::InvalidNumber("300".to_string())
+    );
+}
+
+#[test]
+pub fn unknown_op() {
+    let error = error("run 1\n\nfork\n  jump 3\n");
+    assert_eq!(
+        error,
+        ParseError {
+            line: 4,
+            kind: ParseErrorKind::UnknownOp("jump".to_string())
+        }
+    );
+    assert_eq!(error.to_string(), "line 4: unknown operation `jump`");
+}
+
+#[test]
+pub fn unexpected_indent() {
+    assert_eq!(
+        error("run 1\n  run 2").kind,
+        ParseErrorKind::UnexpectedIndent
+    );
+    assert_eq!(error("  run 1").line, 1);
+    // the last line belongs neither to the fork nor to the first process
+    assert_eq!(
+        error("fork\n    run 1\n  run 2"),
+        ParseError {
+            line: 3,
+            kind: ParseErrorKind::UnexpectedIndent
+        }
+    );
+}
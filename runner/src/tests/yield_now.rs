@@ -0,0 +1,69 @@
+use scheduler::{round_robin, Pid, Scheduler, SchedulerEvent, StopReason, Syscall};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use super::{dispatch, syscall};
+
+#[test]
+pub fn yield_refreshes_quantum() {
+    let mut scheduler = round_robin(NonZeroUsize::new(10).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), timeslice - 1));
+    syscall(&mut scheduler, Syscall::Yield, 5);
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(2), timeslice));
+    scheduler.stop(StopReason::expired());
+
+    assert_eq!(dispatch(&mut scheduler), (Pid::new(1), timeslice));
+}
+
+#[test]
+pub fn yield_alone() {
+    let events = Arc::new(Mutex::new(vec![]));
+    let mut scheduler = round_robin(NonZeroUsize::new(3).unwrap(), 1);
+    let trace = events.clone();
+    scheduler.set_trace(Box::new(move |event| trace.lock().unwrap().push(*event)));
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let (pid, timeslice) = dispatch(&mut scheduler);
+    // the yield uses the last time unit of the timeslice
+    syscall(&mut scheduler, Syscall::Yield, timeslice - 3);
+    assert_eq!(dispatch(&mut scheduler), (pid, timeslice));
+
+    let preempts = events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|event| matches!(event, SchedulerEvent::Preempt { .. }))
+        .count();
+    assert_eq!(preempts, 1);
+}
+
+#[test]
+pub fn alternating_yields() {
+    let mut scheduler = round_robin(NonZeroUsize::new(4).unwrap(), 1);
+    scheduler.stop(StopReason::syscall(Syscall::Fork(0)));
+
+    let (_, timeslice) = dispatch(&mut scheduler);
+    syscall(&mut scheduler, Syscall::Fork(0), timeslice - 1);
+
+    let mut order = vec![];
+    for _ in 0..10 {
+        let (pid, timeslice) = dispatch(&mut scheduler);
+        order.push(pid.get());
+        syscall(&mut scheduler, Syscall::Yield, timeslice - 1);
+    }
+
+    assert_eq!(order, vec![1, 2, 1, 2, 1, 2, 1, 2, 1, 2]);
+    let mut timings = scheduler
+        .list()
+        .iter()
+        .map(|p| (p.pid().get(), p.timings()))
+        .collect::<Vec<_>>();
+    timings.sort();
+    // pid 1 has also issued the fork
+    assert_eq!(timings, vec![(1, (11, 6, 0)), (2, (10, 5, 0))]);
+}
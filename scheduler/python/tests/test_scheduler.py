@@ -0,0 +1,40 @@
+# Run with `maturin develop && pytest python/tests` in the crate directory.
+import pytest
+
+from scheduler import Scheduler
+
+
+def test_fork_and_run():
+    s = Scheduler("round-robin", 3, 1)
+    assert s.stop(("syscall", "fork", [0], 0)) == {"kind": "pid", "pid": 1}
+    assert s.next() == {"kind": "run", "pid": 1, "timeslice": 3}
+    assert s.stop(("syscall", "fork", [4], 2)) == {"kind": "pid", "pid": 2}
+    assert s.stop(("syscall", "sleep", [5], 1)) == {"kind": "success"}
+    assert [(p["pid"], p["state"], p["priority"], p["parent"]) for p in s.list()] == [
+        (1, "sleeping", 0, None),
+        (2, "ready", 4, 1),
+    ]
+    assert s.next() == {"kind": "run", "pid": 2, "timeslice": 3}
+    assert s.stop(("expired",)) == {"kind": "success"}
+
+
+def test_done():
+    s = Scheduler("fcfs", 2)
+    s.stop(("syscall", "fork", [0], 0))
+    s.next()
+    assert s.stop(("syscall", "exit", [], 1)) == {"kind": "success"}
+    assert s.next() == {"kind": "done"}
+
+
+def test_invalid_arguments():
+    with pytest.raises(ValueError):
+        Scheduler("lottery", 3)
+    with pytest.raises(ValueError):
+        Scheduler("cfs", 0)
+    s = Scheduler("round-robin", 3)
+    with pytest.raises(ValueError):
+        s.stop(("syscall", "nap", [1], 0))
+    with pytest.raises(ValueError):
+        s.stop(("syscall", "sleep", [-1], 0))
+    with pytest.raises(ValueError):
+        s.stop(("preempted",))
@@ -0,0 +1,113 @@
+use crate::pcb::ProcessControlBlock;
+use crate::scheduler::{Pid, Process};
+
+/// How the running process has stopped, which decides how the time it
+/// has consumed is charged to it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ChargeKind {
+    /// The process has been preempted, it has run for all the time.
+    Execution,
+
+    /// The process has issued a system call, which is accounted as the
+    /// last of the consumed time units.
+    Syscall,
+}
+
+/// The time of a scheduler, with the part of it during which a process
+/// has been running and the part during which every process has been
+/// sleeping or waiting.
+///
+/// The time stops at the largest value it can count instead of
+/// overflowing, and so do the timings of the processes. Until then, the
+/// busy time and the idle time add up to the time.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountingClock {
+    time: usize,
+    /// The time charged to the processes, running or making system calls.
+    busy_time: usize,
+    /// The time during which no process has been running.
+    idle_time: usize,
+}
+
+impl AccountingClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn time(&self) -> usize {
+        self.time
+    }
+
+    pub fn busy_time(&self) -> usize {
+        self.busy_time
+    }
+
+    pub fn idle_time(&self) -> usize {
+        self.idle_time
+    }
+
+    /// Advances the time by the `consumed` time units during which
+    /// `runner` has been running.
+    ///
+    /// Every process of `processes` is charged the time as time during
+    /// which it has existed. If `runner` is one of them, it is charged the
+    /// time as execution as well, except for the time unit of the system
+    /// call with [`ChargeKind::Syscall`]. A scheduler that credits the time
+    /// of the processes that have been waiting in some other way only
+    /// gives the runner.
+    pub fn charge<'a>(
+        &mut self,
+        runner: Pid,
+        consumed: usize,
+        kind: ChargeKind,
+        processes: impl IntoIterator<Item = &'a mut ProcessControlBlock>,
+    ) {
+        self.charge_parallel(runner, consumed, consumed, kind, processes);
+    }
+
+    /// Like [`AccountingClock::charge`], for a runner on one of several
+    /// processors. Only the last `elapsed` of the `consumed` time units
+    /// advance the time, the other ones have already been counted while
+    /// another processor was running. The processes of `processes` are
+    /// charged `elapsed` time units during which they have existed.
+    pub fn charge_parallel<'a>(
+        &mut self,
+        runner: Pid,
+        consumed: usize,
+        elapsed: usize,
+        kind: ChargeKind,
+        processes: impl IntoIterator<Item = &'a mut ProcessControlBlock>,
+    ) {
+        self.time = self.time.saturating_add(elapsed);
+        self.busy_time = self.busy_time.saturating_add(elapsed);
+        for pcb in processes {
+            pcb.charge_waiting(elapsed);
+            if pcb.pid() != runner {
+                continue;
+            }
+            match kind {
+                ChargeKind::Execution => pcb.charge_execution(consumed),
+                ChargeKind::Syscall => {
+                    pcb.charge_syscall();
+                    pcb.charge_execution(consumed.saturating_sub(1));
+                }
+            }
+        }
+    }
+
+    /// Advances the time by `amount` time units during which no process
+    /// has been running, while every process of `processes` has been
+    /// sleeping or waiting.
+    pub fn advance_sleep<'a>(
+        &mut self,
+        amount: usize,
+        processes: impl IntoIterator<Item = &'a mut ProcessControlBlock>,
+    ) {
+        self.time = self.time.saturating_add(amount);
+        self.idle_time = self.idle_time.saturating_add(amount);
+        for pcb in processes {
+            pcb.charge_waiting(amount);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::scheduler::{
+    Pid, Process, ProcessState, Scheduler, SchedulerError, SchedulingDecision, StopReason, Syscall,
+    SyscallResult,
+};
+use alloc::vec::Vec;
+
+/// A scheduler whose calls return an error instead of a result that
+/// does not make sense, for code that cannot let a wrong call go by.
+pub trait TryScheduler {
+    /// Like [`Scheduler::next`].
+    fn try_next(&mut self) -> Result<SchedulingDecision, SchedulerError>;
+
+    /// Like [`Scheduler::stop`], the scheduler is not called if the
+    /// stop is not valid.
+    fn try_stop(&mut self, reason: StopReason) -> Result<SyscallResult, SchedulerError>;
+}
+
+/// Checks the calls made to any scheduler against the order in which
+/// the OS has to call it, and reports a wrong call as a [`SchedulerError`]
+/// without passing it to the scheduler.
+///
+/// The scheduler can only be stopped while a process runs, after it has
+/// been dispatched and until it expires, blocks or exits, except for the
+/// fork of the first process. A [`Syscall::Sleep`] has to be for some
+/// time and a [`Syscall::Kill`] or a [`Syscall::WaitPid`] has to refer
+/// to an existing process. A [`SyscallResult::Error`] returned by the
+/// scheduler is also turned into an error.
+pub struct CheckedScheduler<S: Scheduler> {
+    scheduler: S,
+    /// The last decision of the scheduler, [`None`] before the first one.
+    last: Option<SchedulingDecision>,
+    /// The process that runs, from its dispatch until it stops running.
+    running: Option<Pid>,
+}
+
+impl<S: Scheduler> CheckedScheduler<S> {
+    pub fn new(scheduler: S) -> Self {
+        Self {
+            scheduler,
+            last: None,
+            running: None,
+        }
+    }
+
+    /// Returns the scheduler that has been checked.
+    pub fn into_inner(self) -> S {
+        self.scheduler
+    }
+
+    /// Lists the processes of the scheduler, like [`Scheduler::list`].
+    pub fn list(&mut self) -> Vec<&dyn Process> {
+        self.scheduler.list()
+    }
+
+    fn exists(&mut self, pid: Pid) -> bool {
+        self.scheduler.list().iter().any(|p| p.pid() == pid)
+    }
+
+    /// Returns the error that stopping the scheduler for `reason` would be.
+    fn check(&mut self, reason: StopReason) -> Result<(), SchedulerError> {
+        match (self.last, self.running) {
+            (Some(SchedulingDecision::Done), _) => return Err(SchedulerError::StopAfterDone),
+            (_, Some(_)) => {}
+            // the first process is created before anything runs
+            (None, None)
+                if matches!(
+                    reason,
+                    StopReason::Syscall {
+                        syscall: Syscall::Fork(_),
+                        ..
+                    }
+                ) => {}
+            _ => return Err(SchedulerError::StopWithoutRun),
+        }
+        match reason {
+            StopReason::Syscall {
+                syscall: Syscall::Sleep(0),
+                ..
+            } => Err(SchedulerError::InvalidSleepDuration(0)),
+            StopReason::Syscall {
+                syscall: Syscall::Kill(pid) | Syscall::WaitPid(pid),
+                ..
+            } if !self.exists(pid) => Err(SchedulerError::UnknownPid(pid)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<S: Scheduler> TryScheduler for CheckedScheduler<S> {
+    fn try_next(&mut self) -> Result<SchedulingDecision, SchedulerError> {
+        let decision = self.scheduler.next();
+        self.last = Some(decision);
+        self.running = match decision {
+            SchedulingDecision::Run { pid, .. } => Some(pid),
+            _ => None,
+        };
+        Ok(decision)
+    }
+
+    fn try_stop(&mut self, reason: StopReason) -> Result<SyscallResult, SchedulerError> {
+        self.check(reason)?;
+        let result = self.scheduler.stop(reason);
+        // the process keeps running only after a system call that
+        // has not blocked it
+        self.running = match (reason, self.running) {
+            (StopReason::Syscall { .. }, Some(pid)) => self
+                .scheduler
+                .list()
+                .iter()
+                .any(|p| p.pid() == pid && p.state() == ProcessState::Running)
+                .then_some(pid),
+            _ => None,
+        };
+        match result {
+            SyscallResult::Error(error) => Err(error),
+            result => Ok(result),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::scheduler::Pid;
+use alloc::vec::Vec;
+use alloc::collections::{BTreeMap, VecDeque};
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex};
+
+/// The processes that wait for events, kept for every event in the order
+/// in which they have started waiting.
+///
+/// Schedulers can use it to implement the [`Syscall::Wait`] and
+/// [`Syscall::Signal`] system calls.
+///
+/// [`Syscall::Wait`]: crate::Syscall::Wait
+/// [`Syscall::Signal`]: crate::Syscall::Signal
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventTable {
+    waiters: BTreeMap<usize, VecDeque<Pid>>,
+}
+
+impl EventTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `pid` to the processes that wait for `event`.
+    pub fn wait(&mut self, pid: Pid, event: usize) {
+        self.waiters.entry(event).or_default().push_back(pid);
+    }
+
+    /// Removes all the processes that wait for `event` and returns
+    /// them in the order in which they have started waiting.
+    pub fn signal(&mut self, event: usize) -> Vec<Pid> {
+        self.waiters
+            .remove(&event)
+            .map(Vec::from)
+            .unwrap_or_default()
+    }
+
+    /// Returns the processes that wait for `event`, in the order
+    /// in which they have started waiting.
+    pub fn waiters(&self, event: usize) -> impl Iterator<Item = Pid> + '_ {
+        self.waiters.get(&event).into_iter().flatten().copied()
+    }
+
+    /// Removes `pid` from the processes that wait for any event, for
+    /// instance when it exits or its wait times out. Returns whether
+    /// the process was waiting.
+    pub fn remove(&mut self, pid: Pid) -> bool {
+        let mut found = false;
+        self.waiters.retain(|_, waiters| {
+            let len = waiters.len();
+            waiters.retain(|&p| p != pid);
+            found |= waiters.len() != len;
+            !waiters.is_empty()
+        });
+        found
+    }
+
+    /// Whether no process waits for any event.
+    pub fn is_empty(&self) -> bool {
+        self.waiters.is_empty()
+    }
+
+    /// The number of processes that wait for events.
+    pub fn waiting_count(&self) -> usize {
+        self.waiters.values().map(VecDeque::len).sum()
+    }
+}
+
+/// Carries the signals between several schedulers, so that a
+/// [`Syscall::Signal`] system call on one of them also wakes up the
+/// processes that wait for the event on the others.
+///
+/// The clones of a bus are the same bus. Every scheduler that uses the
+/// bus subscribes to it and receives the events signaled by the other
+/// subscribers, which it handles the next time it is asked to schedule.
+///
+/// [`Syscall::Signal`]: crate::Syscall::Signal
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct EventBus {
+    /// The events signaled to every subscriber that it has not handled yet.
+    inboxes: Arc<Mutex<Vec<VecDeque<usize>>>>,
+}
+
+#[cfg(feature = "std")]
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a subscriber and returns its number.
+    pub(crate) fn subscribe(&self) -> usize {
+        let mut inboxes = self.inboxes.lock().unwrap();
+        inboxes.push(VecDeque::new());
+        inboxes.len() - 1
+    }
+
+    /// Adds a subscriber that has received the same events as `subscriber`
+    /// so far, and returns its number.
+    pub(crate) fn fork(&self, subscriber: usize) -> usize {
+        let mut inboxes = self.inboxes.lock().unwrap();
+        let inbox = inboxes[subscriber].clone();
+        inboxes.push(inbox);
+        inboxes.len() - 1
+    }
+
+    /// Sends `event` to all the subscribers except `from`.
+    pub(crate) fn publish(&self, from: usize, event: usize) {
+        let mut inboxes = self.inboxes.lock().unwrap();
+        for (subscriber, inbox) in inboxes.iter_mut().enumerate() {
+            if subscriber != from {
+                inbox.push_back(event);
+            }
+        }
+    }
+
+    /// Returns the events sent to `subscriber`, in the order
+    /// in which they have been signaled, and removes them.
+    pub(crate) fn take(&self, subscriber: usize) -> Vec<usize> {
+        self.inboxes.lock().unwrap()[subscriber].drain(..).collect()
+    }
+
+    /// Returns the events sent to `subscriber` without removing them.
+    pub(crate) fn pending(&self, subscriber: usize) -> Vec<usize> {
+        self.inboxes.lock().unwrap()[subscriber]
+            .iter()
+            .copied()
+            .collect()
+    }
+}
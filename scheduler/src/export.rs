@@ -0,0 +1,56 @@
+//! Exports of the scheduler state as CSV, for spreadsheets.
+
+use crate::scheduler::{ProcessSnapshot, Scheduler, SchedulerStats};
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+/// Quotes a CSV field if it contains a comma, a quote or a line break.
+fn escape(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Writes the processes of `scheduler` as CSV, one row for every process
+/// sorted by PID, after a header row.
+///
+/// The columns are the PID, the state, the priority, the timings (total,
+/// syscalls, execution) and the details returned by [`Process::extra`].
+///
+/// [`Process::extra`]: crate::Process::extra
+pub fn write_timings_csv<W: Write>(scheduler: &mut dyn Scheduler, w: W) -> io::Result<()> {
+    write_snapshots_csv(&ProcessSnapshot::all(scheduler), w)
+}
+
+/// Writes `processes` as CSV, in the same format as [`write_timings_csv`].
+pub fn write_snapshots_csv<W: Write>(processes: &[ProcessSnapshot], mut w: W) -> io::Result<()> {
+    writeln!(w, "pid,state,priority,total,syscalls,execution,extra")?;
+    for process in processes {
+        let (total, syscalls, execution) = process.timings;
+        writeln!(
+            w,
+            "{},{},{},{},{},{},{}",
+            process.pid,
+            escape(&process.state.to_string()),
+            process.priority,
+            total,
+            syscalls,
+            execution,
+            escape(&process.extra)
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes `stats` as a two column CSV, with the name and the value
+/// of every statistic, after a header row.
+pub fn write_stats_csv<W: Write>(stats: &SchedulerStats, mut w: W) -> io::Result<()> {
+    writeln!(w, "key,value")?;
+    writeln!(w, "total_time,{}", stats.total_time)?;
+    writeln!(w, "idle_time,{}", stats.idle_time)?;
+    writeln!(w, "completed,{}", stats.completed)?;
+    writeln!(w, "context_switches,{}", stats.context_switches)?;
+    writeln!(w, "average_turnaround,{}", stats.average_turnaround)
+}
@@ -0,0 +1,290 @@
+use crate::scheduler::{
+    Pid, Process, ProcessState, Scheduler, SchedulerError, SchedulingDecision, StopReason, Syscall,
+    SyscallResult,
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+/// One of the two schedulers of a [`FallbackScheduler`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Tier {
+    /// The scheduler whose processes run first.
+    First,
+
+    /// The scheduler whose processes run once the first one is done.
+    Second,
+}
+
+/// A process of a [`FallbackScheduler`], with the PIDs that the
+/// processes have outside of their tier.
+struct TierProcess {
+    pid: Pid,
+    parent: Option<Pid>,
+    state: ProcessState,
+    timings: (usize, usize, usize),
+    priority: i8,
+    extra: String,
+}
+
+impl Process for TierProcess {
+    fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    fn state(&self) -> ProcessState {
+        self.state
+    }
+
+    fn timings(&self) -> (usize, usize, usize) {
+        self.timings
+    }
+
+    fn priority(&self) -> i8 {
+        self.priority
+    }
+
+    fn extra(&self) -> String {
+        self.extra.clone()
+    }
+
+    fn parent(&self) -> Option<Pid> {
+        self.parent
+    }
+}
+
+/// Two schedulers in tiers, the processes of the second one only run
+/// when the first one is done, like a real-time tier over a best-effort
+/// tier.
+///
+/// Every process belongs to one tier, the processes forked by a process
+/// belong to its tier. The fork of the first process goes to the first
+/// tier, [`FallbackScheduler::spawn`] starts a process in any tier. The
+/// stops are given to the tier of the running process.
+///
+/// The tiers number their processes on their own, so the processes get
+/// new PIDs, in the order in which they are created, and the PIDs of the
+/// system calls, the decisions and the listed processes are translated.
+/// A process can only kill or wait for the processes of its own tier and
+/// the signals only wake up the processes of the same tier.
+///
+/// The second tier does not see the time pass while the first one runs,
+/// its sleeping processes wake up that much later. The tiers do not
+/// report events, the trace is ignored.
+pub struct FallbackScheduler<A: Scheduler, B: Scheduler> {
+    first: A,
+    second: B,
+    /// The tier and the PID in that tier of every process, by its PID.
+    pids: BTreeMap<Pid, (Tier, Pid)>,
+    /// The number of PIDs given out so far.
+    nr_processes: usize,
+    /// The tier and the PID in that tier of the running process, from
+    /// its dispatch until it stops running.
+    running: Option<(Tier, Pid)>,
+    /// The processes returned by [`Scheduler::list`].
+    listed: Vec<TierProcess>,
+}
+
+impl<A: Scheduler, B: Scheduler> FallbackScheduler<A, B> {
+    /// Creates a scheduler that runs the processes of `first` before those
+    /// of `second`. The processes that the tiers already have get PIDs
+    /// in the order of their tier and of their PID in it.
+    pub fn new(mut first: A, mut second: B) -> Self {
+        let existing = first
+            .list()
+            .iter()
+            .map(|p| (Tier::First, p.pid()))
+            .chain(second.list().iter().map(|p| (Tier::Second, p.pid())))
+            .collect::<Vec<_>>();
+        let mut scheduler = Self {
+            first,
+            second,
+            pids: BTreeMap::new(),
+            nr_processes: 0,
+            running: None,
+            listed: Vec::new(),
+        };
+        for (tier, local) in existing {
+            scheduler.register(tier, local);
+        }
+        scheduler
+    }
+
+    /// Returns the first tier.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the second tier.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Returns the tier of a process, or [`None`] if there is no
+    /// process with this PID.
+    pub fn tier(&self, pid: Pid) -> Option<Tier> {
+        self.pids.get(&pid).map(|&(tier, _)| tier)
+    }
+
+    /// Creates a process in `tier` that has no parent, like the fork of the
+    /// first process, and returns its PID. The tier answers as for a fork
+    /// made while none of its processes runs.
+    ///
+    /// Returns [`SchedulerError::SpawnWhileRunning`] while a process of
+    /// `tier` is running, as the tier would take the fork for one made by
+    /// that process.
+    pub fn spawn(&mut self, tier: Tier, priority: i8) -> SyscallResult {
+        if self.running.is_some_and(|(running, _)| running == tier) {
+            return SyscallResult::Error(SchedulerError::SpawnWhileRunning);
+        }
+        let result = self.stop_tier(tier, StopReason::syscall(Syscall::Fork(priority)));
+        self.result(tier, result)
+    }
+
+    /// Gives the next PID to the process with PID `local` in `tier`.
+    fn register(&mut self, tier: Tier, local: Pid) -> Pid {
+        self.nr_processes += 1;
+        let pid = Pid::new(self.nr_processes);
+        self.pids.insert(pid, (tier, local));
+        pid
+    }
+
+    /// Returns the PID of the process with PID `local` in `tier`.
+    fn global(&self, tier: Tier, local: Pid) -> Option<Pid> {
+        self.pids
+            .iter()
+            .find(|(_, &owner)| owner == (tier, local))
+            .map(|(&pid, _)| pid)
+    }
+
+    /// Translates a decision of `tier`.
+    fn decision(&self, tier: Tier, decision: SchedulingDecision) -> SchedulingDecision {
+        match decision {
+            SchedulingDecision::Run { pid, timeslice } => SchedulingDecision::Run {
+                pid: self
+                    .global(tier, pid)
+                    .expect("Every process of a tier has a PID"),
+                timeslice,
+            },
+            decision => decision,
+        }
+    }
+
+    /// Translates a result of `tier`, the PIDs returned by forks are new processes.
+    fn result(&mut self, tier: Tier, result: SyscallResult) -> SyscallResult {
+        match result {
+            SyscallResult::Pid(local) => SyscallResult::Pid(self.register(tier, local)),
+            result => result,
+        }
+    }
+
+    fn stop_tier(&mut self, tier: Tier, reason: StopReason) -> SyscallResult {
+        match tier {
+            Tier::First => self.first.stop(reason),
+            Tier::Second => self.second.stop(reason),
+        }
+    }
+
+    fn list_tier(&mut self, tier: Tier) -> Vec<&dyn Process> {
+        match tier {
+            Tier::First => self.first.list(),
+            Tier::Second => self.second.list(),
+        }
+    }
+}
+
+impl<A: Scheduler, B: Scheduler> Scheduler for FallbackScheduler<A, B> {
+    fn next(&mut self) -> SchedulingDecision {
+        let (tier, decision) = match self.first.next() {
+            SchedulingDecision::Done => (Tier::Second, self.second.next()),
+            decision => (Tier::First, decision),
+        };
+        self.running = match decision {
+            SchedulingDecision::Run { pid, .. } => Some((tier, pid)),
+            _ => None,
+        };
+        self.decision(tier, decision)
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        match self.first.peek_next() {
+            SchedulingDecision::Done => self.decision(Tier::Second, self.second.peek_next()),
+            decision => self.decision(Tier::First, decision),
+        }
+    }
+
+    fn stop(&mut self, mut reason: StopReason) -> SyscallResult {
+        let Some((tier, local)) = self.running else {
+            // the first process is forked in the first tier
+            let result = self.first.stop(reason);
+            return self.result(Tier::First, result);
+        };
+        if let StopReason::Syscall {
+            syscall: Syscall::Kill(pid) | Syscall::WaitPid(pid),
+            ..
+        } = &mut reason
+        {
+            match self.pids.get(pid) {
+                Some(&(owner, target)) if owner == tier => *pid = target,
+                _ => return SyscallResult::NoSuchProcess,
+            }
+        }
+        let result = self.stop_tier(tier, reason);
+        let result = self.result(tier, result);
+        // the process keeps running only after a system call that
+        // has not blocked it
+        let still_running = matches!(reason, StopReason::Syscall { .. })
+            && self
+                .list_tier(tier)
+                .iter()
+                .any(|p| p.pid() == local && p.state() == ProcessState::Running);
+        if !still_running {
+            self.running = None;
+        }
+        result
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        let mut listed = Vec::new();
+        for tier in [Tier::First, Tier::Second] {
+            let processes = match tier {
+                Tier::First => self.first.list(),
+                Tier::Second => self.second.list(),
+            };
+            for process in processes {
+                let global = |local: Pid| {
+                    self.pids
+                        .iter()
+                        .find(|(_, &owner)| owner == (tier, local))
+                        .map(|(&pid, _)| pid)
+                };
+                listed.push(TierProcess {
+                    pid: global(process.pid()).expect("Every process of a tier has a PID"),
+                    parent: process.parent().and_then(global),
+                    state: process.state(),
+                    timings: process.timings(),
+                    priority: process.priority(),
+                    extra: process.extra(),
+                });
+            }
+        }
+        listed.sort_by_key(|p| p.pid);
+        self.listed = listed;
+        self.listed.iter().map(|p| p as &dyn Process).collect()
+    }
+
+    fn deadlocked(&self) -> Vec<Pid> {
+        let mut deadlocked = self
+            .first
+            .deadlocked()
+            .into_iter()
+            .filter_map(|pid| self.global(Tier::First, pid))
+            .chain(
+                self.second
+                    .deadlocked()
+                    .into_iter()
+                    .filter_map(|pid| self.global(Tier::Second, pid)),
+            )
+            .collect::<Vec<_>>();
+        deadlocked.sort();
+        deadlocked
+    }
+}
@@ -0,0 +1,449 @@
+//! The C interface of the library, for simulators that are not written in Rust.
+//!
+//! A scheduler is created by one of the `scheduler_new_*` functions, which
+//! return a handle, and is used through the handle until it is released by
+//! [`scheduler_free`]. The handles are checked before every use, so a null
+//! handle, a pointer that is not a handle or a handle that has already been
+//! released makes the functions return [`SCHEDULER_ERROR_INVALID_HANDLE`].
+//!
+//! The structures are `#[repr(C)]`, a header can be generated with
+//! `cbindgen --config cbindgen.toml --output scheduler.h` in the crate
+//! directory.
+
+use crate::scheduler::{
+    Pid, ProcessState, Scheduler, SchedulerError, SchedulingDecision, StopReason, Syscall,
+    SyscallResult,
+};
+use crate::RoundRobinScheduler;
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use core::num::NonZeroUsize;
+use core::ptr;
+use std::sync::Mutex;
+
+/// The call has succeeded.
+pub const SCHEDULER_OK: i32 = 0;
+
+/// A pointer where the result has to be written is null.
+pub const SCHEDULER_ERROR_NULL_POINTER: i32 = -1;
+
+/// The handle is null, has already been released or is not a handle.
+pub const SCHEDULER_ERROR_INVALID_HANDLE: i32 = -2;
+
+/// The stop reason is not valid: its `expired` is neither 0 nor 1, its
+/// system call is not a [`CSyscallKind`], its priority does not fit in
+/// an `int8_t` or it refers to process 0, which does not exist.
+pub const SCHEDULER_ERROR_INVALID_ARGUMENT: i32 = -3;
+
+/// Returned by [`scheduler_list`] instead of the number of processes
+/// when the handle or the buffer is invalid.
+pub const SCHEDULER_LIST_ERROR: usize = usize::MAX;
+
+/// A scheduler used from C, only handled through pointers.
+pub struct SchedulerHandle {
+    scheduler: Box<dyn Scheduler>,
+}
+
+/// The handles that have been created and not released yet.
+static HANDLES: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+
+/// Runs `f` with the scheduler of `handle`, or returns
+/// [`SCHEDULER_ERROR_INVALID_HANDLE`] if it is not a live handle.
+///
+/// The registry stays locked while `f` runs, so that the handle
+/// cannot be released meanwhile by another thread.
+fn with_handle(handle: *mut SchedulerHandle, f: impl FnOnce(&mut dyn Scheduler) -> i32) -> i32 {
+    let handles = HANDLES.lock().unwrap_or_else(|e| e.into_inner());
+    if handle.is_null() || !handles.contains(&(handle as usize)) {
+        return SCHEDULER_ERROR_INVALID_HANDLE;
+    }
+    // SAFETY: the handle has been created by `register` and has not been
+    // released, as it is still in the registry, which is locked
+    let handle = unsafe { &mut *handle };
+    f(handle.scheduler.as_mut())
+}
+
+/// Moves `scheduler` to the heap and returns its handle.
+fn register(scheduler: Box<dyn Scheduler>) -> *mut SchedulerHandle {
+    let handle = Box::into_raw(Box::new(SchedulerHandle { scheduler }));
+    HANDLES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(handle as usize);
+    handle
+}
+
+/// The kind of a [`CDecision`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CDecisionKind {
+    Run,
+    Sleep,
+    Deadlock,
+    Panic,
+    Done,
+}
+
+/// A [`SchedulingDecision`]. `pid` is set only for [`CDecisionKind::Run`],
+/// `time` is the timeslice for [`CDecisionKind::Run`] and the amount of
+/// time for [`CDecisionKind::Sleep`], the unused fields are 0.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CDecision {
+    pub kind: CDecisionKind,
+    pub pid: usize,
+    pub time: usize,
+}
+
+impl From<SchedulingDecision> for CDecision {
+    fn from(decision: SchedulingDecision) -> Self {
+        let (kind, pid, time) = match decision {
+            SchedulingDecision::Run { pid, timeslice } => {
+                (CDecisionKind::Run, pid.get(), timeslice.get())
+            }
+            SchedulingDecision::Sleep(amount) => (CDecisionKind::Sleep, 0, amount.get()),
+            SchedulingDecision::Deadlock => (CDecisionKind::Deadlock, 0, 0),
+            SchedulingDecision::Panic => (CDecisionKind::Panic, 0, 0),
+            SchedulingDecision::Done => (CDecisionKind::Done, 0, 0),
+        };
+        CDecision { kind, pid, time }
+    }
+}
+
+/// The system call of a [`CStopReason`], which holds one of these values.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CSyscallKind {
+    Fork,
+    Sleep,
+    Wait,
+    WaitTimeout,
+    Signal,
+    Yield,
+    SetGroup,
+    SignalGroup,
+    SetPriority,
+    WaitPid,
+    Kill,
+    Exit,
+    ExitWith,
+}
+
+impl CSyscallKind {
+    const ALL: [CSyscallKind; 13] = [
+        CSyscallKind::Fork,
+        CSyscallKind::Sleep,
+        CSyscallKind::Wait,
+        CSyscallKind::WaitTimeout,
+        CSyscallKind::Signal,
+        CSyscallKind::Yield,
+        CSyscallKind::SetGroup,
+        CSyscallKind::SignalGroup,
+        CSyscallKind::SetPriority,
+        CSyscallKind::WaitPid,
+        CSyscallKind::Kill,
+        CSyscallKind::Exit,
+        CSyscallKind::ExitWith,
+    ];
+
+    /// Returns the kind whose value is `value`, if there is one.
+    fn from_u32(value: u32) -> Option<Self> {
+        CSyscallKind::ALL.into_iter().find(|kind| *kind as u32 == value)
+    }
+}
+
+/// A [`StopReason`]. If `expired` is 1 the other fields are ignored,
+/// otherwise it has to be 0 and `syscall` has to hold a [`CSyscallKind`].
+/// The fields are plain integers, so that any value written by C can be
+/// checked.
+///
+/// The arguments of the system call are, by kind:
+/// * `Fork` and `SetPriority` - the priority in `value`
+/// * `Sleep` - the amount of time in `arg0`
+/// * `Wait` and `Signal` - the event in `arg0`
+/// * `WaitTimeout` - the event in `arg0` and the maximum time in `arg1`
+/// * `SetGroup` - the group in `arg0`
+/// * `SignalGroup` - the group in `arg0` and the event in `arg1`
+/// * `WaitPid` and `Kill` - the PID in `arg0`
+/// * `ExitWith` - the exit code in `value`
+///
+/// A priority has to fit in an `int8_t`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CStopReason {
+    pub expired: u8,
+    pub syscall: u32,
+    pub arg0: usize,
+    pub arg1: usize,
+    pub value: i32,
+    pub remaining: usize,
+}
+
+impl CStopReason {
+    /// Returns the stop reason, or [`None`] if it is not valid, see
+    /// [`SCHEDULER_ERROR_INVALID_ARGUMENT`].
+    fn to_stop_reason(self) -> Option<StopReason> {
+        match self.expired {
+            0 => {}
+            1 => return Some(StopReason::Expired),
+            _ => return None,
+        }
+        let pid = || (self.arg0 != 0).then(|| Pid::new(self.arg0));
+        let priority = || i8::try_from(self.value).ok();
+        let syscall = match CSyscallKind::from_u32(self.syscall)? {
+            CSyscallKind::Fork => Syscall::Fork(priority()?),
+            CSyscallKind::Sleep => Syscall::Sleep(self.arg0),
+            CSyscallKind::Wait => Syscall::Wait(self.arg0),
+            CSyscallKind::WaitTimeout => Syscall::WaitTimeout {
+                event: self.arg0,
+                max: self.arg1,
+            },
+            CSyscallKind::Signal => Syscall::Signal(self.arg0),
+            CSyscallKind::Yield => Syscall::Yield,
+            CSyscallKind::SetGroup => Syscall::SetGroup(self.arg0),
+            CSyscallKind::SignalGroup => Syscall::SignalGroup(self.arg0, self.arg1),
+            CSyscallKind::SetPriority => Syscall::SetPriority(priority()?),
+            CSyscallKind::WaitPid => Syscall::WaitPid(pid()?),
+            CSyscallKind::Kill => Syscall::Kill(pid()?),
+            CSyscallKind::Exit => Syscall::Exit,
+            CSyscallKind::ExitWith => Syscall::ExitWith(self.value),
+        };
+        Some(StopReason::Syscall {
+            syscall,
+            remaining: self.remaining,
+        })
+    }
+}
+
+/// The kind of a [`CSyscallResult`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CSyscallResultKind {
+    Pid,
+    Success,
+    NoRunningProcess,
+    NoSuchProcess,
+    MaxProcessesReached,
+    Error,
+}
+
+/// The [`SchedulerError`] of a [`CSyscallResult`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CSchedulerError {
+    None,
+    InvalidSleepDuration,
+    PidOverflow,
+    UnknownPid,
+    StopWithoutRun,
+    StopAfterDone,
+    SpawnWhileRunning,
+    PermissionDenied,
+    WaitForSelf,
+}
+
+/// A [`SyscallResult`]. `pid` is the PID of the new process for
+/// [`CSyscallResultKind::Pid`]. For [`CSyscallResultKind::Error`], `error`
+/// is the error and `value` is the duration of an invalid sleep, the
+/// unknown PID or the PID of the process that could not be targeted. The
+/// unused fields are 0.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CSyscallResult {
+    pub kind: CSyscallResultKind,
+    pub pid: usize,
+    pub error: CSchedulerError,
+    pub value: usize,
+}
+
+impl From<SyscallResult> for CSyscallResult {
+    fn from(result: SyscallResult) -> Self {
+        let kind = match result {
+            SyscallResult::Pid(_) => CSyscallResultKind::Pid,
+            SyscallResult::Success => CSyscallResultKind::Success,
+            SyscallResult::NoRunningProcess => CSyscallResultKind::NoRunningProcess,
+            SyscallResult::NoSuchProcess => CSyscallResultKind::NoSuchProcess,
+            SyscallResult::MaxProcessesReached => CSyscallResultKind::MaxProcessesReached,
+            SyscallResult::Error(_) => CSyscallResultKind::Error,
+        };
+        let pid = match result {
+            SyscallResult::Pid(pid) => pid.get(),
+            _ => 0,
+        };
+        let (error, value) = match result {
+            SyscallResult::Error(error) => match error {
+                SchedulerError::InvalidSleepDuration(amount) => {
+                    (CSchedulerError::InvalidSleepDuration, amount)
+                }
+                SchedulerError::PidOverflow => (CSchedulerError::PidOverflow, 0),
+                SchedulerError::UnknownPid(pid) => (CSchedulerError::UnknownPid, pid.get()),
+                SchedulerError::StopWithoutRun => (CSchedulerError::StopWithoutRun, 0),
+                SchedulerError::StopAfterDone => (CSchedulerError::StopAfterDone, 0),
+                SchedulerError::SpawnWhileRunning => (CSchedulerError::SpawnWhileRunning, 0),
+                SchedulerError::PermissionDenied(pid) => {
+                    (CSchedulerError::PermissionDenied, pid.get())
+                }
+                SchedulerError::WaitForSelf => (CSchedulerError::WaitForSelf, 0),
+            },
+            _ => (CSchedulerError::None, 0),
+        };
+        CSyscallResult {
+            kind,
+            pid,
+            error,
+            value,
+        }
+    }
+}
+
+/// The state of a [`CProcess`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CProcessState {
+    Ready,
+    Running,
+    /// Waiting for an event.
+    Waiting,
+    /// Waiting with no event.
+    Sleeping,
+}
+
+/// A process, as listed by [`scheduler_list`]. `event` is the event for
+/// [`CProcessState::Waiting`] and 0 otherwise, `parent` is 0 for a
+/// process with no parent.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CProcess {
+    pub pid: usize,
+    pub state: CProcessState,
+    pub event: usize,
+    pub priority: i8,
+    pub parent: usize,
+    pub total_time: usize,
+    pub syscall_time: usize,
+    pub execution_time: usize,
+}
+
+/// Creates a round robin scheduler, see [`RoundRobinScheduler::new`].
+///
+/// Returns null if `timeslice` is 0.
+#[no_mangle]
+pub extern "C" fn scheduler_new_round_robin(
+    timeslice: usize,
+    min_remaining: usize,
+) -> *mut SchedulerHandle {
+    match NonZeroUsize::new(timeslice) {
+        Some(timeslice) => register(Box::new(RoundRobinScheduler::new(timeslice, min_remaining))),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Asks the scheduler for the next decision, see [`Scheduler::next`],
+/// and writes it to `out_decision`.
+///
+/// # Safety
+///
+/// `out_decision` has to be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn scheduler_next(
+    handle: *mut SchedulerHandle,
+    out_decision: *mut CDecision,
+) -> i32 {
+    with_handle(handle, |scheduler| {
+        if out_decision.is_null() {
+            return SCHEDULER_ERROR_NULL_POINTER;
+        }
+        let decision = CDecision::from(scheduler.next());
+        // SAFETY: the caller guarantees that a non-null pointer is valid
+        unsafe { out_decision.write(decision) };
+        SCHEDULER_OK
+    })
+}
+
+/// Stops the running process, see [`Scheduler::stop`], and writes
+/// the result to `out_result`.
+///
+/// # Safety
+///
+/// `out_result` has to be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn scheduler_stop(
+    handle: *mut SchedulerHandle,
+    reason: CStopReason,
+    out_result: *mut CSyscallResult,
+) -> i32 {
+    with_handle(handle, |scheduler| {
+        if out_result.is_null() {
+            return SCHEDULER_ERROR_NULL_POINTER;
+        }
+        let Some(reason) = reason.to_stop_reason() else {
+            return SCHEDULER_ERROR_INVALID_ARGUMENT;
+        };
+        let result = CSyscallResult::from(scheduler.stop(reason));
+        // SAFETY: the caller guarantees that a non-null pointer is valid
+        unsafe { out_result.write(result) };
+        SCHEDULER_OK
+    })
+}
+
+/// Writes the first `cap` processes of the scheduler, sorted by PID, to
+/// `out_buf` and returns the number of processes, which can be more than
+/// `cap`. `out_buf` can be null if `cap` is 0, to get only the number.
+///
+/// Returns [`SCHEDULER_LIST_ERROR`] if the handle is invalid or if
+/// `out_buf` is null and `cap` is not 0.
+///
+/// # Safety
+///
+/// `out_buf` has to be null or valid for writing `cap` processes.
+#[no_mangle]
+pub unsafe extern "C" fn scheduler_list(
+    handle: *mut SchedulerHandle,
+    out_buf: *mut CProcess,
+    cap: usize,
+) -> usize {
+    let mut count = SCHEDULER_LIST_ERROR;
+    with_handle(handle, |scheduler| {
+        if out_buf.is_null() && cap > 0 {
+            return SCHEDULER_ERROR_NULL_POINTER;
+        }
+        let processes = scheduler.list();
+        for (index, process) in processes.iter().take(cap).enumerate() {
+            let (state, event) = match process.state() {
+                ProcessState::Ready => (CProcessState::Ready, 0),
+                ProcessState::Running => (CProcessState::Running, 0),
+                ProcessState::Waiting { event: Some(event) } => (CProcessState::Waiting, event),
+                ProcessState::Waiting { event: None } => (CProcessState::Sleeping, 0),
+            };
+            let (total_time, syscall_time, execution_time) = process.timings();
+            let process = CProcess {
+                pid: process.pid().get(),
+                state,
+                event,
+                priority: process.priority(),
+                parent: process.parent().map_or(0, Pid::get),
+                total_time,
+                syscall_time,
+                execution_time,
+            };
+            // SAFETY: the caller guarantees that the buffer has room for `cap` processes
+            unsafe { out_buf.add(index).write(process) };
+        }
+        count = processes.len();
+        SCHEDULER_OK
+    });
+    count
+}
+
+/// Releases a scheduler. The handle cannot be used afterwards.
+#[no_mangle]
+pub extern "C" fn scheduler_free(handle: *mut SchedulerHandle) -> i32 {
+    let mut handles = HANDLES.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(address) = handles.take(&(handle as usize)) else {
+        return SCHEDULER_ERROR_INVALID_HANDLE;
+    };
+    // SAFETY: the address has been returned by `Box::into_raw` in `register`
+    // and has just been removed from the registry, so it is released once
+    drop(unsafe { Box::from_raw(address as *mut SchedulerHandle) });
+    SCHEDULER_OK
+}
@@ -3,32 +3,88 @@
 //! This library provides the traits and structures necessary
 //! to implement a process scheduler.
 //!
+//! The bundled schedulers are built with a [`SchedulerBuilder`],
+//! which checks their settings.
+//!
+//! The `std` feature, enabled by default, can be disabled to use the
+//! library without the standard library, with `alloc` only. The
+//! `export` module, the `EventBus` and the `ObservedScheduler`
+//! need it, everything else works the same.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::num::NonZeroUsize;
+extern crate alloc;
 
+use core::num::NonZeroUsize;
+
+mod accounting;
+mod checked;
+mod events;
+#[cfg(feature = "std")]
+pub mod export;
+mod fallback;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "std")]
+mod observer;
+mod pcb;
 mod scheduler;
+mod stats;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod timeline;
+
+pub use crate::accounting::{AccountingClock, ChargeKind};
+
+pub use crate::checked::{CheckedScheduler, TryScheduler};
+
+pub use crate::events::EventTable;
+
+#[cfg(feature = "std")]
+pub use crate::events::EventBus;
+
+#[cfg(feature = "std")]
+pub use crate::observer::{ObservedEvent, ObservedScheduler, SchedulerObserver, VecObserver};
 
-use schedulers::RoundRobinScheduler;
+pub use crate::fallback::{FallbackScheduler, Tier};
 
+pub use crate::pcb::ProcessControlBlock;
+
+pub use crate::stats::{Aggregate, ProcessStats, SimulationStats};
+
+pub use crate::timeline::{Activity, Segment, Timeline, TimelineScheduler};
 
 pub use crate::scheduler::{
-    Pid, Process, ProcessState, Scheduler, SchedulingDecision, StopReason, Syscall, SyscallResult,
+    AdoptSpec, AdoptState, FailReason, Pid, Process, ProcessSnapshot, ProcessState, Processes,
+    Scheduler, SchedulerError, SchedulerEvent, SchedulerStats, SchedulingDecision, SignalPolicy,
+    StateKind, StopReason, Syscall, SyscallResult, TargetPolicy, Trace,
 };
 
 mod schedulers;
 
-// TODO import your scheduler here
-// This example imports the Empty scheduler
+pub use schedulers::{
+    make_scheduler, BuildError, CfsScheduler, FcfsScheduler, FifoQueue, PriorityQueue,
+    PriorityRoundRobinScheduler, QueueContext, QueueHints, QueueScheduler, ReadyQueue,
+    RoundRobinScheduler, SchedulerBuilder, SchedulerConfig, SchedulerKind, SchedulerSnapshot,
+    SrtfScheduler, StrideScheduler, UnknownSchedulerKind,
+};
+
+#[cfg(feature = "log")]
+pub use schedulers::LoggingScheduler;
 
 /// Returns a structure that implements the `Scheduler` trait with a round robin scheduler policy
 ///
 /// * `timeslice` - the time quanta that a process can run before it is preempted
 /// * `minimum_remaining_timeslice` - when a process makes a system call, the scheduler
-///                                 has to decode whether to schedule it again for the
-///                                 remaining time of its quanta, or to schedule a new
-///                                 process. The scheduler will schedule the process
-///                                 again of the remaining quanta is greater or equal to
-///                                 the `minimum_remaining_timeslice` value.
+///   has to decode whether to schedule it again for the
+///   remaining time of its quanta, or to schedule a new
+///   process. The scheduler will schedule the process
+///   again of the remaining quanta is greater or equal to
+///   the `minimum_remaining_timeslice` value.
 #[allow(unused_variables)]
 pub fn round_robin(timeslice: NonZeroUsize, minimum_remaining_timeslice: usize) -> impl Scheduler {
     RoundRobinScheduler::new(timeslice, minimum_remaining_timeslice)
@@ -37,29 +93,37 @@ pub fn round_robin(timeslice: NonZeroUsize, minimum_remaining_timeslice: usize)
 /// Returns a structure that implements the `Scheduler` trait with a priority queue scheduler policy
 /// * `timeslice` - the time quanta that a process can run before it is preempted
 /// * `minimum_remaining_timeslice` - when a process makes a system call, the scheduler
-///                                 has to decode whether to schedule it again for the
-///                                 remaining time of its quanta, or to schedule a new
-///                                 process. The scheduler will schedule the process
-///                                 again of the remaining quanta is greater or equal to
-///                                 the `minimum_remaining_timeslice` value.
+///   has to decode whether to schedule it again for the
+///   remaining time of its quanta, or to schedule a new
+///   process. The scheduler will schedule the process
+///   again of the remaining quanta is greater or equal to
+///   the `minimum_remaining_timeslice` value.
 #[allow(unused_variables)]
 pub fn priority_queue(
     timeslice: NonZeroUsize,
     minimum_remaining_timeslice: usize,
 ) -> impl Scheduler {
-    RoundRobinScheduler::new(timeslice, minimum_remaining_timeslice)
+    PriorityRoundRobinScheduler::new(timeslice, minimum_remaining_timeslice)
 }
 
 /// Returns a structure that implements the `Scheduler` trait with a simplified [cfs](https://opensource.com/article/19/2/fair-scheduling-linux) scheduler policy
 /// * `cpu_time` - the total time units that the cpu has for an iteration, this is used to compute
-///                    the `timeslice` of each process.
+///   the `timeslice` of each process.
 /// * `minimum_remaining_timeslice` - when a process makes a system call, the scheduler
-///                                 has to decode whether to schedule it again for the
-///                                 remaining time of its quanta, or to schedule a new
-///                                 process. The scheduler will schedule the process
-///                                 again of the remaining quanta is greater or equal to
-///                                 the `minimum_remaining_timeslice` value.
+///   has to decode whether to schedule it again for the
+///   remaining time of its quanta, or to schedule a new
+///   process. The scheduler will schedule the process
+///   again of the remaining quanta is greater or equal to
+///   the `minimum_remaining_timeslice` value.
 #[allow(unused_variables)]
 pub fn cfs(cpu_time: NonZeroUsize, minimum_remaining_timeslice: usize) -> impl Scheduler {
-    RoundRobinScheduler::new(cpu_time, minimum_remaining_timeslice)
+    CfsScheduler::new(cpu_time, minimum_remaining_timeslice)
+}
+
+/// Returns a structure that implements the `Scheduler` trait with a first come first served
+/// scheduler policy, processes run in arrival order and are never preempted.
+/// * `timeslice` - the time units granted to a process at once, when they are used
+///   the process is granted the same amount again.
+pub fn fcfs(timeslice: NonZeroUsize) -> impl Scheduler {
+    FcfsScheduler::new(timeslice)
 }
@@ -0,0 +1,233 @@
+use crate::scheduler::{
+    Pid, Process, ProcessState, Scheduler, SchedulingDecision, StopReason, Syscall, SyscallResult,
+    Trace,
+};
+use core::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+/// Receives what happens in a scheduler observed by an [`ObservedScheduler`].
+///
+/// Observers only get copies of the values, they cannot change the
+/// scheduler. All the methods do nothing by default.
+pub trait SchedulerObserver: Send + Sync {
+    /// A process has been dispatched at time `now`.
+    fn on_dispatch(&mut self, _pid: Pid, _timeslice: NonZeroUsize, _now: usize) {}
+
+    /// A process has stopped at time `now`, `pid` is [`None`] if no
+    /// process was running, like for the fork of the first process.
+    fn on_stop(&mut self, _pid: Option<Pid>, _reason: StopReason, _now: usize) {}
+
+    /// A process has been created.
+    fn on_fork(&mut self, _parent: Option<Pid>, _child: Pid) {}
+
+    /// A process has exited or has been killed.
+    fn on_exit(&mut self, _pid: Pid) {}
+
+    /// A process has changed its state.
+    fn on_state_change(&mut self, _pid: Pid, _old: ProcessState, _new: ProcessState) {}
+}
+
+/// A call to a [`SchedulerObserver`], recorded by a [`VecObserver`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ObservedEvent {
+    Dispatch {
+        pid: Pid,
+        timeslice: NonZeroUsize,
+        now: usize,
+    },
+    Stop {
+        pid: Option<Pid>,
+        reason: StopReason,
+        now: usize,
+    },
+    Fork {
+        parent: Option<Pid>,
+        child: Pid,
+    },
+    Exit {
+        pid: Pid,
+    },
+    StateChange {
+        pid: Pid,
+        old: ProcessState,
+        new: ProcessState,
+    },
+}
+
+/// An observer that records every event. Its clones share the
+/// recorded events, so that a clone can be given to the scheduler.
+#[derive(Debug, Clone, Default)]
+pub struct VecObserver {
+    events: Arc<Mutex<Vec<ObservedEvent>>>,
+}
+
+impl VecObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the events recorded so far, in the order they have happened.
+    pub fn events(&self) -> Vec<ObservedEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    fn push(&self, event: ObservedEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+impl SchedulerObserver for VecObserver {
+    fn on_dispatch(&mut self, pid: Pid, timeslice: NonZeroUsize, now: usize) {
+        self.push(ObservedEvent::Dispatch {
+            pid,
+            timeslice,
+            now,
+        });
+    }
+
+    fn on_stop(&mut self, pid: Option<Pid>, reason: StopReason, now: usize) {
+        self.push(ObservedEvent::Stop { pid, reason, now });
+    }
+
+    fn on_fork(&mut self, parent: Option<Pid>, child: Pid) {
+        self.push(ObservedEvent::Fork { parent, child });
+    }
+
+    fn on_exit(&mut self, pid: Pid) {
+        self.push(ObservedEvent::Exit { pid });
+    }
+
+    fn on_state_change(&mut self, pid: Pid, old: ProcessState, new: ProcessState) {
+        self.push(ObservedEvent::StateChange { pid, old, new });
+    }
+}
+
+/// A scheduler that reports to [`SchedulerObserver`]s what another
+/// scheduler does.
+///
+/// The changes of the processes are found by comparing the processes
+/// listed by the scheduler before and after every call. The time is
+/// counted like for a [`TimelineScheduler`](crate::TimelineScheduler).
+pub struct ObservedScheduler<S: Scheduler> {
+    scheduler: S,
+    observers: Vec<Box<dyn SchedulerObserver>>,
+    time: usize,
+    /// The process that has been dispatched and the timeslice it has been granted.
+    granted: Option<(Pid, usize)>,
+}
+
+impl<S: Scheduler> ObservedScheduler<S> {
+    pub fn new(scheduler: S) -> Self {
+        Self {
+            scheduler,
+            observers: Vec::new(),
+            time: 0,
+            granted: None,
+        }
+    }
+
+    /// Adds an observer, which is called after the ones added before.
+    pub fn add_observer(&mut self, observer: Box<dyn SchedulerObserver>) {
+        self.observers.push(observer);
+    }
+
+    /// Returns the scheduler that has been observed.
+    pub fn into_inner(self) -> S {
+        self.scheduler
+    }
+
+    fn notify(&mut self, event: impl Fn(&mut dyn SchedulerObserver)) {
+        for observer in self.observers.iter_mut() {
+            event(observer.as_mut());
+        }
+    }
+
+    /// Returns the PID and the state of every process, sorted by PID.
+    fn states(&mut self) -> Vec<(Pid, ProcessState)> {
+        self.scheduler
+            .list()
+            .iter()
+            .map(|p| (p.pid(), p.state()))
+            .collect()
+    }
+
+    /// Reports the processes that have changed their state and those that
+    /// have exited since `before`.
+    fn notify_changes(&mut self, before: &[(Pid, ProcessState)]) {
+        let after = self.states();
+        for &(pid, old) in before.iter() {
+            match after.iter().find(|(p, _)| *p == pid) {
+                Some(&(_, new)) if new != old => {
+                    self.notify(|observer| observer.on_state_change(pid, old, new));
+                }
+                Some(_) => {}
+                None => self.notify(|observer| observer.on_exit(pid)),
+            }
+        }
+    }
+}
+
+impl<S: Scheduler> Scheduler for ObservedScheduler<S> {
+    fn next(&mut self) -> SchedulingDecision {
+        let before = self.states();
+        let decision = self.scheduler.next();
+        if let SchedulingDecision::Sleep(amount) = decision {
+            self.time += amount.get();
+        }
+        self.notify_changes(&before);
+        if let SchedulingDecision::Run { pid, timeslice } = decision {
+            self.granted = Some((pid, timeslice.get()));
+            let now = self.time;
+            self.notify(|observer| observer.on_dispatch(pid, timeslice, now));
+        }
+        decision
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        self.scheduler.peek_next()
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        let running = self.granted.take().map(|(pid, timeslice)| {
+            let remaining = match reason {
+                StopReason::Syscall { remaining, .. } => {
+                    // the process may issue more system calls before the next dispatch
+                    self.granted = Some((pid, remaining));
+                    remaining
+                }
+                StopReason::Expired => 0,
+            };
+            self.time += timeslice.saturating_sub(remaining);
+            pid
+        });
+
+        let before = self.states();
+        let result = self.scheduler.stop(reason);
+        let now = self.time;
+        self.notify(|observer| observer.on_stop(running, reason, now));
+        if let (
+            StopReason::Syscall {
+                syscall: Syscall::Fork(_),
+                ..
+            },
+            SyscallResult::Pid(child),
+        ) = (reason, result)
+        {
+            self.notify(|observer| observer.on_fork(running, child));
+        }
+        self.notify_changes(&before);
+        result
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.scheduler.list()
+    }
+
+    fn deadlocked(&self) -> Vec<Pid> {
+        self.scheduler.deadlocked()
+    }
+
+    fn set_trace(&mut self, trace: Trace) {
+        self.scheduler.set_trace(trace);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::scheduler::{Pid, Process, ProcessState};
+use alloc::string::String;
+
+/// The bookkeeping that every scheduler keeps for a process, whatever its
+/// policy: the PID, the state, the priority, the timings, the parent and
+/// the time at which the process has been created.
+///
+/// The processes of a scheduler embed it next to the fields of their
+/// policy and forward the [`Process`] methods to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessControlBlock {
+    pid: Pid,
+    state: ProcessState,
+    priority: i8,
+    /// The timings (total, syscalls, execution).
+    timings: (usize, usize, usize),
+    parent: Option<Pid>,
+    /// The time at which the process has been created.
+    created: usize,
+}
+
+impl ProcessControlBlock {
+    /// Creates the block of a process without a parent, created at time 0.
+    pub fn new(
+        pid: Pid,
+        state: ProcessState,
+        priority: i8,
+        timings: (usize, usize, usize),
+    ) -> Self {
+        ProcessControlBlock {
+            pid,
+            state,
+            priority,
+            timings,
+            parent: None,
+            created: 0,
+        }
+    }
+
+    pub fn set_state(&mut self, state: ProcessState) {
+        self.state = state;
+    }
+
+    pub fn set_priority(&mut self, priority: i8) {
+        self.priority = priority;
+    }
+
+    pub fn set_parent(&mut self, parent: Option<Pid>) {
+        self.parent = parent;
+    }
+
+    /// Returns the time at which the process has been created.
+    pub fn created(&self) -> usize {
+        self.created
+    }
+
+    pub fn set_created(&mut self, time: usize) {
+        self.created = time;
+    }
+
+    /// Charges `time` time units during which the process has run.
+    pub fn charge_execution(&mut self, time: usize) {
+        self.timings.2 = self.timings.2.saturating_add(time);
+    }
+
+    /// Charges a system call issued by the process.
+    pub fn charge_syscall(&mut self) {
+        self.timings.1 += 1;
+    }
+
+    /// Charges `time` time units during which the process has existed,
+    /// whether it has run or waited.
+    pub fn charge_waiting(&mut self, time: usize) {
+        self.timings.0 = self.timings.0.saturating_add(time);
+    }
+}
+
+impl Process for ProcessControlBlock {
+    fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    fn state(&self) -> ProcessState {
+        self.state
+    }
+
+    fn timings(&self) -> (usize, usize, usize) {
+        self.timings
+    }
+
+    fn priority(&self) -> i8 {
+        self.priority
+    }
+
+    fn extra(&self) -> String {
+        String::new()
+    }
+
+    fn parent(&self) -> Option<Pid> {
+        self.parent
+    }
+}
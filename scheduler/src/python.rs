@@ -0,0 +1,257 @@
+//! Python bindings, to try the schedulers out from a notebook.
+//!
+//! The module is built with `maturin develop` in the crate directory and
+//! provides a `Scheduler` class:
+//!
+//! ```python
+//! from scheduler import Scheduler
+//!
+//! s = Scheduler("round-robin", 3, 1)
+//! s.stop(("syscall", "fork", [0], 0))  # {'kind': 'pid', 'pid': 1}
+//! s.next()  # {'kind': 'run', 'pid': 1, 'timeslice': 3}
+//! s.stop(("syscall", "sleep", [5], 2))
+//! s.stop(("expired",))
+//! s.list()  # [{'pid': 1, 'state': 'running', ...}]
+//! ```
+//!
+//! Decisions, results and states are dictionaries whose `kind` or
+//! `state` is a string. The conversions are done by the functions of
+//! this module, which do not need Python.
+
+use crate::scheduler::{
+    Pid, ProcessState, Scheduler, SchedulingDecision, StopReason, Syscall, SyscallResult,
+};
+use crate::schedulers::{make_scheduler, SchedulerConfig, SchedulerKind};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::num::NonZeroUsize;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyTuple};
+
+/// Returns the name of a system call and its arguments, as they are given
+/// to `Scheduler.stop` in Python.
+pub fn syscall_to_parts(syscall: Syscall) -> (&'static str, Vec<i64>) {
+    match syscall {
+        Syscall::Fork(priority) => ("fork", vec![priority as i64]),
+        Syscall::Sleep(amount) => ("sleep", vec![amount as i64]),
+        Syscall::Wait(event) => ("wait", vec![event as i64]),
+        Syscall::WaitTimeout { event, max } => ("wait_timeout", vec![event as i64, max as i64]),
+        Syscall::Signal(event) => ("signal", vec![event as i64]),
+        Syscall::Yield => ("yield", vec![]),
+        Syscall::SetGroup(group) => ("set_group", vec![group as i64]),
+        Syscall::SignalGroup(group, event) => ("signal_group", vec![group as i64, event as i64]),
+        Syscall::SetPriority(priority) => ("set_priority", vec![priority as i64]),
+        Syscall::WaitPid(pid) => ("wait_pid", vec![pid.get() as i64]),
+        Syscall::Kill(pid) => ("kill", vec![pid.get() as i64]),
+        Syscall::Exit => ("exit", vec![]),
+        Syscall::ExitWith(code) => ("exit_with", vec![code as i64]),
+    }
+}
+
+/// Returns the system call with `name` and `args`, the reverse of
+/// [`syscall_to_parts`], or a message explaining why they are wrong.
+pub fn syscall_from_parts(name: &str, args: &[i64]) -> Result<Syscall, String> {
+    let arity = match name {
+        "yield" | "exit" => 0,
+        "wait_timeout" | "signal_group" => 2,
+        _ => 1,
+    };
+    if args.len() != arity {
+        return Err(format!(
+            "the `{}` system call takes {} arguments, not {}",
+            name,
+            arity,
+            args.len()
+        ));
+    }
+    let number = |index: usize| {
+        usize::try_from(args[index])
+            .map_err(|_| format!("the argument {} must not be negative", args[index]))
+    };
+    let priority =
+        || i8::try_from(args[0]).map_err(|_| format!("the priority {} is out of range", args[0]));
+    let pid = || match number(0)? {
+        0 => Err(String::from("there is no process with PID 0")),
+        pid => Ok(Pid::new(pid)),
+    };
+    Ok(match name {
+        "fork" => Syscall::Fork(priority()?),
+        "sleep" => Syscall::Sleep(number(0)?),
+        "wait" => Syscall::Wait(number(0)?),
+        "wait_timeout" => Syscall::WaitTimeout {
+            event: number(0)?,
+            max: number(1)?,
+        },
+        "signal" => Syscall::Signal(number(0)?),
+        "yield" => Syscall::Yield,
+        "set_group" => Syscall::SetGroup(number(0)?),
+        "signal_group" => Syscall::SignalGroup(number(0)?, number(1)?),
+        "set_priority" => Syscall::SetPriority(priority()?),
+        "wait_pid" => Syscall::WaitPid(pid()?),
+        "kill" => Syscall::Kill(pid()?),
+        "exit" => Syscall::Exit,
+        "exit_with" => Syscall::ExitWith(
+            i32::try_from(args[0])
+                .map_err(|_| format!("the exit code {} is out of range", args[0]))?,
+        ),
+        _ => return Err(format!("unknown system call `{}`", name)),
+    })
+}
+
+/// Returns the kind of a decision and its fields, which make up the
+/// dictionary returned by `Scheduler.next`.
+pub fn decision_to_parts(
+    decision: SchedulingDecision,
+) -> (&'static str, Vec<(&'static str, usize)>) {
+    match decision {
+        SchedulingDecision::Run { pid, timeslice } => (
+            "run",
+            vec![("pid", pid.get()), ("timeslice", timeslice.get())],
+        ),
+        SchedulingDecision::Sleep(amount) => ("sleep", vec![("amount", amount.get())]),
+        SchedulingDecision::Deadlock => ("deadlock", vec![]),
+        SchedulingDecision::Panic => ("panic", vec![]),
+        SchedulingDecision::Done => ("done", vec![]),
+    }
+}
+
+/// Returns the kind of a system call result, the `kind` of the
+/// dictionary returned by `Scheduler.stop`.
+pub fn result_kind(result: SyscallResult) -> &'static str {
+    match result {
+        SyscallResult::Pid(_) => "pid",
+        SyscallResult::Success => "success",
+        SyscallResult::NoRunningProcess => "no_running_process",
+        SyscallResult::NoSuchProcess => "no_such_process",
+        SyscallResult::MaxProcessesReached => "max_processes_reached",
+        SyscallResult::Error(_) => "error",
+    }
+}
+
+/// Returns the name of a process state and the event that the process
+/// waits for, if it waits for one.
+pub fn state_to_parts(state: ProcessState) -> (&'static str, Option<usize>) {
+    match state {
+        ProcessState::Ready => ("ready", None),
+        ProcessState::Running => ("running", None),
+        ProcessState::Waiting { event: Some(event) } => ("waiting", Some(event)),
+        ProcessState::Waiting { event: None } => ("sleeping", None),
+    }
+}
+
+/// Returns the stop reason given to `Scheduler.stop`, either `("expired",)`
+/// or `("syscall", name, args, remaining)`.
+pub fn stop_reason_from_parts(
+    kind: &str,
+    syscall: Option<(&str, &[i64], usize)>,
+) -> Result<StopReason, String> {
+    match (kind, syscall) {
+        ("expired", None) => Ok(StopReason::Expired),
+        ("syscall", Some((name, args, remaining))) => Ok(StopReason::Syscall {
+            syscall: syscall_from_parts(name, args)?,
+            remaining,
+        }),
+        ("expired", Some(_)) => Err(String::from("an expired timeslice has no system call")),
+        ("syscall", None) => Err(String::from(
+            "a system call is given as (\"syscall\", name, args, remaining)",
+        )),
+        _ => Err(format!("unknown stop reason `{}`", kind)),
+    }
+}
+
+/// A scheduler used from Python, one of the bundled schedulers.
+#[pyclass(name = "Scheduler", unsendable)]
+pub struct PyScheduler {
+    scheduler: Box<dyn Scheduler>,
+}
+
+#[pymethods]
+impl PyScheduler {
+    /// Creates the scheduler named `kind`, like `"round-robin"` or `"cfs"`.
+    #[new]
+    #[pyo3(signature = (kind, timeslice, minimum_remaining = 0))]
+    fn new(kind: &str, timeslice: usize, minimum_remaining: usize) -> PyResult<Self> {
+        let kind = kind
+            .parse::<SchedulerKind>()
+            .map_err(|error| PyValueError::new_err(error.to_string()))?;
+        let timeslice = NonZeroUsize::new(timeslice)
+            .ok_or_else(|| PyValueError::new_err("the timeslice must not be 0"))?;
+        Ok(PyScheduler {
+            scheduler: make_scheduler(kind, SchedulerConfig::new(timeslice, minimum_remaining)),
+        })
+    }
+
+    /// Returns the next decision, like `{"kind": "run", "pid": 1, "timeslice": 3}`.
+    fn next<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (kind, fields) = decision_to_parts(self.scheduler.next());
+        let decision = PyDict::new(py);
+        decision.set_item("kind", kind)?;
+        for (name, value) in fields {
+            decision.set_item(name, value)?;
+        }
+        Ok(decision)
+    }
+
+    /// Stops the running process, `reason` is either `("expired",)` or
+    /// `("syscall", name, args, remaining)`. Returns the result, like
+    /// `{"kind": "pid", "pid": 2}` or `{"kind": "error", "error": "..."}`.
+    fn stop<'py>(
+        &mut self,
+        py: Python<'py>,
+        reason: &Bound<'py, PyTuple>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let kind = reason.get_item(0)?.extract::<String>()?;
+        let reason = match reason.len() {
+            1 => stop_reason_from_parts(&kind, None),
+            4 => {
+                let name = reason.get_item(1)?.extract::<String>()?;
+                let args = reason.get_item(2)?.extract::<Vec<i64>>()?;
+                let remaining = reason.get_item(3)?.extract::<usize>()?;
+                stop_reason_from_parts(&kind, Some((&name, &args, remaining)))
+            }
+            _ => Err(String::from("a stop reason has 1 or 4 items")),
+        }
+        .map_err(PyValueError::new_err)?;
+
+        let result = self.scheduler.stop(reason);
+        let dict = PyDict::new(py);
+        dict.set_item("kind", result_kind(result))?;
+        match result {
+            SyscallResult::Pid(pid) => dict.set_item("pid", pid.get())?,
+            SyscallResult::Error(error) => dict.set_item("error", error.to_string())?,
+            _ => {}
+        }
+        Ok(dict)
+    }
+
+    /// Returns the processes sorted by PID, each one as a dictionary with
+    /// its `pid`, `state`, `event`, `priority`, `timings`, `extra` and `parent`.
+    fn list<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let processes = PyList::empty(py);
+        for process in self.scheduler.list() {
+            let (state, event) = state_to_parts(process.state());
+            let dict = PyDict::new(py);
+            dict.set_item("pid", process.pid().get())?;
+            dict.set_item("state", state)?;
+            dict.set_item("event", event)?;
+            dict.set_item("priority", process.priority())?;
+            dict.set_item("timings", process.timings())?;
+            dict.set_item("extra", process.extra())?;
+            dict.set_item("parent", process.parent().map(Pid::get))?;
+            processes.append(dict)?;
+        }
+        Ok(processes)
+    }
+}
+
+/// The `scheduler` Python module.
+#[pymodule]
+fn scheduler(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyScheduler>()
+}
@@ -1,18 +1,34 @@
-use std::fmt::{self, Display};
-use std::num::NonZeroUsize;
-use std::ops::Add;
+use core::fmt::{self, Display};
+use core::num::NonZeroUsize;
+use core::ops::Add;
+use alloc::{boxed::Box, string::String, vec::Vec};
 
 /// The PID of a process
 ///
 /// The PID cannot be 0, PIDs start from 1.
 #[derive(PartialEq, Eq, Copy, Clone, Hash, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[repr(transparent)]
 pub struct Pid(NonZeroUsize);
 
 impl Pid {
+    /// Creates a new PID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pid` is 0.
     pub fn new(pid: usize) -> Pid {
         Pid(NonZeroUsize::new(pid).unwrap())
     }
+
+    /// Returns the PID as a number.
+    pub fn get(self) -> usize {
+        self.0.get()
+    }
 }
 
 impl PartialEq<usize> for Pid {
@@ -22,13 +38,13 @@ impl PartialEq<usize> for Pid {
 }
 
 impl Display for Pid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
 impl fmt::Debug for Pid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
@@ -44,7 +60,8 @@ impl Add<usize> for Pid {
 /// The action that the scheduler asks the OS to take.
 ///
 /// This is returned by the [`Scheduler::next`] function.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SchedulingDecision {
     /// Run the process with PID `pid` for a maximum of `timeslice` time units.
     Run { pid: Pid, timeslice: NonZeroUsize },
@@ -64,7 +81,7 @@ pub enum SchedulingDecision {
 }
 
 impl Display for SchedulingDecision {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             SchedulingDecision::Run { pid, timeslice } => {
                 write!(f, "Run {} for {} slices", pid, timeslice)
@@ -86,9 +103,15 @@ impl Display for SchedulingDecision {
 }
 
 /// A system call that processes make towards the scheduler.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Syscall {
     /// Create a new process and return its PID.
+    ///
+    /// The scheduler does not know how long the new process will run, the
+    /// process decides it by the system calls it makes. The new process
+    /// starts with all its timings at 0 and gets a full timeslice the first
+    /// time it is scheduled, whatever is left of the timeslice of its parent.
     Fork(
         /// The process's priority. Some scheduling algorithms can ignore this value.
         i8,
@@ -110,6 +133,17 @@ pub enum Syscall {
         usize,
     ),
 
+    /// Wait for an event, but for no longer than an amount of time.
+    WaitTimeout {
+        /// The event number, like for [`Syscall::Wait`].
+        event: usize,
+
+        /// The maximum amount of time to wait. If no process signals the
+        /// event meanwhile, the process is placed in the [`ProcessState::Ready`]
+        /// state after this time, like after a [`Syscall::Sleep`].
+        max: usize,
+    },
+
     /// Signal all processes that wait for an event.
     Signal(
         /// The event number. All processes that are waiting for this event
@@ -117,11 +151,71 @@ pub enum Syscall {
         usize,
     ),
 
+    /// Give up the processor without sleeping or waiting.
+    ///
+    /// The process is placed in the [`ProcessState::Ready`] state. Whether it
+    /// keeps the time left from its quanta for when it is scheduled again
+    /// depends on the scheduler, round robin gives it a fresh quantum.
+    Yield,
+
+    /// Move the running process to a process group.
+    ///
+    /// Processes start in the group of the process that has forked them,
+    /// the first process starts in group 0. Schedulers that do not support
+    /// groups ignore this system call and keep all the processes in group 0.
+    SetGroup(
+        /// The group number.
+        usize,
+    ),
+
+    /// Signal an event only to the processes of a group that wait for it.
+    SignalGroup(
+        /// The group number.
+        usize,
+        /// The event number, like for [`Syscall::Signal`].
+        usize,
+    ),
+
+    /// Change the priority of the running process.
+    SetPriority(
+        /// The new priority. Schedulers that limit the priority range
+        /// clamp the value to it.
+        i8,
+    ),
+
+    /// Wait until another process exits.
+    ///
+    /// The process will be placed in the [`ProcessState::Waiting`] state,
+    /// with no event, until the process with this PID exits. If the process
+    /// has already exited, the system call returns right away.
+    WaitPid(
+        /// The PID of the process to wait for.
+        Pid,
+    ),
+
+    /// Terminate another process.
+    ///
+    /// A process that kills itself behaves as if it had issued
+    /// a [`Syscall::Exit`] system call.
+    Kill(
+        /// The PID of the process to terminate.
+        Pid,
+    ),
+
     /// Ask the scheduler to finish the process.
     ///
     /// The process will never be scheduled again and will be deleted
     /// from the list of processes the the scheduler keeps track of.
     Exit,
+
+    /// Ask the scheduler to finish the process with an exit code.
+    ///
+    /// This behaves like [`Syscall::Exit`], which is the same as
+    /// exiting with code 0.
+    ExitWith(
+        /// The exit code of the process.
+        i32,
+    ),
 }
 
 /*
@@ -131,7 +225,8 @@ pub enum Syscall {
  */
 
 /// The result returned by a system call.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SyscallResult {
     /// Returned after a [`Syscall::Fork`] system call.
     Pid(
@@ -145,11 +240,86 @@ pub enum SyscallResult {
 
     /// The system call was issues while no process was scheduled.
     NoRunningProcess,
+
+    /// The system call refers to a process that does not exist.
+    NoSuchProcess,
+
+    /// Returned after a [`Syscall::Fork`] system call when the scheduler
+    /// already has as many processes as it can keep track of.
+    MaxProcessesReached,
+
+    /// The scheduler could not handle the system call, which has
+    /// had no effect.
+    Error(SchedulerError),
+}
+
+/// The reason a scheduler could not handle a system call, instead of
+/// panicking.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SchedulerError {
+    /// A [`Syscall::Sleep`] or [`Syscall::WaitTimeout`] system call would
+    /// end after the largest time the scheduler can count, or, for a
+    /// [`CheckedScheduler`](crate::CheckedScheduler), is for 0 time units.
+    InvalidSleepDuration(usize),
+
+    /// A [`Syscall::Fork`] system call was issued after all
+    /// the PIDs have been used.
+    PidOverflow,
+
+    /// A [`Syscall::Kill`] or [`Syscall::WaitPid`] system call refers
+    /// to a process that does not exist.
+    UnknownPid(Pid),
+
+    /// The scheduler has been stopped while no process has been running.
+    StopWithoutRun,
+
+    /// The scheduler has been stopped after it has decided that all
+    /// the processes are done.
+    StopAfterDone,
+
+    /// A process has been spawned in a tier of a
+    /// [`FallbackScheduler`](crate::FallbackScheduler) while a process
+    /// of that tier is running.
+    SpawnWhileRunning,
+
+    /// A system call targets a process that the running process is not
+    /// allowed to act on, see [`TargetPolicy`].
+    PermissionDenied(Pid),
+
+    /// A [`Syscall::WaitPid`] system call waits for the process that
+    /// issues it, which would never wake up.
+    WaitForSelf,
+}
+
+impl Display for SchedulerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SchedulerError::InvalidSleepDuration(amount) => {
+                write!(f, "cannot sleep for {} time units", amount)
+            }
+            SchedulerError::PidOverflow => write!(f, "no PID is left for a new process"),
+            SchedulerError::UnknownPid(pid) => write!(f, "there is no process with PID {}", pid),
+            SchedulerError::StopWithoutRun => write!(f, "no process has been running"),
+            SchedulerError::StopAfterDone => write!(f, "all the processes are done"),
+            SchedulerError::SpawnWhileRunning => {
+                write!(f, "cannot spawn a process while its tier is running")
+            }
+            SchedulerError::PermissionDenied(pid) => {
+                write!(f, "not allowed to act on the process with PID {}", pid)
+            }
+            SchedulerError::WaitForSelf => write!(f, "a process cannot wait for itself"),
+        }
+    }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for SchedulerError {}
+
 /// The reason that a process has stopped and the OS
 /// has called the scheduler.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StopReason {
     /// The process sent a [`Syscall`] system call.
     Syscall {
@@ -166,7 +336,7 @@ pub enum StopReason {
 }
 
 impl Display for StopReason {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             StopReason::Syscall { syscall, remaining } => {
                 write!(f, "Syscall {syscall:?}, remaining {remaining}")
@@ -197,21 +367,253 @@ impl StopReason {
     }
 }
 
+/// A summary of what a scheduler has done so far.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SchedulerStats {
+    /// The time that has passed since the scheduler has started.
+    pub total_time: usize,
+
+    /// The time during which no process has been running.
+    pub idle_time: usize,
+
+    /// The number of processes that have exited.
+    pub completed: usize,
+
+    /// The number of times a process has been dispatched
+    /// after a different one.
+    pub context_switches: usize,
+
+    /// The average time between the creation and the exit of the
+    /// processes that have exited, or 0 if none has exited.
+    pub average_turnaround: f64,
+}
+
+#[cfg(feature = "serde")]
+impl SchedulerStats {
+    /// Returns the statistics as a JSON object.
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(self).expect("The statistics are always serializable")
+    }
+}
+
+/// Which processes a [`Syscall::Signal`] system call wakes up.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignalPolicy {
+    /// Wake up all the processes that wait for the event, like
+    /// a condition variable broadcast. A signal that finds no waiting
+    /// process is dropped, unless the scheduler keeps it, like
+    /// [`RoundRobinScheduler::level_triggered_signals`] does.
+    ///
+    /// [`RoundRobinScheduler::level_triggered_signals`]: crate::RoundRobinScheduler::level_triggered_signals
+    #[default]
+    WakeAll,
+
+    /// Wake up only the process that has been waiting for the event the
+    /// longest, like a semaphore. A signal that finds no waiting process
+    /// is kept, and the next process that waits for the event continues
+    /// right away.
+    WakeOne,
+}
+
+/// Which processes a process may act on with a system call that targets
+/// another process, like [`Syscall::Kill`].
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TargetPolicy {
+    /// A process may target itself and its descendants, and the process
+    /// with PID 1 may target any process.
+    #[default]
+    Descendants,
+
+    /// A process may target any process.
+    AllowAll,
+
+    /// A process may only target itself.
+    SelfOnly,
+}
+
+impl TargetPolicy {
+    /// Returns whether the process `pid` may target the process `target`,
+    /// where `parent` returns the parent of a process.
+    pub fn allows(&self, pid: Pid, target: Pid, parent: impl Fn(Pid) -> Option<Pid>) -> bool {
+        match self {
+            TargetPolicy::Descendants => {
+                pid == 1 || core::iter::successors(Some(target), |&p| parent(p)).any(|p| p == pid)
+            }
+            TargetPolicy::AllowAll => true,
+            TargetPolicy::SelfOnly => pid == target,
+        }
+    }
+}
+
+/// The state in which an adopted process starts.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum AdoptState {
+    /// The process is ready to be scheduled.
+    Ready,
+
+    /// The process is sleeping.
+    Sleeping {
+        /// The scheduler time at which the process wakes up. If this time
+        /// has already passed, the process is adopted as ready.
+        until: usize,
+    },
+}
+
+/// The description of a process that a scheduler adopts, instead of
+/// creating it through a [`Syscall::Fork`] system call.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AdoptSpec {
+    /// The PID that the process should have. If it is [`None`], the
+    /// scheduler allocates the PID like it does for a fork.
+    pub pid: Option<Pid>,
+
+    /// The process priority.
+    pub priority: i8,
+
+    /// The state in which the process starts.
+    pub state: AdoptState,
+
+    /// The timings that the process has accumulated so far
+    /// (total, syscalls, execution).
+    pub timings: (usize, usize, usize),
+
+    /// The process group that the process joins. Schedulers that do not
+    /// model groups keep every process in group 0.
+    pub group: usize,
+}
+
+/// The reason a scheduler refused to adopt a process.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FailReason {
+    /// The requested PID belongs to a process that is still alive.
+    PidInUse(Pid),
+
+    /// The scheduler already has as many processes as it accepts.
+    MaxProcessesReached,
+
+    /// The scheduler has given out every PID, so there is none left for
+    /// the process.
+    PidOverflow,
+}
+
+impl Display for FailReason {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FailReason::PidInUse(pid) => write!(f, "PID {} is already in use", pid),
+            FailReason::MaxProcessesReached => write!(f, "too many processes"),
+            FailReason::PidOverflow => write!(f, "no PID left"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FailReason {}
+
+/// Something that happened inside a scheduler, reported to the trace
+/// callback set with [`Scheduler::set_trace`].
+///
+/// Every event carries the PID of the process it refers to and the
+/// scheduler time at which it happened.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SchedulerEvent {
+    /// The process has been scheduled to run.
+    Dispatch { pid: Pid, time: usize },
+
+    /// The process has been moved from the processor back to the ready queue.
+    Preempt { pid: Pid, time: usize },
+
+    /// The process has started sleeping or waiting for an event.
+    Block { pid: Pid, time: usize },
+
+    /// The process has stopped sleeping or waiting and is ready again.
+    Wake { pid: Pid, time: usize },
+
+    /// The process has been created.
+    Fork { pid: Pid, time: usize },
+
+    /// The process has exited.
+    Exit { pid: Pid, time: usize },
+
+    /// The process has issued a system call that targets the process
+    /// `target`, which it is not allowed to act on.
+    PermissionDenied { pid: Pid, target: Pid, time: usize },
+}
+
+/// A callback that receives the events of a scheduler. It can be shared
+/// between threads, so that the scheduler that calls it can be too.
+pub type Trace = Box<dyn FnMut(&SchedulerEvent) + Send + Sync>;
+
 /// The trait that any scheduler has to implement.
+///
+/// A scheduler has to be [`Send`], so that simulations can run in other
+/// threads. The bundled schedulers are also [`Sync`]: they own all their
+/// state, without globals, and the state shared with other schedulers,
+/// like an [`EventBus`](crate::EventBus), is behind an `Arc<Mutex<_>>`.
 pub trait Scheduler: Send {
     /// Returns the action that the OS has to perform next.
     fn next(&mut self) -> SchedulingDecision;
 
+    /// Returns the decision that [`Scheduler::next`] would return now,
+    /// without changing the state of the scheduler.
+    fn peek_next(&self) -> SchedulingDecision;
+
     /// The scheduler is informed about the stopping of a process
     /// and the reason.
     fn stop(&mut self, reason: StopReason) -> SyscallResult;
 
-    /// Returns the list of processes.
+    /// Returns the list of processes, sorted by PID in ascending order.
     fn list(&mut self) -> Vec<&dyn Process>;
+
+    /// Returns the processes that can never be woken up, sorted by PID.
+    ///
+    /// Unlike [`SchedulingDecision::Deadlock`], this also finds processes
+    /// that wait for each other while other processes keep running.
+    /// Schedulers that do not detect deadlocks return an empty list.
+    fn deadlocked(&self) -> Vec<Pid> {
+        Vec::new()
+    }
+
+    /// Sets a callback that receives every [`SchedulerEvent`], replacing
+    /// the previous one.
+    ///
+    /// Schedulers that do not report events ignore the callback.
+    fn set_trace(&mut self, _trace: Trace) {}
+}
+
+/// A boxed scheduler, like the ones built by
+/// [`make_scheduler`](crate::make_scheduler), can be wrapped too.
+impl<S: Scheduler + ?Sized> Scheduler for Box<S> {
+    fn next(&mut self) -> SchedulingDecision {
+        (**self).next()
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        (**self).peek_next()
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        (**self).stop(reason)
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        (**self).list()
+    }
+
+    fn deadlocked(&self) -> Vec<Pid> {
+        (**self).deadlocked()
+    }
+
+    fn set_trace(&mut self, trace: Trace) {
+        (**self).set_trace(trace)
+    }
 }
 
 /// The state of a process.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ProcessState {
     /// The process is ready to be scheduled.
     Ready,
@@ -229,7 +631,7 @@ pub enum ProcessState {
 }
 
 impl Display for ProcessState {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             ProcessState::Ready => write!(f, "READY"),
             ProcessState::Running => write!(f, "RUNNING"),
@@ -244,6 +646,53 @@ impl Display for ProcessState {
     }
 }
 
+impl ProcessState {
+    /// Returns the kind of the state, without the event.
+    pub fn kind(&self) -> StateKind {
+        match self {
+            ProcessState::Ready => StateKind::Ready,
+            ProcessState::Running => StateKind::Running,
+            ProcessState::Waiting { event: None } => StateKind::Sleeping,
+            ProcessState::Waiting { event: Some(_) } => StateKind::Waiting,
+        }
+    }
+}
+
+/// The kind of a [`ProcessState`], to select processes by their state.
+///
+/// The sleeping processes are told apart from those waiting for an event,
+/// like they are when the state is displayed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StateKind {
+    /// [`ProcessState::Ready`].
+    Ready,
+    /// [`ProcessState::Running`].
+    Running,
+    /// [`ProcessState::Waiting`] without an event.
+    Sleeping,
+    /// [`ProcessState::Waiting`] for an event.
+    Waiting,
+}
+
+/// The processes of a scheduler, borrowed without the [`Vec`] that
+/// [`Scheduler::list`] allocates on every call.
+pub trait Processes {
+    /// Returns the processes sorted by PID in ascending order, like
+    /// [`Scheduler::list`].
+    fn processes(&self) -> impl Iterator<Item = &dyn Process>;
+
+    /// Returns the processes whose state is of the kind `state_kind`,
+    /// sorted by PID in ascending order.
+    fn processes_in_state(&self, state_kind: StateKind) -> impl Iterator<Item = &dyn Process> {
+        self.processes().filter(move |p| p.state().kind() == state_kind)
+    }
+
+    /// Returns the process with the PID `pid`, or [`None`] if there is none.
+    fn get(&self, pid: Pid) -> Option<&dyn Process> {
+        self.processes().find(|p| p.pid() == pid)
+    }
+}
+
 /// The trait that the Process Control Block (PCB) has to implement.
 ///
 /// The PCB can be implemented with any data structure as long as
@@ -263,5 +712,49 @@ pub trait Process: Send {
 
     /// Returns details information
     fn extra(&self) -> String;
-    
+
+    /// Returns the PID of the process that has forked this process, or
+    /// [`None`] for the first process and for schedulers that do not keep
+    /// track of it.
+    fn parent(&self) -> Option<Pid> {
+        None
+    }
+}
+
+/// A copy of what a [`Process`] reports about itself, which does
+/// not borrow the scheduler.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProcessSnapshot {
+    pub pid: Pid,
+    pub state: ProcessState,
+    pub priority: i8,
+    /// The process timings (total, syscalls, execution).
+    pub timings: (usize, usize, usize),
+    pub extra: String,
+}
+
+impl ProcessSnapshot {
+    /// Takes a snapshot of every process of `scheduler`, sorted by PID.
+    pub fn all(scheduler: &mut dyn Scheduler) -> Vec<ProcessSnapshot> {
+        scheduler.list().into_iter().map(ProcessSnapshot::from).collect()
+    }
+
+    /// Returns the snapshot as a JSON object.
+    #[cfg(feature = "serde")]
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(self).expect("A process snapshot is always serializable")
+    }
+}
+
+impl From<&dyn Process> for ProcessSnapshot {
+    fn from(process: &dyn Process) -> Self {
+        ProcessSnapshot {
+            pid: process.pid(),
+            state: process.state(),
+            priority: process.priority(),
+            timings: process.timings(),
+            extra: process.extra(),
+        }
+    }
 }
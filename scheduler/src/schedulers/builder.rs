@@ -0,0 +1,316 @@
+use super::{
+    CfsScheduler, FcfsScheduler, PriorityRoundRobinScheduler, RoundRobinScheduler, SchedulerKind,
+    SrtfScheduler, StrideScheduler,
+};
+#[cfg(feature = "std")]
+use crate::events::EventBus;
+use crate::scheduler::{Scheduler, SignalPolicy, TargetPolicy, Trace};
+use core::fmt::{self, Display};
+use core::num::NonZeroUsize;
+use alloc::boxed::Box;
+
+/// The reason a [`SchedulerBuilder`] could not build a scheduler.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// No timeslice has been set.
+    MissingTimeslice,
+
+    /// The timeslice is 0.
+    ZeroTimeslice,
+
+    /// The aging time is 0.
+    ZeroAging,
+
+    /// The number of cores is 0.
+    ZeroCores,
+
+    /// More than one core has been set for a scheduler driven through
+    /// [`Scheduler`], which runs a single process at a time.
+    MultipleCores,
+
+    /// An option has been set that the scheduler does not have.
+    Unsupported {
+        option: &'static str,
+        kind: SchedulerKind,
+    },
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::MissingTimeslice => write!(f, "the timeslice has not been set"),
+            BuildError::ZeroTimeslice => write!(f, "the timeslice must not be 0"),
+            BuildError::ZeroAging => write!(f, "the aging time must not be 0"),
+            BuildError::ZeroCores => write!(f, "the number of cores must not be 0"),
+            BuildError::MultipleCores => {
+                write!(f, "the cores can only be driven by a round robin scheduler")
+            }
+            BuildError::Unsupported { option, kind } => {
+                write!(f, "the {} scheduler has no `{}` option", kind, option)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuildError {}
+
+/// Builds any of the bundled schedulers, checking the settings.
+///
+/// The timeslice is required, all the other settings are optional.
+/// Setting an option that the built scheduler does not have is an error.
+///
+/// ```
+/// use scheduler::SchedulerBuilder;
+///
+/// let scheduler = SchedulerBuilder::new()
+///     .timeslice(5)
+///     .minimum_remaining(2)
+///     .max_processes(10)
+///     .build_round_robin()
+///     .unwrap();
+/// ```
+#[derive(Default)]
+pub struct SchedulerBuilder {
+    timeslice: Option<usize>,
+    minimum_remaining: usize,
+    aging: Option<usize>,
+    cores: Option<usize>,
+    kill_orphans: bool,
+    signal_policy: Option<SignalPolicy>,
+    target_policy: Option<TargetPolicy>,
+    level_triggered_signals: bool,
+    max_processes: Option<usize>,
+    #[cfg(feature = "std")]
+    event_bus: Option<EventBus>,
+    trace: Option<Trace>,
+}
+
+impl SchedulerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The time a process can run before it is preempted, the
+    /// total time that is split between the processes for CFS.
+    pub fn timeslice(mut self, timeslice: usize) -> Self {
+        self.timeslice = Some(timeslice);
+        self
+    }
+
+    /// The least time that a process has to have left from its
+    /// timeslice after a system call to keep running, 0 by default.
+    pub fn minimum_remaining(mut self, minimum_remaining: usize) -> Self {
+        self.minimum_remaining = minimum_remaining;
+        self
+    }
+
+    /// The time a ready process waits before its priority is raised,
+    /// only for the priority scheduler.
+    pub fn aging(mut self, aging: usize) -> Self {
+        self.aging = Some(aging);
+        self
+    }
+
+    /// The number of processors, only for round robin.
+    pub fn cores(mut self, cores: usize) -> Self {
+        self.cores = Some(cores);
+        self
+    }
+
+    /// Kills the children of exited processes, only for round robin.
+    pub fn kill_orphans(mut self, kill_orphans: bool) -> Self {
+        self.kill_orphans = kill_orphans;
+        self
+    }
+
+    /// Which processes a signal wakes up, only for round robin.
+    pub fn signal_policy(mut self, signal_policy: SignalPolicy) -> Self {
+        self.signal_policy = Some(signal_policy);
+        self
+    }
+
+    /// Which processes a process may kill, only for round robin.
+    pub fn target_policy(mut self, target_policy: TargetPolicy) -> Self {
+        self.target_policy = Some(target_policy);
+        self
+    }
+
+    /// Keeps the signals that find no waiting process for the next wait,
+    /// only for round robin.
+    pub fn level_triggered_signals(mut self, level_triggered: bool) -> Self {
+        self.level_triggered_signals = level_triggered;
+        self
+    }
+
+    /// The number of processes after which forks fail, only for round robin.
+    pub fn max_processes(mut self, max_processes: usize) -> Self {
+        self.max_processes = Some(max_processes);
+        self
+    }
+
+    /// The bus that carries the signals to and from other schedulers,
+    /// only for round robin.
+    #[cfg(feature = "std")]
+    pub fn event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// The callback that receives the events of the scheduler,
+    /// see [`Scheduler::set_trace`].
+    pub fn trace(mut self, trace: Trace) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    fn checked_timeslice(&self) -> Result<NonZeroUsize, BuildError> {
+        let timeslice = self.timeslice.ok_or(BuildError::MissingTimeslice)?;
+        NonZeroUsize::new(timeslice).ok_or(BuildError::ZeroTimeslice)
+    }
+
+    /// Fails if an option is set that the scheduler of `kind` does not have.
+    fn check_options(&self, kind: SchedulerKind) -> Result<(), BuildError> {
+        // the options that only some schedulers have, whether they are set
+        // and the scheduler that has them
+        let options = [
+            ("aging", self.aging.is_some(), SchedulerKind::Priority),
+            ("cores", self.cores.is_some(), SchedulerKind::RoundRobin),
+            ("kill_orphans", self.kill_orphans, SchedulerKind::RoundRobin),
+            (
+                "signal_policy",
+                self.signal_policy.is_some(),
+                SchedulerKind::RoundRobin,
+            ),
+            (
+                "target_policy",
+                self.target_policy.is_some(),
+                SchedulerKind::RoundRobin,
+            ),
+            (
+                "level_triggered_signals",
+                self.level_triggered_signals,
+                SchedulerKind::RoundRobin,
+            ),
+            (
+                "max_processes",
+                self.max_processes.is_some(),
+                SchedulerKind::RoundRobin,
+            ),
+            #[cfg(feature = "std")]
+            (
+                "event_bus",
+                self.event_bus.is_some(),
+                SchedulerKind::RoundRobin,
+            ),
+        ];
+        match options
+            .into_iter()
+            .find(|&(_, set, owner)| set && owner != kind)
+        {
+            Some((option, ..)) => Err(BuildError::Unsupported { option, kind }),
+            None => Ok(()),
+        }
+    }
+
+    fn apply_trace(&mut self, scheduler: &mut dyn Scheduler) {
+        if let Some(trace) = self.trace.take() {
+            scheduler.set_trace(trace);
+        }
+    }
+
+    pub fn build_round_robin(mut self) -> Result<RoundRobinScheduler, BuildError> {
+        let timeslice = self.checked_timeslice()?;
+        self.check_options(SchedulerKind::RoundRobin)?;
+        let mut scheduler = match self.cores {
+            Some(cores) => RoundRobinScheduler::with_cores(
+                timeslice,
+                self.minimum_remaining,
+                NonZeroUsize::new(cores).ok_or(BuildError::ZeroCores)?,
+            ),
+            None => RoundRobinScheduler::new(timeslice, self.minimum_remaining),
+        };
+        scheduler = scheduler.kill_orphans(self.kill_orphans);
+        if let Some(signal_policy) = self.signal_policy {
+            scheduler = scheduler.signal_policy(signal_policy);
+        }
+        if let Some(target_policy) = self.target_policy {
+            scheduler = scheduler.target_policy(target_policy);
+        }
+        scheduler = scheduler.level_triggered_signals(self.level_triggered_signals);
+        if let Some(max_processes) = self.max_processes {
+            scheduler = scheduler.max_processes(max_processes);
+        }
+        #[cfg(feature = "std")]
+        if let Some(event_bus) = self.event_bus.take() {
+            scheduler = scheduler.with_event_bus(event_bus);
+        }
+        self.apply_trace(&mut scheduler);
+        Ok(scheduler)
+    }
+
+    pub fn build_priority(mut self) -> Result<PriorityRoundRobinScheduler, BuildError> {
+        let timeslice = self.checked_timeslice()?;
+        self.check_options(SchedulerKind::Priority)?;
+        let mut scheduler = match self.aging {
+            Some(aging) => PriorityRoundRobinScheduler::with_aging(
+                timeslice,
+                self.minimum_remaining,
+                NonZeroUsize::new(aging).ok_or(BuildError::ZeroAging)?,
+            ),
+            None => PriorityRoundRobinScheduler::new(timeslice, self.minimum_remaining),
+        };
+        self.apply_trace(&mut scheduler);
+        Ok(scheduler)
+    }
+
+    pub fn build_cfs(mut self) -> Result<CfsScheduler, BuildError> {
+        let timeslice = self.checked_timeslice()?;
+        self.check_options(SchedulerKind::Cfs)?;
+        let mut scheduler = CfsScheduler::new(timeslice, self.minimum_remaining);
+        self.apply_trace(&mut scheduler);
+        Ok(scheduler)
+    }
+
+    pub fn build_fcfs(mut self) -> Result<FcfsScheduler, BuildError> {
+        let timeslice = self.checked_timeslice()?;
+        self.check_options(SchedulerKind::Fcfs)?;
+        let mut scheduler = FcfsScheduler::new(timeslice);
+        self.apply_trace(&mut scheduler);
+        Ok(scheduler)
+    }
+
+    pub fn build_srtf(mut self) -> Result<SrtfScheduler, BuildError> {
+        let timeslice = self.checked_timeslice()?;
+        self.check_options(SchedulerKind::Srtf)?;
+        let mut scheduler = SrtfScheduler::new(timeslice);
+        self.apply_trace(&mut scheduler);
+        Ok(scheduler)
+    }
+
+    pub fn build_stride(mut self) -> Result<StrideScheduler, BuildError> {
+        let timeslice = self.checked_timeslice()?;
+        self.check_options(SchedulerKind::Stride)?;
+        let mut scheduler = StrideScheduler::new(timeslice);
+        self.apply_trace(&mut scheduler);
+        Ok(scheduler)
+    }
+
+    /// Builds the scheduler of `kind`.
+    ///
+    /// The built scheduler runs on a single core, a round robin scheduler
+    /// with more cores is built with [`SchedulerBuilder::build_round_robin`].
+    pub fn build(self, kind: SchedulerKind) -> Result<Box<dyn Scheduler>, BuildError> {
+        if kind == SchedulerKind::RoundRobin && self.cores.is_some_and(|cores| cores > 1) {
+            return Err(BuildError::MultipleCores);
+        }
+        Ok(match kind {
+            SchedulerKind::RoundRobin => Box::new(self.build_round_robin()?),
+            SchedulerKind::Priority => Box::new(self.build_priority()?),
+            SchedulerKind::Cfs => Box::new(self.build_cfs()?),
+            SchedulerKind::Fcfs => Box::new(self.build_fcfs()?),
+            SchedulerKind::Srtf => Box::new(self.build_srtf()?),
+            SchedulerKind::Stride => Box::new(self.build_stride()?),
+        })
+    }
+}
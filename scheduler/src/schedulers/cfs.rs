@@ -0,0 +1,468 @@
+use crate::accounting::{AccountingClock, ChargeKind};
+use crate::events::EventTable;
+use crate::pcb::ProcessControlBlock;
+use crate::scheduler::{
+    AdoptSpec, AdoptState, FailReason, Pid, Process, ProcessState, Processes, Scheduler,
+    SchedulerError, SchedulingDecision, StopReason, Syscall, SyscallResult,
+};
+use core::num::NonZeroUsize;
+use alloc::{format, string::String, vec::Vec};
+
+/// The lowest priority a process can have, lower values are clamped to it.
+const MIN_PRIORITY: i8 = 0;
+
+/// The highest priority a process can have, higher values are clamped to it.
+const MAX_PRIORITY: i8 = 5;
+
+/// The vruntime multiplier of each priority, indexed by the clamped priority.
+///
+/// Lower priorities have larger weights, so their vruntime grows faster
+/// and they get picked less often than higher priority processes.
+const WEIGHTS: [usize; (MAX_PRIORITY - MIN_PRIORITY + 1) as usize] = [6, 5, 4, 3, 2, 1];
+
+/// Returns the vruntime multiplier of `priority`.
+fn weight(priority: i8) -> usize {
+    WEIGHTS[(priority.clamp(MIN_PRIORITY, MAX_PRIORITY) - MIN_PRIORITY) as usize]
+}
+
+pub struct CfsProcess {
+    pcb: ProcessControlBlock,
+    vruntime: usize,
+    remaining: usize,
+    wake_time: usize,
+    /// Whether the process waits for an event only until `wake_time`.
+    has_timeout: bool,
+    /// The process that this process waits to exit.
+    waiting_for: Option<Pid>,
+}
+
+impl CfsProcess {
+    pub fn new(pid: Pid, priority: i8, vruntime: usize) -> Self {
+        CfsProcess {
+            pcb: ProcessControlBlock::new(pid, ProcessState::Ready, priority, (0, 0, 0)),
+            vruntime,
+            remaining: 0,
+            wake_time: 0,
+            has_timeout: false,
+            waiting_for: None,
+        }
+    }
+
+    pub fn set_state(&mut self, new_state: ProcessState) {
+        self.pcb.set_state(new_state);
+    }
+}
+
+impl Process for CfsProcess {
+    fn pid(&self) -> Pid {
+        self.pcb.pid()
+    }
+
+    fn state(&self) -> ProcessState {
+        self.pcb.state()
+    }
+
+    fn timings(&self) -> (usize, usize, usize) {
+        self.pcb.timings()
+    }
+
+    fn priority(&self) -> i8 {
+        self.pcb.priority()
+    }
+
+    fn extra(&self) -> String {
+        format!("vruntime={}", self.vruntime)
+    }
+
+    fn parent(&self) -> Option<Pid> {
+        self.pcb.parent()
+    }
+}
+
+pub struct CfsScheduler {
+    processes: Vec<CfsProcess>,
+    running: Option<Pid>,
+    events: EventTable,
+    cpu_time: NonZeroUsize,
+    minimum_remaining_timeslice: usize,
+    nr_processes: usize,
+    accounting: AccountingClock,
+    /// The floor of the vruntimes, the smallest vruntime of the processes
+    /// that are ready or running, which never decreases. New processes and
+    /// processes that wake up start from it.
+    min_vruntime: usize,
+}
+
+impl CfsScheduler {
+    pub fn new(cpu_time: NonZeroUsize, minimum_remaining_timeslice: usize) -> Self {
+        Self {
+            processes: Vec::new(),
+            running: None,
+            events: EventTable::new(),
+            cpu_time,
+            minimum_remaining_timeslice,
+            nr_processes: 0,
+            accounting: AccountingClock::new(),
+            min_vruntime: 0,
+        }
+    }
+
+    fn index(&self, pid: Pid) -> usize {
+        self.processes
+            .iter()
+            .position(|p| p.pid() == pid)
+            .expect("Process not found in the list")
+    }
+
+    /// The timeslice of a process, the cpu time is split equally
+    /// between all the processes.
+    fn timeslice(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.cpu_time.get() / self.processes.len().max(1))
+            .unwrap_or(NonZeroUsize::MIN)
+    }
+
+    /// Raises the floor of the vruntimes to the smallest vruntime of the
+    /// processes that are ready or running, if there are some. The floor
+    /// does not follow a process that leaves with a smaller vruntime.
+    fn update_min_vruntime(&mut self) {
+        let runnable = self
+            .processes
+            .iter()
+            .filter(|p| matches!(p.state(), ProcessState::Ready | ProcessState::Running))
+            .map(|p| p.vruntime)
+            .min();
+        if let Some(vruntime) = runnable {
+            self.min_vruntime = self.min_vruntime.max(vruntime);
+        }
+    }
+
+    /// Makes a waiting process ready. Its vruntime has not grown while it
+    /// waited, so it is raised to `min_vruntime`, otherwise the process
+    /// would monopolize the cpu until it caught up with the others.
+    fn wake(process: &mut CfsProcess, min_vruntime: usize) {
+        process.vruntime = process.vruntime.max(min_vruntime);
+        process.set_state(ProcessState::Ready);
+    }
+
+    /// Charges the running process for the time it has used until it has
+    /// stopped with `remaining` time units left, and advances the clock
+    /// for everybody else.
+    fn charge(&mut self, pid: Pid, remaining: usize, kind: ChargeKind) {
+        let index = self.index(pid);
+        let consumed = self.processes[index].remaining.saturating_sub(remaining);
+        let processes = self.processes.iter_mut().map(|p| &mut p.pcb);
+        self.accounting.charge(pid, consumed, kind, processes);
+        let process = &mut self.processes[index];
+        process.vruntime += consumed * weight(process.priority());
+        // a process cannot have more time left than it has been granted
+        process.remaining = process.remaining.min(remaining);
+        self.update_min_vruntime();
+    }
+
+    /// Adds a process that has not been created by a [`Syscall::Fork`]
+    /// system call. Like a forked process, it starts with the floor of
+    /// the vruntimes. The group of the process is ignored, as every
+    /// process is in group 0. Fails if there is no PID left to give to
+    /// the process.
+    pub fn adopt(&mut self, spec: AdoptSpec) -> Result<Pid, FailReason> {
+        let pid = match spec.pid {
+            Some(pid) if self.processes.iter().any(|p| p.pid() == pid) => {
+                return Err(FailReason::PidInUse(pid));
+            }
+            Some(pid) => pid,
+            None if self.nr_processes == usize::MAX => return Err(FailReason::PidOverflow),
+            None => Pid::new(self.nr_processes + 1),
+        };
+        self.nr_processes = self.nr_processes.max(pid.get());
+
+        let mut process = CfsProcess::new(pid, spec.priority, self.min_vruntime);
+        process.pcb =
+            ProcessControlBlock::new(pid, ProcessState::Ready, spec.priority, spec.timings);
+        if let AdoptState::Sleeping { until } = spec.state {
+            if until > self.accounting.time() {
+                process.wake_time = until;
+                process.set_state(ProcessState::Waiting { event: None });
+            }
+        }
+        // the processes stay sorted by PID when the PID is given
+        let at = self.processes.partition_point(|p| p.pid() < pid);
+        self.processes.insert(at, process);
+        Ok(pid)
+    }
+
+    /// Removes a process and wakes up the processes waiting for it to exit.
+    fn remove(&mut self, pid: Pid) {
+        if self.running == Some(pid) {
+            self.running = None;
+        }
+        self.processes.retain(|p| p.pid() != pid);
+        self.events.remove(pid);
+        for process in self.processes.iter_mut() {
+            if process.waiting_for == Some(pid) {
+                process.waiting_for = None;
+                Self::wake(process, self.min_vruntime);
+            }
+        }
+    }
+
+    /// Whether the process waits until its `wake_time`, either sleeping
+    /// or waiting for an event with a timeout.
+    fn is_sleeping(process: &CfsProcess) -> bool {
+        match process.state() {
+            ProcessState::Waiting { event: None } => process.waiting_for.is_none(),
+            ProcessState::Waiting { event: Some(_) } => process.has_timeout,
+            _ => false,
+        }
+    }
+
+    /// Whether the process is ready, or will be once the processes
+    /// whose sleep time has passed are woken up.
+    fn is_ready(&self, process: &CfsProcess) -> bool {
+        process.state() == ProcessState::Ready
+            || (Self::is_sleeping(process) && process.wake_time <= self.accounting.time())
+    }
+
+    /// Moves all the processes whose sleep time has passed to the ready state.
+    fn wake_sleepers(&mut self) {
+        for process in self.processes.iter_mut() {
+            if Self::is_sleeping(process)
+                && process.wake_time <= self.accounting.time()
+            {
+                // a wait for an event may have timed out
+                self.events.remove(process.pid());
+                Self::wake(process, self.min_vruntime);
+            }
+        }
+    }
+}
+
+impl Processes for CfsScheduler {
+    fn processes(&self) -> impl Iterator<Item = &dyn Process> {
+        // the processes are kept sorted by PID
+        self.processes.iter().map(|p| p as &dyn Process)
+    }
+}
+
+impl Scheduler for CfsScheduler {
+    fn next(&mut self) -> SchedulingDecision {
+        if !self.processes.is_empty() && !self.processes.iter().any(|p| p.pid() == 1) {
+            return SchedulingDecision::Panic;
+        }
+        self.wake_sleepers();
+
+        if let Some(pid) = self.running.take() {
+            let index = self.index(pid);
+            let remaining = self.processes[index].remaining;
+            match NonZeroUsize::new(remaining) {
+                Some(timeslice) if remaining >= self.minimum_remaining_timeslice => {
+                    self.running = Some(pid);
+                    return SchedulingDecision::Run { pid, timeslice };
+                }
+                _ => self.processes[index].set_state(ProcessState::Ready),
+            }
+        }
+
+        let next = self
+            .processes
+            .iter()
+            .filter(|p| p.state() == ProcessState::Ready)
+            .min_by_key(|p| (p.vruntime, p.pid()))
+            .map(|p| p.pid());
+        if let Some(pid) = next {
+            let timeslice = self.timeslice();
+            let index = self.index(pid);
+            self.processes[index].remaining = timeslice.get();
+            self.processes[index].set_state(ProcessState::Running);
+            self.running = Some(pid);
+            return SchedulingDecision::Run { pid, timeslice };
+        }
+
+        let wake_time = self
+            .processes
+            .iter()
+            .filter(|p| Self::is_sleeping(p))
+            .map(|p| p.wake_time)
+            .min();
+        let time = self.accounting.time();
+        if let Some(amount) = wake_time.and_then(|t| NonZeroUsize::new(t - time)) {
+            let processes = self.processes.iter_mut().map(|p| &mut p.pcb);
+            self.accounting.advance_sleep(amount.get(), processes);
+            self.wake_sleepers();
+            SchedulingDecision::Sleep(amount)
+        } else if self.processes.is_empty() {
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        if !self.processes.is_empty() && !self.processes.iter().any(|p| p.pid() == 1) {
+            return SchedulingDecision::Panic;
+        }
+
+        if let Some(pid) = self.running {
+            let remaining = self.processes[self.index(pid)].remaining;
+            match NonZeroUsize::new(remaining) {
+                Some(timeslice) if remaining >= self.minimum_remaining_timeslice => {
+                    return SchedulingDecision::Run { pid, timeslice };
+                }
+                // the running process would be put back with the ready ones
+                _ => {}
+            }
+        }
+
+        let next = self
+            .processes
+            .iter()
+            .filter(|p| self.is_ready(p) || self.running == Some(p.pid()))
+            // the sleepers are raised to the floor when they wake up
+            .min_by_key(|p| (p.vruntime.max(self.min_vruntime), p.pid()))
+            .map(|p| p.pid());
+        if let Some(pid) = next {
+            return SchedulingDecision::Run {
+                pid,
+                timeslice: self.timeslice(),
+            };
+        }
+
+        let wake_time = self
+            .processes
+            .iter()
+            .filter(|p| Self::is_sleeping(p))
+            .map(|p| p.wake_time)
+            .min();
+        let time = self.accounting.time();
+        if let Some(amount) = wake_time.and_then(|t| NonZeroUsize::new(t - time)) {
+            SchedulingDecision::Sleep(amount)
+        } else if self.processes.is_empty() {
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        match reason {
+            StopReason::Syscall { syscall, remaining } => {
+                if let Some(pid) = self.running {
+                    self.charge(pid, remaining, ChargeKind::Syscall);
+                } else if !matches!(syscall, Syscall::Fork(_)) {
+                    return SyscallResult::NoRunningProcess;
+                }
+                match syscall {
+                    Syscall::Fork(priority) => {
+                        self.nr_processes += 1;
+                        let pid = Pid::new(self.nr_processes);
+                        let vruntime = self.min_vruntime;
+                        self.processes.push(CfsProcess::new(pid, priority, vruntime));
+                        SyscallResult::Pid(pid)
+                    }
+                    Syscall::Sleep(amount) | Syscall::WaitTimeout { max: amount, .. }
+                        if self.accounting.time().checked_add(amount).is_none() =>
+                    {
+                        SyscallResult::Error(SchedulerError::InvalidSleepDuration(amount))
+                    }
+                    Syscall::Sleep(0) | Syscall::Yield => {
+                        if let Some(pid) = self.running.take() {
+                            let index = self.index(pid);
+                            self.processes[index].set_state(ProcessState::Ready);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::Sleep(amount) => {
+                        if let Some(pid) = self.running.take() {
+                            let index = self.index(pid);
+                            self.processes[index].wake_time = self.accounting.time() + amount;
+                            self.processes[index]
+                                .set_state(ProcessState::Waiting { event: None });
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::SetPriority(priority) => {
+                        if let Some(pid) = self.running {
+                            let index = self.index(pid);
+                            self.processes[index]
+                                .pcb
+                                .set_priority(priority.clamp(MIN_PRIORITY, MAX_PRIORITY));
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::Wait(event) => {
+                        if let Some(pid) = self.running.take() {
+                            let index = self.index(pid);
+                            self.processes[index].has_timeout = false;
+                            self.processes[index].set_state(ProcessState::Waiting {
+                                event: Some(event),
+                            });
+                            self.events.wait(pid, event);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitTimeout { event, max } => {
+                        if let Some(pid) = self.running.take() {
+                            let index = self.index(pid);
+                            self.processes[index].wake_time = self.accounting.time() + max;
+                            self.processes[index].has_timeout = true;
+                            self.processes[index].set_state(ProcessState::Waiting {
+                                event: Some(event),
+                            });
+                            self.events.wait(pid, event);
+                        }
+                        SyscallResult::Success
+                    }
+                    // all the processes are in group 0
+                    Syscall::Signal(event) | Syscall::SignalGroup(0, event) => {
+                        for pid in self.events.signal(event) {
+                            let index = self.index(pid);
+                            Self::wake(&mut self.processes[index], self.min_vruntime);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::SetGroup(_) | Syscall::SignalGroup(..) => SyscallResult::Success,
+                    Syscall::WaitPid(pid) if self.running == Some(pid) => {
+                        SyscallResult::Error(SchedulerError::WaitForSelf)
+                    }
+                    Syscall::WaitPid(pid) if self.processes.iter().any(|p| p.pid() == pid) => {
+                        if let Some(waiter) = self.running.take() {
+                            let index = self.index(waiter);
+                            self.processes[index].waiting_for = Some(pid);
+                            self.processes[index].set_state(ProcessState::Waiting { event: None });
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(pid) if pid.get() <= self.nr_processes => {
+                        // the process has already exited
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(_) => SyscallResult::NoSuchProcess,
+                    Syscall::Kill(pid) => {
+                        if !self.processes.iter().any(|p| p.pid() == pid) {
+                            return SyscallResult::NoSuchProcess;
+                        }
+                        self.remove(pid);
+                        SyscallResult::Success
+                    }
+                    Syscall::Exit | Syscall::ExitWith(_) => {
+                        if let Some(pid) = self.running {
+                            self.remove(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                }
+            }
+            StopReason::Expired => {
+                if let Some(pid) = self.running.take() {
+                    self.charge(pid, 0, ChargeKind::Execution);
+                    let index = self.index(pid);
+                    self.processes[index].set_state(ProcessState::Ready);
+                }
+                SyscallResult::Success
+            }
+        }
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.processes().collect()
+    }
+}
@@ -0,0 +1,394 @@
+use crate::accounting::{AccountingClock, ChargeKind};
+use crate::events::EventTable;
+use crate::pcb::ProcessControlBlock;
+use crate::scheduler::{
+    Pid, Process, ProcessState, Processes, Scheduler, SchedulerError, SchedulingDecision,
+    StopReason, Syscall, SyscallResult,
+};
+use alloc::{string::String, vec::Vec};
+use alloc::collections::VecDeque;
+use core::num::NonZeroUsize;
+
+pub struct FcfsProcess {
+    pcb: ProcessControlBlock,
+    wake_time: usize,
+    /// Whether the process waits for an event only until `wake_time`.
+    has_timeout: bool,
+    /// The process that this process waits to exit.
+    waiting_for: Option<Pid>,
+}
+
+impl FcfsProcess {
+    pub fn new(pid: Pid, priority: i8) -> Self {
+        FcfsProcess {
+            pcb: ProcessControlBlock::new(pid, ProcessState::Ready, priority, (0, 0, 0)),
+            wake_time: 0,
+            has_timeout: false,
+            waiting_for: None,
+        }
+    }
+
+    pub fn set_state(&mut self, new_state: ProcessState) {
+        self.pcb.set_state(new_state);
+    }
+
+    /// Whether the process waits until its `wake_time`, either sleeping
+    /// or waiting for an event with a timeout.
+    fn is_sleeping(&self) -> bool {
+        match self.state() {
+            ProcessState::Waiting { event: None } => self.waiting_for.is_none(),
+            ProcessState::Waiting { event: Some(_) } => self.has_timeout,
+            _ => false,
+        }
+    }
+}
+
+impl Process for FcfsProcess {
+    fn pid(&self) -> Pid {
+        self.pcb.pid()
+    }
+
+    fn state(&self) -> ProcessState {
+        self.pcb.state()
+    }
+
+    fn timings(&self) -> (usize, usize, usize) {
+        self.pcb.timings()
+    }
+
+    fn priority(&self) -> i8 {
+        self.pcb.priority()
+    }
+
+    fn extra(&self) -> String {
+        String::new()
+    }
+
+    fn parent(&self) -> Option<Pid> {
+        self.pcb.parent()
+    }
+}
+
+/// A first come first served scheduler.
+///
+/// Processes run in the order in which they have arrived, either forked
+/// or woken up, and keep the processor until they give it up through a
+/// system call. A process is never preempted, when the time it has been
+/// granted expires it is granted more.
+pub struct FcfsScheduler {
+    processes: Vec<FcfsProcess>,
+    /// The time granted to a process each time it is dispatched.
+    timeslice: NonZeroUsize,
+    /// The processes in arrival order, the front one is the running process.
+    ready_queue: VecDeque<Pid>,
+    events: EventTable,
+    /// The time left from what has been granted to the running process.
+    remaining: usize,
+    nr_processes: usize,
+    accounting: AccountingClock,
+    /// Set when the process with PID 1 exits, any process left
+    /// after that makes the scheduler panic.
+    init_exited: bool,
+}
+
+impl FcfsScheduler {
+    pub fn new(timeslice: NonZeroUsize) -> Self {
+        Self {
+            processes: Vec::new(),
+            timeslice,
+            ready_queue: VecDeque::new(),
+            events: EventTable::new(),
+            remaining: 0,
+            nr_processes: 0,
+            accounting: AccountingClock::new(),
+            init_exited: false,
+        }
+    }
+
+    fn process_index(&self, pid: Pid) -> usize {
+        self.processes
+            .iter()
+            .position(|p| p.pid() == pid)
+            .expect("Process not found in the list")
+    }
+
+    /// The running process, the front of the ready queue once it is dispatched.
+    fn running(&self) -> Option<Pid> {
+        self.ready_queue
+            .front()
+            .copied()
+            .filter(|&pid| self.processes[self.process_index(pid)].state() == ProcessState::Running)
+    }
+
+    /// Charges the running process for `consumed` time units and credits the
+    /// same amount of time to every other process.
+    fn charge(&mut self, pid: Pid, consumed: usize, kind: ChargeKind) {
+        let processes = self.processes.iter_mut().map(|p| &mut p.pcb);
+        self.accounting.charge(pid, consumed, kind, processes);
+    }
+
+    /// Takes the running process off the ready queue and puts it in `state`.
+    fn block(&mut self, state: ProcessState) -> Option<Pid> {
+        let pid = self.ready_queue.pop_front()?;
+        let process_index = self.process_index(pid);
+        self.processes[process_index].set_state(state);
+        Some(pid)
+    }
+
+    /// Moves a process to the back of the ready queue, as a new arrival.
+    fn arrive(&mut self, pid: Pid) {
+        let process_index = self.process_index(pid);
+        self.processes[process_index].set_state(ProcessState::Ready);
+        self.processes[process_index].waiting_for = None;
+        self.ready_queue.push_back(pid);
+    }
+
+    /// Returns the processes whose sleep has ended, in the order
+    /// in which they wake up.
+    fn woken(&self) -> Vec<Pid> {
+        let mut woken = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping() && p.wake_time <= self.accounting.time())
+            .map(|p| (p.wake_time, p.pid()))
+            .collect::<Vec<_>>();
+        woken.sort();
+        woken.into_iter().map(|(_, pid)| pid).collect()
+    }
+
+    /// Moves the processes whose sleep has ended to the ready queue,
+    /// in the order in which they wake up.
+    fn wake_sleepers(&mut self) {
+        for pid in self.woken() {
+            // a wait for an event may have timed out
+            self.events.remove(pid);
+            self.arrive(pid);
+        }
+    }
+
+    /// Removes a process and wakes up the processes waiting for it to exit.
+    fn remove(&mut self, pid: Pid) {
+        self.ready_queue.retain(|&p| p != pid);
+        self.events.remove(pid);
+        self.processes.retain(|p| p.pid() != pid);
+        if pid == 1 {
+            self.init_exited = true;
+        }
+        let waiters = self
+            .processes
+            .iter()
+            .filter(|p| p.waiting_for == Some(pid))
+            .map(|p| p.pid())
+            .collect::<Vec<_>>();
+        for waiter in waiters {
+            self.arrive(waiter);
+        }
+    }
+}
+
+impl Processes for FcfsScheduler {
+    fn processes(&self) -> impl Iterator<Item = &dyn Process> {
+        // the processes are kept sorted by PID
+        self.processes.iter().map(|p| p as &dyn Process)
+    }
+}
+
+impl Scheduler for FcfsScheduler {
+    fn next(&mut self) -> SchedulingDecision {
+        if self.init_exited && !self.processes.is_empty() {
+            return SchedulingDecision::Panic;
+        }
+        self.wake_sleepers();
+
+        if let Some(&pid) = self.ready_queue.front() {
+            // a process that has just been dispatched, or that has used
+            // everything it has been granted, gets a new timeslice
+            if self.running() != Some(pid) || self.remaining == 0 {
+                self.remaining = self.timeslice.get();
+            }
+            let process_index = self.process_index(pid);
+            self.processes[process_index].set_state(ProcessState::Running);
+            return SchedulingDecision::Run {
+                pid,
+                timeslice: NonZeroUsize::new(self.remaining).unwrap_or(self.timeslice),
+            };
+        }
+
+        let wake_time = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping())
+            .map(|p| p.wake_time)
+            .min();
+        if let Some(wake_time) = wake_time {
+            let amount = wake_time.saturating_sub(self.accounting.time());
+            let processes = self.processes.iter_mut().map(|p| &mut p.pcb);
+            self.accounting.advance_sleep(amount, processes);
+            match NonZeroUsize::new(amount) {
+                Some(amount) => SchedulingDecision::Sleep(amount),
+                // the sleep has already ended, the process is ready right away
+                None => self.next(),
+            }
+        } else if self.processes.is_empty() {
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        if self.init_exited && !self.processes.is_empty() {
+            return SchedulingDecision::Panic;
+        }
+
+        // the processes that wake up are queued after the ready ones
+        let front = self
+            .ready_queue
+            .front()
+            .copied()
+            .or_else(|| self.woken().first().copied());
+        if let Some(pid) = front {
+            let remaining = if self.running() != Some(pid) || self.remaining == 0 {
+                self.timeslice.get()
+            } else {
+                self.remaining
+            };
+            return SchedulingDecision::Run {
+                pid,
+                timeslice: NonZeroUsize::new(remaining).unwrap_or(self.timeslice),
+            };
+        }
+
+        // every sleep that has ended is handled above
+        let wake_time = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping())
+            .map(|p| p.wake_time)
+            .min();
+        let time = self.accounting.time();
+        if let Some(amount) = wake_time.and_then(|t| NonZeroUsize::new(t - time)) {
+            SchedulingDecision::Sleep(amount)
+        } else if self.processes.is_empty() {
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        match reason {
+            StopReason::Syscall { syscall, remaining } => {
+                let running = self.running();
+                if let Some(pid) = running {
+                    self.charge(pid, self.remaining.saturating_sub(remaining), ChargeKind::Syscall);
+                    // a process cannot have more time left than it has been granted
+                    self.remaining = self.remaining.min(remaining);
+                } else if !matches!(syscall, Syscall::Fork(_)) {
+                    return SyscallResult::NoRunningProcess;
+                }
+                match syscall {
+                    Syscall::Fork(priority) => {
+                        self.nr_processes += 1;
+                        let pid = Pid::new(self.nr_processes);
+                        self.processes.push(FcfsProcess::new(pid, priority));
+                        self.ready_queue.push_back(pid);
+                        SyscallResult::Pid(pid)
+                    }
+                    Syscall::Sleep(amount) | Syscall::WaitTimeout { max: amount, .. }
+                        if self.accounting.time().checked_add(amount).is_none() =>
+                    {
+                        SyscallResult::Error(SchedulerError::InvalidSleepDuration(amount))
+                    }
+                    Syscall::Sleep(0) | Syscall::Yield => {
+                        if let Some(pid) = self.block(ProcessState::Ready) {
+                            self.arrive(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::Sleep(amount) => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: None }) {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].wake_time =
+                                self.accounting.time() + amount;
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::Wait(event) => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: Some(event) }) {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].has_timeout = false;
+                            self.events.wait(pid, event);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitTimeout { event, max } => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: Some(event) }) {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].wake_time = self.accounting.time() + max;
+                            self.processes[process_index].has_timeout = true;
+                            self.events.wait(pid, event);
+                        }
+                        SyscallResult::Success
+                    }
+                    // all the processes are in group 0
+                    Syscall::Signal(event) | Syscall::SignalGroup(0, event) => {
+                        for pid in self.events.signal(event) {
+                            self.arrive(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::SetGroup(_) | Syscall::SignalGroup(..) => SyscallResult::Success,
+                    Syscall::SetPriority(priority) => {
+                        if let Some(pid) = running {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].pcb.set_priority(priority);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(pid) if running == Some(pid) => {
+                        SyscallResult::Error(SchedulerError::WaitForSelf)
+                    }
+                    Syscall::WaitPid(pid) if self.processes.iter().any(|p| p.pid() == pid) => {
+                        if let Some(waiter) = self.block(ProcessState::Waiting { event: None }) {
+                            let process_index = self.process_index(waiter);
+                            self.processes[process_index].waiting_for = Some(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(pid) if pid.get() <= self.nr_processes => {
+                        // the process has already exited
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(_) => SyscallResult::NoSuchProcess,
+                    Syscall::Kill(pid) => {
+                        if self.processes.iter().any(|p| p.pid() == pid) {
+                            self.remove(pid);
+                            SyscallResult::Success
+                        } else {
+                            SyscallResult::NoSuchProcess
+                        }
+                    }
+                    Syscall::Exit | Syscall::ExitWith(_) => {
+                        if let Some(pid) = running {
+                            self.remove(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                }
+            }
+            StopReason::Expired => {
+                // the process keeps the processor, it is granted more time
+                if let Some(pid) = self.running() {
+                    self.charge(pid, self.remaining, ChargeKind::Execution);
+                    self.remaining = 0;
+                }
+                SyscallResult::Success
+            }
+        }
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.processes().collect()
+    }
+}
@@ -0,0 +1,103 @@
+use crate::scheduler::{
+    Pid, Process, Scheduler, SchedulingDecision, StopReason, SyscallResult, Trace,
+};
+use alloc::{string::{String, ToString}, vec::Vec};
+
+/// A scheduler that logs the decisions of another scheduler with the
+/// [`log`] crate.
+///
+/// Every decision is logged at the debug level and every stop of a process
+/// at the trace level, with the state of the process before and after it.
+/// The messages start with the time, counted like for a
+/// [`TimelineScheduler`](crate::TimelineScheduler).
+pub struct LoggingScheduler<S: Scheduler> {
+    scheduler: S,
+    time: usize,
+    /// The process that has been dispatched and the timeslice it has been granted.
+    granted: Option<(Pid, usize)>,
+}
+
+impl<S: Scheduler> LoggingScheduler<S> {
+    pub fn new(scheduler: S) -> Self {
+        Self {
+            scheduler,
+            time: 0,
+            granted: None,
+        }
+    }
+
+    /// Returns the scheduler that has been logged.
+    pub fn into_inner(self) -> S {
+        self.scheduler
+    }
+
+    /// Returns the state of a process as it is logged.
+    fn state(&mut self, pid: Pid) -> String {
+        self.scheduler
+            .list()
+            .iter()
+            .find(|p| p.pid() == pid)
+            .map_or(String::from("EXITED"), |p| p.state().to_string())
+    }
+}
+
+impl<S: Scheduler> Scheduler for LoggingScheduler<S> {
+    fn next(&mut self) -> SchedulingDecision {
+        let decision = self.scheduler.next();
+        log::debug!("time {}: {}", self.time, decision);
+        match decision {
+            SchedulingDecision::Run { pid, timeslice } => {
+                self.granted = Some((pid, timeslice.get()));
+            }
+            SchedulingDecision::Sleep(amount) => self.time += amount.get(),
+            _ => {}
+        }
+        decision
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        self.scheduler.peek_next()
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        let Some((pid, timeslice)) = self.granted.take() else {
+            let result = self.scheduler.stop(reason);
+            log::trace!("time {}: {}, no running process", self.time, reason);
+            return result;
+        };
+        let remaining = match reason {
+            StopReason::Syscall { remaining, .. } => {
+                // the process may issue more system calls before the next dispatch
+                self.granted = Some((pid, remaining));
+                remaining
+            }
+            StopReason::Expired => 0,
+        };
+        self.time += timeslice.saturating_sub(remaining);
+
+        let before = self.state(pid);
+        let result = self.scheduler.stop(reason);
+        let after = self.state(pid);
+        log::trace!(
+            "time {}: {}, process {} {} -> {}",
+            self.time,
+            reason,
+            pid,
+            before,
+            after
+        );
+        result
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.scheduler.list()
+    }
+
+    fn deadlocked(&self) -> Vec<Pid> {
+        self.scheduler.deadlocked()
+    }
+
+    fn set_trace(&mut self, trace: Trace) {
+        self.scheduler.set_trace(trace);
+    }
+}
@@ -10,9 +10,159 @@
 //! ```
 //!
 
-// TODO delete this example
-mod empty;
+mod builder;
+mod cfs;
+mod fcfs;
+#[cfg(feature = "log")]
+mod logging;
+mod priority_round_robin;
+mod queue;
 mod round_robinn;
-pub use empty::Empty;
-pub use round_robinn::RoundRobinScheduler;
-// TODO import your schedulers here
+mod srtf;
+mod stride;
+pub use builder::{BuildError, SchedulerBuilder};
+pub use cfs::CfsScheduler;
+pub use fcfs::FcfsScheduler;
+#[cfg(feature = "log")]
+pub use logging::LoggingScheduler;
+pub use priority_round_robin::PriorityRoundRobinScheduler;
+pub use queue::{FifoQueue, PriorityQueue, QueueContext, QueueHints, QueueScheduler, ReadyQueue};
+pub use round_robinn::{RoundRobinScheduler, SchedulerSnapshot};
+pub use srtf::SrtfScheduler;
+pub use stride::StrideScheduler;
+
+use crate::Scheduler;
+use core::fmt::{self, Display};
+use core::num::NonZeroUsize;
+use core::str::FromStr;
+use alloc::{boxed::Box, string::{String, ToString}};
+
+/// The bundled schedulers, each one can be built with [`make_scheduler`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum SchedulerKind {
+    /// [`RoundRobinScheduler`]
+    RoundRobin,
+
+    /// [`PriorityRoundRobinScheduler`]
+    Priority,
+
+    /// [`CfsScheduler`]
+    Cfs,
+
+    /// [`FcfsScheduler`]
+    Fcfs,
+
+    /// [`SrtfScheduler`]
+    Srtf,
+
+    /// [`StrideScheduler`]
+    Stride,
+}
+
+impl SchedulerKind {
+    pub const ALL: [SchedulerKind; 6] = [
+        SchedulerKind::RoundRobin,
+        SchedulerKind::Priority,
+        SchedulerKind::Cfs,
+        SchedulerKind::Fcfs,
+        SchedulerKind::Srtf,
+        SchedulerKind::Stride,
+    ];
+
+    /// The name of the scheduler, accepted by [`SchedulerKind::from_str`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchedulerKind::RoundRobin => "round-robin",
+            SchedulerKind::Priority => "priority",
+            SchedulerKind::Cfs => "cfs",
+            SchedulerKind::Fcfs => "fcfs",
+            SchedulerKind::Srtf => "srtf",
+            SchedulerKind::Stride => "stride",
+        }
+    }
+}
+
+impl Display for SchedulerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The name of a scheduler that does not exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownSchedulerKind(pub String);
+
+impl Display for UnknownSchedulerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown scheduler `{}`, expected one of: ", self.0)?;
+        let names = SchedulerKind::ALL.map(|kind| kind.name());
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownSchedulerKind {}
+
+impl FromStr for SchedulerKind {
+    type Err = UnknownSchedulerKind;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        SchedulerKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+            .ok_or_else(|| UnknownSchedulerKind(name.to_string()))
+    }
+}
+
+/// The settings of a scheduler built with [`make_scheduler`], the
+/// schedulers ignore the settings that they do not use.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchedulerConfig {
+    /// The time a process can run before it is preempted, the
+    /// total time that is split between the processes for CFS.
+    pub timeslice: NonZeroUsize,
+
+    /// The least time that a process has to have left from its
+    /// timeslice after a system call to keep running, 0 if it is
+    /// left out of a serialized configuration.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub minimum_remaining_timeslice: usize,
+
+    /// The time after which a waiting process has its priority
+    /// raised, for the priority scheduler.
+    pub aging: Option<NonZeroUsize>,
+}
+
+impl SchedulerConfig {
+    pub fn new(timeslice: NonZeroUsize, minimum_remaining_timeslice: usize) -> Self {
+        SchedulerConfig {
+            timeslice,
+            minimum_remaining_timeslice,
+            aging: None,
+        }
+    }
+
+    pub fn aging(mut self, aging: NonZeroUsize) -> Self {
+        self.aging = Some(aging);
+        self
+    }
+}
+
+/// Builds the scheduler of `kind` with the settings of `config`.
+pub fn make_scheduler(kind: SchedulerKind, config: SchedulerConfig) -> Box<dyn Scheduler> {
+    let mut builder = SchedulerBuilder::new()
+        .timeslice(config.timeslice.get())
+        .minimum_remaining(config.minimum_remaining_timeslice);
+    if let (SchedulerKind::Priority, Some(aging)) = (kind, config.aging) {
+        builder = builder.aging(aging.get());
+    }
+    builder
+        .build(kind)
+        .expect("The configuration only has valid settings")
+}
@@ -0,0 +1,528 @@
+use crate::accounting::{AccountingClock, ChargeKind};
+use crate::events::EventTable;
+use crate::pcb::ProcessControlBlock;
+use crate::scheduler::{
+    Pid, Process, ProcessState, Processes, Scheduler, SchedulerError, SchedulingDecision,
+    StopReason, Syscall, SyscallResult,
+};
+use alloc::{format, string::String, vec::Vec};
+use alloc::collections::VecDeque;
+use core::num::NonZeroUsize;
+
+/// The lowest priority a process can have, lower values are clamped to it.
+const MIN_PRIORITY: i8 = 0;
+
+/// The highest priority a process can have, higher values are clamped to it.
+const MAX_PRIORITY: i8 = 5;
+
+pub struct PriorityRoundRobinProcess {
+    pcb: ProcessControlBlock,
+    remaining: usize,
+    wake_time: usize,
+    /// Whether the process waits for an event only until `wake_time`.
+    has_timeout: bool,
+    /// The process that this process waits to exit.
+    waiting_for: Option<Pid>,
+    /// The time the process has spent in the ready queue since it last ran.
+    waited: usize,
+}
+
+impl PriorityRoundRobinProcess {
+    pub fn new(pid: Pid, priority: i8, quantum: NonZeroUsize) -> Self {
+        let priority = priority.clamp(MIN_PRIORITY, MAX_PRIORITY);
+        PriorityRoundRobinProcess {
+            pcb: ProcessControlBlock::new(pid, ProcessState::Ready, priority, (0, 0, 0)),
+            remaining: quantum.get(),
+            wake_time: 0,
+            has_timeout: false,
+            waiting_for: None,
+            waited: 0,
+        }
+    }
+
+    pub fn set_state(&mut self, new_state: ProcessState) {
+        self.pcb.set_state(new_state);
+    }
+
+    /// Whether the process waits until its `wake_time`, either sleeping
+    /// or waiting for an event with a timeout.
+    fn is_sleeping(&self) -> bool {
+        match self.state() {
+            ProcessState::Waiting { event: None } => self.waiting_for.is_none(),
+            ProcessState::Waiting { event: Some(_) } => self.has_timeout,
+            _ => false,
+        }
+    }
+
+    /// The priority used to pick the next process, raised by one for every
+    /// `aging` time units that the process has waited without running.
+    fn effective_priority(&self, aging: Option<NonZeroUsize>) -> i8 {
+        let bump = aging.map_or(0, |aging| self.waited / aging.get());
+        let bump = i8::try_from(bump).unwrap_or(i8::MAX);
+        self.priority().saturating_add(bump).min(MAX_PRIORITY)
+    }
+}
+
+impl Process for PriorityRoundRobinProcess {
+    fn pid(&self) -> Pid {
+        self.pcb.pid()
+    }
+
+    fn state(&self) -> ProcessState {
+        self.pcb.state()
+    }
+
+    fn timings(&self) -> (usize, usize, usize) {
+        self.pcb.timings()
+    }
+
+    fn priority(&self) -> i8 {
+        self.pcb.priority()
+    }
+
+    fn extra(&self) -> String {
+        format!("waited={}", self.waited)
+    }
+
+    fn parent(&self) -> Option<Pid> {
+        self.pcb.parent()
+    }
+}
+
+/// A round robin scheduler with priorities.
+///
+/// The ready process with the highest priority is dispatched next, processes
+/// with the same priority take turns in the order in which they have become
+/// ready. With aging, a process that waits in the ready queue gets a higher
+/// priority as time passes, until it runs, so that low priority processes
+/// do not starve. A running process that lowers its priority with
+/// [`Syscall::SetPriority`] below the one of a ready process gives up
+/// the processor right away.
+pub struct PriorityRoundRobinScheduler {
+    processes: Vec<PriorityRoundRobinProcess>,
+    running: Option<Pid>,
+    /// The ready processes, in the order in which they have become ready.
+    ready_queue: VecDeque<Pid>,
+    events: EventTable,
+    timeslice: NonZeroUsize,
+    minimum_remaining_timeslice: usize,
+    /// The time a process has to wait without running for its
+    /// priority to be raised by one, or [`None`] for no aging.
+    aging: Option<NonZeroUsize>,
+    nr_processes: usize,
+    accounting: AccountingClock,
+    /// Set when the process with PID 1 exits, any process left
+    /// after that makes the scheduler panic.
+    init_exited: bool,
+}
+
+impl PriorityRoundRobinScheduler {
+    pub fn new(timeslice: NonZeroUsize, minimum_remaining_timeslice: usize) -> Self {
+        Self {
+            processes: Vec::new(),
+            running: None,
+            ready_queue: VecDeque::new(),
+            events: EventTable::new(),
+            timeslice,
+            minimum_remaining_timeslice,
+            aging: None,
+            nr_processes: 0,
+            accounting: AccountingClock::new(),
+            init_exited: false,
+        }
+    }
+
+    /// Creates a scheduler that raises the priority of a ready process
+    /// by one for every `aging` time units it waits without running.
+    /// The raised priority drops back when the process runs.
+    pub fn with_aging(
+        timeslice: NonZeroUsize,
+        minimum_remaining_timeslice: usize,
+        aging: NonZeroUsize,
+    ) -> Self {
+        Self {
+            aging: Some(aging),
+            ..Self::new(timeslice, minimum_remaining_timeslice)
+        }
+    }
+
+    fn process_index(&self, pid: Pid) -> usize {
+        self.processes
+            .iter()
+            .position(|p| p.pid() == pid)
+            .expect("Process not found in the list")
+    }
+
+    /// Charges the running process for `consumed` time units and credits
+    /// the same amount of time to every other process. The ready processes
+    /// have waited for this time.
+    fn charge(&mut self, pid: Pid, consumed: usize, kind: ChargeKind) {
+        let processes = self.processes.iter_mut().map(|p| &mut p.pcb);
+        self.accounting.charge(pid, consumed, kind, processes);
+        for process in self.processes.iter_mut() {
+            if process.state() == ProcessState::Ready {
+                process.waited += consumed;
+            }
+        }
+    }
+
+    /// Takes the processor from the running process and puts it in `state`.
+    fn block(&mut self, state: ProcessState) -> Option<Pid> {
+        let pid = self.running.take()?;
+        let process_index = self.process_index(pid);
+        self.processes[process_index].set_state(state);
+        Some(pid)
+    }
+
+    /// Moves a process to the back of the ready queue, with
+    /// a new timeslice if `reset_quantum` is set.
+    fn arrive(&mut self, pid: Pid, reset_quantum: bool) {
+        let process_index = self.process_index(pid);
+        let process = &mut self.processes[process_index];
+        process.set_state(ProcessState::Ready);
+        process.waiting_for = None;
+        process.waited = 0;
+        if reset_quantum {
+            process.remaining = self.timeslice.get();
+        }
+        self.ready_queue.push_back(pid);
+    }
+
+    /// Moves the running process back to the ready queue.
+    fn preempt(&mut self) {
+        if let Some(pid) = self.block(ProcessState::Ready) {
+            self.arrive(pid, true);
+        }
+    }
+
+    /// Returns the processes whose sleep has ended, in the order
+    /// in which they wake up.
+    fn woken(&self) -> Vec<Pid> {
+        let mut woken = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping() && p.wake_time <= self.accounting.time())
+            .map(|p| (p.wake_time, p.pid()))
+            .collect::<Vec<_>>();
+        woken.sort();
+        woken.into_iter().map(|(_, pid)| pid).collect()
+    }
+
+    /// Moves the processes whose sleep has ended to the ready queue,
+    /// in the order in which they wake up.
+    fn wake_sleepers(&mut self) {
+        for pid in self.woken() {
+            // a wait for an event may have timed out
+            self.events.remove(pid);
+            self.arrive(pid, true);
+        }
+    }
+
+    /// Removes a process and wakes up the processes waiting for it to exit.
+    fn remove(&mut self, pid: Pid) {
+        if self.running == Some(pid) {
+            self.running = None;
+        }
+        self.ready_queue.retain(|&p| p != pid);
+        self.events.remove(pid);
+        self.processes.retain(|p| p.pid() != pid);
+        if pid == 1 {
+            self.init_exited = true;
+        }
+        let waiters = self
+            .processes
+            .iter()
+            .filter(|p| p.waiting_for == Some(pid))
+            .map(|p| p.pid())
+            .collect::<Vec<_>>();
+        for waiter in waiters {
+            self.arrive(waiter, true);
+        }
+    }
+
+    /// Takes the ready process with the highest effective priority out of
+    /// the ready queue, the one that has become ready first if there are
+    /// several.
+    fn pick(&mut self) -> Option<Pid> {
+        let position =
+            highest(self.ready_queue.iter().map(|&pid| {
+                self.processes[self.process_index(pid)].effective_priority(self.aging)
+            }))?;
+        self.ready_queue.remove(position)
+    }
+}
+
+/// Returns the position of the highest priority, the first one if there are several.
+fn highest(priorities: impl Iterator<Item = i8>) -> Option<usize> {
+    let mut best: Option<(usize, i8)> = None;
+    for (position, priority) in priorities.enumerate() {
+        if best.is_none_or(|(_, best_priority)| priority > best_priority) {
+            best = Some((position, priority));
+        }
+    }
+    best.map(|(position, _)| position)
+}
+
+impl Processes for PriorityRoundRobinScheduler {
+    fn processes(&self) -> impl Iterator<Item = &dyn Process> {
+        // the processes are kept sorted by PID
+        self.processes.iter().map(|p| p as &dyn Process)
+    }
+}
+
+impl Scheduler for PriorityRoundRobinScheduler {
+    fn next(&mut self) -> SchedulingDecision {
+        if self.init_exited && !self.processes.is_empty() {
+            return SchedulingDecision::Panic;
+        }
+        self.wake_sleepers();
+
+        if self.running.is_none() {
+            self.running = self.pick();
+        }
+        if let Some(pid) = self.running {
+            let process_index = self.process_index(pid);
+            let process = &mut self.processes[process_index];
+            process.set_state(ProcessState::Running);
+            // the raised priority drops back once the process runs
+            process.waited = 0;
+            return SchedulingDecision::Run {
+                pid,
+                timeslice: NonZeroUsize::new(process.remaining).unwrap_or(self.timeslice),
+            };
+        }
+
+        let wake_time = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping())
+            .map(|p| p.wake_time)
+            .min();
+        if let Some(wake_time) = wake_time {
+            let amount = wake_time.saturating_sub(self.accounting.time());
+            let processes = self.processes.iter_mut().map(|p| &mut p.pcb);
+            self.accounting.advance_sleep(amount, processes);
+            match NonZeroUsize::new(amount) {
+                Some(amount) => SchedulingDecision::Sleep(amount),
+                // the sleep has already ended, the process is ready right away
+                None => self.next(),
+            }
+        } else if self.processes.is_empty() {
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        if self.init_exited && !self.processes.is_empty() {
+            return SchedulingDecision::Panic;
+        }
+
+        if let Some(pid) = self.running {
+            let process = &self.processes[self.process_index(pid)];
+            return SchedulingDecision::Run {
+                pid,
+                timeslice: NonZeroUsize::new(process.remaining).unwrap_or(self.timeslice),
+            };
+        }
+
+        // the processes that wake up are queued after the ready ones,
+        // with a new timeslice and without aging
+        let woken = self.woken();
+        let priorities = self
+            .ready_queue
+            .iter()
+            .map(|&pid| self.processes[self.process_index(pid)].effective_priority(self.aging))
+            .chain(
+                woken
+                    .iter()
+                    .map(|&pid| self.processes[self.process_index(pid)].priority()),
+            );
+        if let Some(position) = highest(priorities) {
+            return match self.ready_queue.get(position) {
+                Some(&pid) => SchedulingDecision::Run {
+                    pid,
+                    timeslice: NonZeroUsize::new(self.processes[self.process_index(pid)].remaining)
+                        .unwrap_or(self.timeslice),
+                },
+                None => SchedulingDecision::Run {
+                    pid: woken[position - self.ready_queue.len()],
+                    timeslice: self.timeslice,
+                },
+            };
+        }
+
+        // every sleep that has ended is handled above
+        let wake_time = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping())
+            .map(|p| p.wake_time)
+            .min();
+        let time = self.accounting.time();
+        if let Some(amount) = wake_time.and_then(|t| NonZeroUsize::new(t - time)) {
+            SchedulingDecision::Sleep(amount)
+        } else if self.processes.is_empty() {
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        match reason {
+            StopReason::Syscall { syscall, remaining } => {
+                let running = self.running;
+                if let Some(pid) = running {
+                    let process_index = self.process_index(pid);
+                    let consumed = self.processes[process_index]
+                        .remaining
+                        .saturating_sub(remaining);
+                    self.charge(pid, consumed, ChargeKind::Syscall);
+                    // a process cannot have more time left than it has been granted
+                    let process = &mut self.processes[process_index];
+                    process.remaining = process.remaining.min(remaining);
+                } else if !matches!(syscall, Syscall::Fork(_)) {
+                    return SyscallResult::NoRunningProcess;
+                }
+                let result = match syscall {
+                    Syscall::Fork(priority) => {
+                        self.nr_processes += 1;
+                        let pid = Pid::new(self.nr_processes);
+                        self.processes.push(PriorityRoundRobinProcess::new(
+                            pid,
+                            priority,
+                            self.timeslice,
+                        ));
+                        self.ready_queue.push_back(pid);
+                        SyscallResult::Pid(pid)
+                    }
+                    Syscall::Sleep(amount) | Syscall::WaitTimeout { max: amount, .. }
+                        if self.accounting.time().checked_add(amount).is_none() =>
+                    {
+                        SyscallResult::Error(SchedulerError::InvalidSleepDuration(amount))
+                    }
+                    Syscall::Sleep(0) => {
+                        self.preempt();
+                        SyscallResult::Success
+                    }
+                    Syscall::Yield => {
+                        // the process keeps the time left from its timeslice
+                        if let Some(pid) = self.block(ProcessState::Ready) {
+                            self.arrive(pid, remaining == 0);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::Sleep(amount) => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: None }) {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].wake_time =
+                                self.accounting.time() + amount;
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::Wait(event) => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: Some(event) })
+                        {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].has_timeout = false;
+                            self.events.wait(pid, event);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitTimeout { event, max } => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: Some(event) })
+                        {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].wake_time = self.accounting.time() + max;
+                            self.processes[process_index].has_timeout = true;
+                            self.events.wait(pid, event);
+                        }
+                        SyscallResult::Success
+                    }
+                    // all the processes are in group 0
+                    Syscall::Signal(event) | Syscall::SignalGroup(0, event) => {
+                        for pid in self.events.signal(event) {
+                            self.arrive(pid, true);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::SetGroup(_) | Syscall::SignalGroup(..) => SyscallResult::Success,
+                    Syscall::SetPriority(priority) => {
+                        if let Some(pid) = running {
+                            let process_index = self.process_index(pid);
+                            let priority = priority.clamp(MIN_PRIORITY, MAX_PRIORITY);
+                            self.processes[process_index].pcb.set_priority(priority);
+                            // like after nice(), a process that is no longer the one
+                            // with the highest priority gives up the processor,
+                            // keeping the time left from its timeslice
+                            let outranked = self.ready_queue.iter().any(|&pid| {
+                                self.processes[self.process_index(pid)]
+                                    .effective_priority(self.aging)
+                                    > priority
+                            });
+                            if outranked {
+                                if let Some(pid) = self.block(ProcessState::Ready) {
+                                    self.arrive(pid, remaining == 0);
+                                }
+                            }
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(pid) if running == Some(pid) => {
+                        SyscallResult::Error(SchedulerError::WaitForSelf)
+                    }
+                    Syscall::WaitPid(pid) if self.processes.iter().any(|p| p.pid() == pid) => {
+                        if let Some(waiter) = self.block(ProcessState::Waiting { event: None }) {
+                            let process_index = self.process_index(waiter);
+                            self.processes[process_index].waiting_for = Some(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(pid) if pid.get() <= self.nr_processes => {
+                        // the process has already exited
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(_) => SyscallResult::NoSuchProcess,
+                    Syscall::Kill(pid) => {
+                        if self.processes.iter().any(|p| p.pid() == pid) {
+                            self.remove(pid);
+                            SyscallResult::Success
+                        } else {
+                            SyscallResult::NoSuchProcess
+                        }
+                    }
+                    Syscall::Exit | Syscall::ExitWith(_) => {
+                        if let Some(pid) = running {
+                            self.remove(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                };
+                // a process left with too little of its timeslice
+                // gives up the processor with a fresh one
+                if running.is_some()
+                    && self.running == running
+                    && (remaining == 0 || remaining < self.minimum_remaining_timeslice)
+                {
+                    self.preempt();
+                }
+                result
+            }
+            StopReason::Expired => {
+                if let Some(pid) = self.running {
+                    let process_index = self.process_index(pid);
+                    let remaining = self.processes[process_index].remaining;
+                    self.charge(pid, remaining, ChargeKind::Execution);
+                    self.preempt();
+                }
+                SyscallResult::Success
+            }
+        }
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.processes().collect()
+    }
+}
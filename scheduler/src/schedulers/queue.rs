@@ -0,0 +1,526 @@
+use crate::accounting::{AccountingClock, ChargeKind};
+use crate::events::EventTable;
+use crate::pcb::ProcessControlBlock;
+use crate::scheduler::{
+    Pid, Process, ProcessState, Processes, Scheduler, SchedulerError, SchedulingDecision,
+    StopReason, Syscall, SyscallResult,
+};
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::{string::String, vec::Vec};
+use core::num::NonZeroUsize;
+
+/// What a [`ReadyQueue`] is told about a process that it queues.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct QueueHints {
+    /// The priority of the process.
+    pub priority: i8,
+}
+
+/// What a [`ReadyQueue`] can look at when it picks the next process.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct QueueContext {
+    /// The time of the scheduler.
+    pub time: usize,
+}
+
+/// The policy of a [`QueueScheduler`], which decides in which order the
+/// ready processes run.
+///
+/// The process that runs is taken out of the queue. It is queued again
+/// when it gives up the processor without blocking, with
+/// [`ReadyQueue::push`] when it yields and with
+/// [`ReadyQueue::requeue_expired`] when its timeslice is over.
+pub trait ReadyQueue {
+    /// Queues a process that has been forked, woken up or that has yielded.
+    fn push(&mut self, pid: Pid, hints: QueueHints);
+
+    /// Takes out the process that runs next, or returns [`None`] if no
+    /// process is queued.
+    fn pop_next(&mut self, ctx: &QueueContext) -> Option<Pid>;
+
+    /// Removes a process that has been killed while it was queued.
+    fn remove(&mut self, pid: Pid);
+
+    /// Queues a process that has used its whole timeslice.
+    fn requeue_expired(&mut self, pid: Pid, hints: QueueHints);
+
+    /// Returns the number of queued processes.
+    fn len(&self) -> usize;
+
+    /// Returns whether no process is queued.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Runs the processes in the order in which they become ready, which
+/// makes a [`QueueScheduler`] a round robin scheduler.
+#[derive(Debug, Clone, Default)]
+pub struct FifoQueue {
+    queue: VecDeque<Pid>,
+}
+
+impl FifoQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ReadyQueue for FifoQueue {
+    fn push(&mut self, pid: Pid, _hints: QueueHints) {
+        self.queue.push_back(pid);
+    }
+
+    fn pop_next(&mut self, _ctx: &QueueContext) -> Option<Pid> {
+        self.queue.pop_front()
+    }
+
+    fn remove(&mut self, pid: Pid) {
+        self.queue.retain(|&p| p != pid);
+    }
+
+    fn requeue_expired(&mut self, pid: Pid, _hints: QueueHints) {
+        self.queue.push_back(pid);
+    }
+
+    fn len(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+/// Runs the process with the highest priority first, and the processes
+/// with the same priority in the order in which they become ready.
+#[derive(Debug, Clone, Default)]
+pub struct PriorityQueue {
+    /// The processes of every priority, in the order in which they run.
+    levels: BTreeMap<i8, VecDeque<Pid>>,
+}
+
+impl PriorityQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ReadyQueue for PriorityQueue {
+    fn push(&mut self, pid: Pid, hints: QueueHints) {
+        self.levels
+            .entry(hints.priority)
+            .or_default()
+            .push_back(pid);
+    }
+
+    fn pop_next(&mut self, _ctx: &QueueContext) -> Option<Pid> {
+        let mut level = self.levels.last_entry()?;
+        let pid = level.get_mut().pop_front();
+        if level.get().is_empty() {
+            level.remove();
+        }
+        pid
+    }
+
+    fn remove(&mut self, pid: Pid) {
+        self.levels.retain(|_, level| {
+            level.retain(|&p| p != pid);
+            !level.is_empty()
+        });
+    }
+
+    fn requeue_expired(&mut self, pid: Pid, hints: QueueHints) {
+        self.push(pid, hints);
+    }
+
+    fn len(&self) -> usize {
+        self.levels.values().map(VecDeque::len).sum()
+    }
+}
+
+#[derive(Clone)]
+struct QueueProcess {
+    pcb: ProcessControlBlock,
+    /// The time left from the timeslice of the process.
+    remaining: usize,
+    wake_time: usize,
+    /// The process that this process waits to exit.
+    waiting_for: Option<Pid>,
+}
+
+impl Process for QueueProcess {
+    fn pid(&self) -> Pid {
+        self.pcb.pid()
+    }
+
+    fn state(&self) -> ProcessState {
+        self.pcb.state()
+    }
+
+    fn timings(&self) -> (usize, usize, usize) {
+        self.pcb.timings()
+    }
+
+    fn priority(&self) -> i8 {
+        self.pcb.priority()
+    }
+
+    fn extra(&self) -> String {
+        self.pcb.extra()
+    }
+
+    fn parent(&self) -> Option<Pid> {
+        self.pcb.parent()
+    }
+}
+
+/// A scheduler that handles the system calls and the accounting the same
+/// way whatever its policy, and leaves the choice of the next process to
+/// run to its [`ReadyQueue`].
+///
+/// A process runs for a whole timeslice, unless it stops earlier. After
+/// a system call, it keeps running with what is left of its timeslice if
+/// that is at least the minimum remaining timeslice, otherwise it is
+/// queued again with a new timeslice. The processes that wake up from a
+/// sleep keep what is left of their timeslice under the same condition,
+/// those that stop waiting for an event and those that yield get a new one.
+///
+/// With a [`FifoQueue`], it schedules the processes like the
+/// [`RoundRobinScheduler`](crate::RoundRobinScheduler) does without its
+/// process groups, cores and statistics.
+#[derive(Clone)]
+pub struct QueueScheduler<Q: ReadyQueue> {
+    queue: Q,
+    processes: BTreeMap<Pid, QueueProcess>,
+    /// The process dispatched last, until it stops running.
+    running: Option<Pid>,
+    /// The processes that wait until their wake time, sleeping or waiting
+    /// for an event with a timeout, in the order in which they have
+    /// started waiting.
+    sleepers: VecDeque<Pid>,
+    events: EventTable,
+    timeslice: NonZeroUsize,
+    minimum_remaining_timeslice: usize,
+    nr_processes: usize,
+    accounting: AccountingClock,
+}
+
+impl<Q: ReadyQueue> QueueScheduler<Q> {
+    pub fn new(queue: Q, timeslice: NonZeroUsize, minimum_remaining_timeslice: usize) -> Self {
+        Self {
+            queue,
+            processes: BTreeMap::new(),
+            running: None,
+            sleepers: VecDeque::new(),
+            events: EventTable::new(),
+            timeslice,
+            minimum_remaining_timeslice,
+            nr_processes: 0,
+            accounting: AccountingClock::new(),
+        }
+    }
+
+    /// Returns the ready queue.
+    pub fn queue(&self) -> &Q {
+        &self.queue
+    }
+
+    fn process(&mut self, pid: Pid) -> &mut QueueProcess {
+        self.processes
+            .get_mut(&pid)
+            .expect("Process not found in the list")
+    }
+
+    fn hints(&self, pid: Pid) -> QueueHints {
+        QueueHints {
+            priority: self.processes[&pid].priority(),
+        }
+    }
+
+    fn context(&self) -> QueueContext {
+        QueueContext {
+            time: self.accounting.time(),
+        }
+    }
+
+    /// Charges the running process for the time it has used, with
+    /// `remaining` time units left from its timeslice, and advances the
+    /// clock for everybody else.
+    fn charge(&mut self, pid: Pid, remaining: usize, kind: ChargeKind) {
+        let consumed = self.processes[&pid].remaining.saturating_sub(remaining);
+        let processes = self.processes.values_mut().map(|p| &mut p.pcb);
+        self.accounting.charge(pid, consumed, kind, processes);
+        let process = self.process(pid);
+        // a process cannot have more time left than it has been granted
+        process.remaining = process.remaining.min(remaining);
+        self.wake_sleepers();
+    }
+
+    /// Queues a process that has used its timeslice, with a new one.
+    fn preempt(&mut self, pid: Pid) {
+        let timeslice = self.timeslice.get();
+        let process = self.process(pid);
+        process.remaining = timeslice;
+        process.pcb.set_state(ProcessState::Ready);
+        self.queue.requeue_expired(pid, self.hints(pid));
+    }
+
+    /// Queues a process that has stopped waiting, with a new timeslice.
+    fn wake(&mut self, pid: Pid) {
+        let timeslice = self.timeslice.get();
+        let process = self.process(pid);
+        process.remaining = timeslice;
+        process.waiting_for = None;
+        process.pcb.set_state(ProcessState::Ready);
+        self.queue.push(pid, self.hints(pid));
+    }
+
+    /// Wakes up the processes whose wake time has passed, in the order
+    /// of their wake times.
+    fn wake_sleepers(&mut self) {
+        let time = self.accounting.time();
+        let (mut woken, sleeping) = self
+            .sleepers
+            .iter()
+            .partition::<Vec<_>, _>(|pid| self.processes[pid].wake_time <= time);
+        self.sleepers = sleeping.into_iter().collect();
+        woken.sort_by_key(|pid| self.processes[pid].wake_time);
+        for pid in woken {
+            let process = &self.processes[&pid];
+            let sleeping = process.pcb.state() == ProcessState::Waiting { event: None };
+            let remaining = process.remaining;
+            let kept = remaining != 0 && remaining >= self.minimum_remaining_timeslice;
+            // a wait for an event may have timed out
+            self.events.remove(pid);
+            self.wake(pid);
+            if sleeping && kept {
+                self.process(pid).remaining = remaining;
+            }
+        }
+    }
+
+    /// Blocks the running process, until `wake_time` if there is one.
+    fn block(&mut self, pid: Pid, state: ProcessState, wake_time: Option<usize>) {
+        self.running = None;
+        let process = self.process(pid);
+        process.pcb.set_state(state);
+        if let Some(wake_time) = wake_time {
+            process.wake_time = wake_time;
+            self.sleepers.push_back(pid);
+        }
+    }
+
+    /// Removes a process, wakes up the processes waiting for it to exit
+    /// and gives its children to the process with PID 1.
+    fn remove(&mut self, pid: Pid) {
+        if self.running == Some(pid) {
+            self.running = None;
+        }
+        self.processes.remove(&pid);
+        self.queue.remove(pid);
+        self.sleepers.retain(|&p| p != pid);
+        self.events.remove(pid);
+        let waiters = self
+            .processes
+            .values()
+            .filter(|p| p.waiting_for == Some(pid))
+            .map(|p| p.pid())
+            .collect::<Vec<_>>();
+        for waiter in waiters {
+            self.wake(waiter);
+        }
+        let parent = (pid != 1).then(|| Pid::new(1));
+        for process in self.processes.values_mut() {
+            if process.parent() == Some(pid) {
+                process.pcb.set_parent(parent);
+            }
+        }
+    }
+
+    fn fork(&mut self, parent: Option<Pid>, priority: i8) -> SyscallResult {
+        if self.nr_processes == usize::MAX {
+            return SyscallResult::Error(SchedulerError::PidOverflow);
+        }
+        self.nr_processes += 1;
+        let pid = Pid::new(self.nr_processes);
+        let mut pcb = ProcessControlBlock::new(pid, ProcessState::Ready, priority, (0, 0, 0));
+        pcb.set_parent(parent);
+        pcb.set_created(self.accounting.time());
+        let process = QueueProcess {
+            pcb,
+            remaining: self.timeslice.get(),
+            wake_time: 0,
+            waiting_for: None,
+        };
+        self.processes.insert(pid, process);
+        self.queue.push(pid, self.hints(pid));
+        SyscallResult::Pid(pid)
+    }
+}
+
+impl<Q: ReadyQueue> Processes for QueueScheduler<Q> {
+    fn processes(&self) -> impl Iterator<Item = &dyn Process> {
+        self.processes.values().map(|p| p as &dyn Process)
+    }
+
+    fn get(&self, pid: Pid) -> Option<&dyn Process> {
+        self.processes.get(&pid).map(|p| p as &dyn Process)
+    }
+}
+
+impl<Q: ReadyQueue + Clone + Send> Scheduler for QueueScheduler<Q> {
+    fn next(&mut self) -> SchedulingDecision {
+        if !self.processes.is_empty() && !self.processes.contains_key(&Pid::new(1)) {
+            return SchedulingDecision::Panic;
+        }
+
+        let ctx = self.context();
+        if let Some(mut pid) = self.running.take().or_else(|| self.queue.pop_next(&ctx)) {
+            // a process left with too little of its timeslice is queued
+            // again with a new one, and the next one is dispatched
+            let remaining = self.processes[&pid].remaining;
+            if remaining == 0 || remaining < self.minimum_remaining_timeslice {
+                self.preempt(pid);
+                pid = self.queue.pop_next(&ctx).unwrap_or(pid);
+            }
+            let process = self.process(pid);
+            process.pcb.set_state(ProcessState::Running);
+            let timeslice = NonZeroUsize::new(process.remaining).unwrap_or(NonZeroUsize::MIN);
+            self.running = Some(pid);
+            return SchedulingDecision::Run { pid, timeslice };
+        }
+
+        let wake_time = self
+            .sleepers
+            .iter()
+            .map(|pid| self.processes[pid].wake_time)
+            .min();
+        if let Some(wake_time) = wake_time {
+            // the sleepers whose wake time has passed are already awake
+            let amount = wake_time - self.accounting.time();
+            let processes = self.processes.values_mut().map(|p| &mut p.pcb);
+            self.accounting.advance_sleep(amount, processes);
+            self.wake_sleepers();
+            match NonZeroUsize::new(amount) {
+                Some(amount) => SchedulingDecision::Sleep(amount),
+                None => self.next(),
+            }
+        } else if self.processes.is_empty() {
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    /// Decides on a copy of the scheduler, so the decision is the one of
+    /// [`Scheduler::next`] whatever the queue.
+    fn peek_next(&self) -> SchedulingDecision {
+        self.clone().next()
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        let StopReason::Syscall { syscall, remaining } = reason else {
+            if let Some(pid) = self.running.take() {
+                self.charge(pid, 0, ChargeKind::Execution);
+                self.preempt(pid);
+            }
+            return SyscallResult::Success;
+        };
+        let running = self.running;
+        match running {
+            Some(pid) => self.charge(pid, remaining, ChargeKind::Syscall),
+            None if !matches!(syscall, Syscall::Fork(_)) => {
+                return SyscallResult::NoRunningProcess;
+            }
+            None => {}
+        }
+        let result = match (syscall, running) {
+            (Syscall::Fork(priority), _) => self.fork(running, priority),
+            (Syscall::Sleep(amount) | Syscall::WaitTimeout { max: amount, .. }, _)
+                if self.accounting.time().checked_add(amount).is_none() =>
+            {
+                SyscallResult::Error(SchedulerError::InvalidSleepDuration(amount))
+            }
+            (Syscall::Sleep(0), Some(pid)) => {
+                // there is nothing to sleep, the process just gives up the processor
+                self.running = None;
+                self.preempt(pid);
+                SyscallResult::Success
+            }
+            (Syscall::Sleep(amount), Some(pid)) => {
+                let state = ProcessState::Waiting { event: None };
+                self.block(pid, state, Some(self.accounting.time() + amount));
+                SyscallResult::Success
+            }
+            (Syscall::Yield, Some(pid)) => {
+                self.running = None;
+                let timeslice = self.timeslice.get();
+                let process = self.process(pid);
+                process.pcb.set_state(ProcessState::Ready);
+                // like for round robin, the process gets a fresh quantum
+                process.remaining = timeslice;
+                self.queue.push(pid, self.hints(pid));
+                SyscallResult::Success
+            }
+            (Syscall::Wait(event), Some(pid)) => {
+                let state = ProcessState::Waiting { event: Some(event) };
+                self.block(pid, state, None);
+                self.events.wait(pid, event);
+                SyscallResult::Success
+            }
+            (Syscall::WaitTimeout { event, max }, Some(pid)) => {
+                let state = ProcessState::Waiting { event: Some(event) };
+                self.block(pid, state, Some(self.accounting.time() + max));
+                self.events.wait(pid, event);
+                SyscallResult::Success
+            }
+            // all the processes are in group 0
+            (Syscall::Signal(event) | Syscall::SignalGroup(0, event), _) => {
+                for pid in self.events.signal(event) {
+                    self.sleepers.retain(|&p| p != pid);
+                    self.wake(pid);
+                }
+                SyscallResult::Success
+            }
+            (Syscall::SetGroup(_) | Syscall::SignalGroup(..), _) => SyscallResult::Success,
+            (Syscall::SetPriority(priority), Some(pid)) => {
+                self.process(pid).pcb.set_priority(priority);
+                SyscallResult::Success
+            }
+            (Syscall::WaitPid(pid), Some(waiter)) if pid == waiter => {
+                SyscallResult::Error(SchedulerError::WaitForSelf)
+            }
+            (Syscall::WaitPid(pid), Some(waiter)) if self.processes.contains_key(&pid) => {
+                self.block(waiter, ProcessState::Waiting { event: None }, None);
+                self.process(waiter).waiting_for = Some(pid);
+                SyscallResult::Success
+            }
+            (Syscall::WaitPid(pid), _) if pid.get() <= self.nr_processes => {
+                // the process has already exited
+                SyscallResult::Success
+            }
+            (Syscall::WaitPid(_), _) => SyscallResult::NoSuchProcess,
+            (Syscall::Kill(pid), _) if !self.processes.contains_key(&pid) => {
+                SyscallResult::NoSuchProcess
+            }
+            (Syscall::Kill(pid), _) => {
+                self.remove(pid);
+                SyscallResult::Success
+            }
+            (Syscall::Exit | Syscall::ExitWith(_), Some(pid)) => {
+                self.remove(pid);
+                SyscallResult::Success
+            }
+            // only a fork can be issued while no process runs
+            (_, None) => SyscallResult::NoRunningProcess,
+        };
+        // the system call has used the last time unit of the timeslice,
+        // so a process that keeps running is preempted like on expiry
+        if let Some(pid) = running.filter(|_| remaining == 0 && self.running == running) {
+            self.running = None;
+            self.preempt(pid);
+        }
+        result
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.processes().collect()
+    }
+}
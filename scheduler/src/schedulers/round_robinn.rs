@@ -1,300 +1,1900 @@
+#[cfg(feature = "std")]
+use crate::events::EventBus;
+use crate::accounting::{AccountingClock, ChargeKind};
+use crate::events::EventTable;
+use crate::pcb::ProcessControlBlock;
+use crate::stats::{SimulationStats, StatsRecorder};
 use crate::scheduler::{
-    Process, ProcessState, Pid, Scheduler, SchedulingDecision, StopReason, Syscall, SyscallResult,
+    AdoptSpec, AdoptState, FailReason, Process, ProcessState, Processes, Pid, Scheduler,
+    SchedulerError, SchedulerEvent, SchedulerStats, SchedulingDecision, SignalPolicy, StopReason,
+    Syscall, SyscallResult, TargetPolicy, Trace,
 };
-use std::{num::NonZeroUsize, collections::VecDeque};
+use alloc::{format, string::{String, ToString}, sync::Arc, vec, vec::Vec};
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::{fmt::Display, iter, num::NonZeroUsize};
 
+/// The number of exited processes whose exit codes are kept.
+const EXIT_CODES: usize = 64;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RoundRobinProcess {
-    pid: Pid,
-    state: ProcessState,
-    priority: i8,
-    timings: (usize, usize, usize),
+    /// Saved next to the other fields with the `serde` feature, as they
+    /// were before it has been shared. The first of its timings is only
+    /// counted up to the clock reading of the process.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pcb: ProcessControlBlock,
+    /// The time left from the quantum of the process, not the time
+    /// that the process still needs, which the scheduler does not know.
     remaining: usize,
-    sleep_time: usize,
-    total_time: usize,
+    wake_time: usize,
+    quantum: NonZeroUsize,
+    /// The quantum given to the process by [`RoundRobinScheduler::add_process`],
+    /// the other processes get the timeslice of the scheduler.
+    #[cfg_attr(feature = "serde", serde(default))]
+    burst: Option<NonZeroUsize>,
+    /// The process that this process waits to exit.
+    waiting_for: Option<Pid>,
+    group: usize,
+    /// The clock of the table of the process, with its reading up to which
+    /// the first of the timings is counted, see [`RoundRobinProcess::total`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    clock: Option<(Clock, usize)>,
 }
 
 impl RoundRobinProcess {
-    pub fn new(pid: Pid, state: ProcessState, priority: i8, timings: (usize, usize, usize), remaining: usize) -> Self {
+    pub fn new(pid: Pid, state: ProcessState, priority: i8, timings: (usize, usize, usize), quantum: NonZeroUsize) -> Self {
         RoundRobinProcess {
-            pid,
-            state,
-            priority,
-            timings,
-            remaining,
-            sleep_time: 0,
-            total_time: remaining,
+            pcb: ProcessControlBlock::new(pid, state, priority, timings),
+            remaining: quantum.get(),
+            wake_time: 0,
+            quantum,
+            burst: None,
+            waiting_for: None,
+            group: 0,
+            clock: None,
         }
     }
     pub fn set_state(&mut self, new_state: ProcessState) {
-        self.state = new_state;
+        self.pcb.set_state(new_state);
+    }
+
+    /// Gives the process its full quantum for the next time it is scheduled,
+    /// see [`RoundRobinProcess::fresh_quantum`].
+    pub fn reset_quantum(
+        &mut self,
+        timeslice: NonZeroUsize,
+        priority_timeslices: &[NonZeroUsize],
+    ) {
+        self.quantum = self.fresh_quantum(timeslice, priority_timeslices);
+        self.remaining = self.quantum.get();
+    }
+
+    /// The time credited by the clock of the process since it has last
+    /// been counted.
+    fn uncounted(&self) -> usize {
+        match &self.clock {
+            Some((clock, counted)) => clock.now() - counted,
+            None => 0,
+        }
+    }
+
+    /// The time during which the process has existed, including the time
+    /// credited by its clock since it has last been counted.
+    fn total(&self) -> usize {
+        self.pcb.timings().0.saturating_add(self.uncounted())
+    }
+
+    /// Counts the time credited by the clock of the process so far in the
+    /// first of the timings, and credits the process with `clock` from now
+    /// on, or with no clock once it has left its table.
+    fn follow(&mut self, clock: Option<&Clock>) {
+        self.pcb.charge_waiting(self.uncounted());
+        self.clock = clock.map(|clock| (clock.clone(), clock.now()));
+    }
+
+    /// The quantum that the process gets when its quantum is reset: its own
+    /// quantum if it has one, otherwise the timeslice of its priority in
+    /// `priority_timeslices`, or `timeslice` if that table is empty. The
+    /// priorities below 0 get the first timeslice of the table and those
+    /// past its end get the last one.
+    fn fresh_quantum(
+        &self,
+        timeslice: NonZeroUsize,
+        priority_timeslices: &[NonZeroUsize],
+    ) -> NonZeroUsize {
+        let index = usize::try_from(self.priority()).unwrap_or(0);
+        let by_priority = priority_timeslices
+            .get(index)
+            .or(priority_timeslices.last())
+            .copied();
+        self.burst.or(by_priority).unwrap_or(timeslice)
+    }
+
+    /// The timeslice granted when the process is scheduled, the time left
+    /// from its quantum, but never more than the quantum and never 0.
+    fn timeslice(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.remaining.min(self.quantum.get())).unwrap_or(self.quantum)
     }
 }
 
 impl Process for RoundRobinProcess {
     fn pid(&self) -> Pid {
-        self.pid
+        self.pcb.pid()
     }
 
     fn state(&self) -> ProcessState {
-        self.state
+        self.pcb.state()
     }
-    
 
     fn timings(&self) -> (usize, usize, usize) {
-        self.timings
+        let (_, syscalls, execution) = self.pcb.timings();
+        (self.total(), syscalls, execution)
     }
 
     fn priority(&self) -> i8 {
-        self.priority
+        self.pcb.priority()
     }
 
     fn extra(&self) -> String {
-        format!("")
+        format!("group={}", self.group)
+    }
+
+    fn parent(&self) -> Option<Pid> {
+        self.pcb.parent()
+    }
+}
+
+/// Counts the processes looked up or visited in a [`ProcessTable`], with
+/// the `testing` feature, so that tests can check how the work done by the
+/// scheduler grows with the number of processes.
+#[derive(Default)]
+struct Visits(#[cfg(feature = "testing")] AtomicUsize);
+
+impl Visits {
+    fn count(&self) {
+        #[cfg(feature = "testing")]
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Clone for Visits {
+    fn clone(&self) -> Self {
+        #[cfg(feature = "testing")]
+        return Visits(AtomicUsize::new(self.0.load(Ordering::Relaxed)));
+        #[cfg(not(feature = "testing"))]
+        Visits()
+    }
+}
+
+/// The time credited to every process of a [`ProcessTable`], shared by the
+/// table and its processes. The time is credited to all the processes at
+/// once, instead of to every one of them whenever the time advances, and
+/// every process can still tell how long it has existed.
+#[derive(Clone, Default)]
+struct Clock(Arc<AtomicUsize>);
+
+impl Clock {
+    fn now(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The processes of a [`RoundRobinScheduler`], kept in slots that do not
+/// move while the process exists, so that a process is found from its PID
+/// without going through the others. The slots of the processes that
+/// exit are reused by the next ones.
+///
+/// The children of every process and the processes waiting for every
+/// process to exit are indexed as well. With the `serde` feature, the
+/// table is saved as the list of its processes, sorted by PID, and the
+/// rest is rebuilt from them.
+#[derive(Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Vec<RoundRobinProcess>", into = "Vec<RoundRobinProcess>")
+)]
+struct ProcessTable {
+    slots: Vec<Option<RoundRobinProcess>>,
+    /// The slot of every process.
+    index: BTreeMap<Pid, usize>,
+    /// The empty slots, the last one is reused first.
+    free: Vec<usize>,
+    /// The children of the processes that have some.
+    children: BTreeMap<Pid, BTreeSet<Pid>>,
+    /// The processes waiting for a process to exit, by the process they wait for.
+    waiting_for: BTreeMap<Pid, BTreeSet<Pid>>,
+    clock: Clock,
+    visits: Visits,
+}
+
+impl Clone for ProcessTable {
+    /// The processes of the copy are credited by a clock of its own.
+    fn clone(&self) -> Self {
+        let clock = Clock::default();
+        let mut slots = self.slots.clone();
+        for process in slots.iter_mut().flatten() {
+            process.follow(Some(&clock));
+        }
+        Self {
+            slots,
+            index: self.index.clone(),
+            free: self.free.clone(),
+            children: self.children.clone(),
+            waiting_for: self.waiting_for.clone(),
+            clock,
+            visits: self.visits.clone(),
+        }
+    }
+}
+
+impl ProcessTable {
+    fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    fn get(&self, pid: Pid) -> Option<&RoundRobinProcess> {
+        self.visits.count();
+        self.slots[*self.index.get(&pid)?].as_ref()
+    }
+
+    fn get_mut(&mut self, pid: Pid) -> Option<&mut RoundRobinProcess> {
+        self.visits.count();
+        self.slots[*self.index.get(&pid)?].as_mut()
+    }
+
+    fn contains(&self, pid: Pid) -> bool {
+        self.get(pid).is_some()
+    }
+
+    /// The processes, sorted by PID.
+    fn iter(&self) -> impl Iterator<Item = &RoundRobinProcess> + '_ {
+        self.index
+            .values()
+            .filter_map(|&slot| self.slots[slot].as_ref())
+            .inspect(|_| self.visits.count())
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut RoundRobinProcess> + '_ {
+        let visits = &self.visits;
+        self.slots.iter_mut().flatten().inspect(move |_| visits.count())
+    }
+
+    /// Adds a process, in place of the process with the same PID if there
+    /// is one. The process is credited with time from now on.
+    fn insert(&mut self, mut process: RoundRobinProcess) {
+        let pid = process.pid();
+        process.follow(Some(&self.clock));
+        self.remove(pid);
+        if let Some(parent) = process.parent() {
+            self.children.entry(parent).or_default().insert(pid);
+        }
+        if let Some(waited) = process.waiting_for {
+            self.waiting_for.entry(waited).or_default().insert(pid);
+        }
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot] = Some(process);
+                slot
+            }
+            None => {
+                self.slots.push(Some(process));
+                self.slots.len() - 1
+            }
+        };
+        self.index.insert(pid, slot);
+    }
+
+    /// Removes a process and frees its slot. Its children and the processes
+    /// waiting for it stay indexed until they are given another parent or
+    /// stop waiting.
+    fn remove(&mut self, pid: Pid) -> Option<RoundRobinProcess> {
+        let slot = self.index.remove(&pid)?;
+        let mut process = self.slots[slot].take()?;
+        process.follow(None);
+        self.free.push(slot);
+        if let Some(parent) = process.parent() {
+            unlink(&mut self.children, parent, pid);
+        }
+        if let Some(waited) = process.waiting_for {
+            unlink(&mut self.waiting_for, waited, pid);
+        }
+        Some(process)
+    }
+
+    /// Credits `amount` time units to every process. Before the clock
+    /// overflows, which takes longer than the time of the scheduler can
+    /// count, the time credited so far is counted in every process and
+    /// the processes are credited by a new clock.
+    fn credit(&mut self, amount: usize) {
+        if self.clock.now().checked_add(amount).is_none() {
+            let clock = Clock::default();
+            // a process left out of the clock stays out of the new one
+            let credited = self.slots.iter_mut().flatten().filter(|p| p.clock.is_some());
+            for process in credited {
+                process.follow(Some(&clock));
+            }
+            self.clock = clock;
+        }
+        self.clock.0.fetch_add(amount, Ordering::Relaxed);
+    }
+
+    /// Credits `amount` time units to every process but `pid`, which is
+    /// left out of the clock meanwhile, and returns that process so that
+    /// it can be charged the time in some other way.
+    fn credit_others(&mut self, pid: Pid, amount: usize) -> Option<&mut RoundRobinProcess> {
+        self.visits.count();
+        let slot = *self.index.get(&pid)?;
+        self.slots[slot].as_mut()?.follow(None);
+        self.credit(amount);
+        let process = self.slots[slot].as_mut()?;
+        process.follow(Some(&self.clock));
+        Some(process)
+    }
+
+    /// The children of a process, sorted by PID.
+    fn children(&self, pid: Pid) -> Vec<Pid> {
+        self.children
+            .get(&pid)
+            .map_or_else(Vec::new, |children| children.iter().copied().collect())
+    }
+
+    /// The processes waiting for a process to exit, sorted by PID.
+    fn waiting_for(&self, pid: Pid) -> Vec<Pid> {
+        self.waiting_for
+            .get(&pid)
+            .map_or_else(Vec::new, |waiters| waiters.iter().copied().collect())
+    }
+
+    fn set_parent(&mut self, pid: Pid, parent: Option<Pid>) {
+        let Some(process) = self.get_mut(pid) else {
+            return;
+        };
+        let old = process.parent();
+        process.pcb.set_parent(parent);
+        if let Some(old) = old {
+            unlink(&mut self.children, old, pid);
+        }
+        if let Some(parent) = parent {
+            self.children.entry(parent).or_default().insert(pid);
+        }
+    }
+
+    fn set_waiting_for(&mut self, pid: Pid, waited: Option<Pid>) {
+        let Some(process) = self.get_mut(pid) else {
+            return;
+        };
+        let old = core::mem::replace(&mut process.waiting_for, waited);
+        if let Some(old) = old {
+            unlink(&mut self.waiting_for, old, pid);
+        }
+        if let Some(waited) = waited {
+            self.waiting_for.entry(waited).or_default().insert(pid);
+        }
+    }
+}
+
+/// Removes `pid` from the set of `key`, and the set once it is empty.
+fn unlink(map: &mut BTreeMap<Pid, BTreeSet<Pid>>, key: Pid, pid: Pid) {
+    if let Some(pids) = map.get_mut(&key) {
+        pids.remove(&pid);
+        if pids.is_empty() {
+            map.remove(&key);
+        }
+    }
+}
+
+impl core::ops::Index<Pid> for ProcessTable {
+    type Output = RoundRobinProcess;
+
+    fn index(&self, pid: Pid) -> &RoundRobinProcess {
+        self.get(pid).expect("Process not found in the list")
+    }
+}
+
+impl core::ops::IndexMut<Pid> for ProcessTable {
+    fn index_mut(&mut self, pid: Pid) -> &mut RoundRobinProcess {
+        self.get_mut(pid).expect("Process not found in the list")
+    }
+}
+
+impl From<Vec<RoundRobinProcess>> for ProcessTable {
+    fn from(processes: Vec<RoundRobinProcess>) -> Self {
+        let mut table = ProcessTable::default();
+        for process in processes {
+            table.insert(process);
+        }
+        table
+    }
+}
+
+impl From<ProcessTable> for Vec<RoundRobinProcess> {
+    fn from(table: ProcessTable) -> Self {
+        let mut processes = table.slots.into_iter().flatten().collect::<Vec<_>>();
+        // the saved timings are up to date
+        for process in processes.iter_mut() {
+            process.follow(None);
+        }
+        processes.sort_by_key(|p| p.pid());
+        processes
     }
 }
 
+/// What a [`RoundRobinScheduler`] has done during its warm-up, which is
+/// left out of its statistics.
+#[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Warmup {
+    /// The number of time units that the warm-up lasts.
+    length: usize,
+    idle_time: usize,
+    context_switches: usize,
+    completed: usize,
+    total_turnaround: usize,
+}
+
+/// A round robin scheduler.
+///
+/// With the `serde` feature, the scheduler can be saved and restored
+/// in the middle of a simulation, except for its trace callback.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RoundRobinScheduler {
-    processes: Vec<RoundRobinProcess>,
+    processes: ProcessTable,
     ready_queue: VecDeque<Pid>,
     sleep_queue: VecDeque<Pid>,
     timeslice: NonZeroUsize,
+    /// The timeslice of every priority, from priority 0, used instead of
+    /// the timeslice of the scheduler if it is not empty.
+    #[cfg_attr(feature = "serde", serde(default))]
+    priority_timeslices: Vec<NonZeroUsize>,
     minimum_remaining_timeslice: usize,
     nr_processes: usize,
-    time: usize,
+    /// The time, split into the time during which a process has been
+    /// running and the time during which none has.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    accounting: AccountingClock,
+    /// Set when the process with PID 1 exits, any process left
+    /// after that makes the scheduler panic.
+    init_exited: bool,
+    /// The exit codes of the processes that have exited most recently.
+    exit_codes: VecDeque<(Pid, i32)>,
+    /// The process dispatched most recently.
+    last_dispatched: Option<Pid>,
+    context_switches: usize,
+    /// The number of processes that have exited.
+    completed: usize,
+    /// The sum of the turnaround times of the processes that have exited.
+    total_turnaround: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    warmup: Warmup,
+    /// The statistics of every process, kept after it has exited.
+    recorder: StatsRecorder,
+    /// When a process exits, its children are killed instead
+    /// of being adopted by the process with PID 1.
+    kill_orphans: bool,
+    /// The members of each process group.
+    groups: BTreeMap<usize, BTreeSet<Pid>>,
+    /// The processes waiting for each event, in the order in which they
+    /// have started waiting.
+    events: EventTable,
+    signal_policy: SignalPolicy,
+    /// Which processes a process may kill.
+    target_policy: TargetPolicy,
+    /// A signal that finds no waiting process is kept for the next wait
+    /// with [`SignalPolicy::WakeAll`], instead of being dropped.
+    level_triggered: bool,
+    /// The signals of each event that have not woken up any process yet,
+    /// used by [`SignalPolicy::WakeOne`] and by level triggered signals.
+    pending_signals: BTreeMap<usize, usize>,
+    /// The processes running on each core, when the scheduler is driven
+    /// through [`RoundRobinScheduler::next_on`] and
+    /// [`RoundRobinScheduler::stop_on`]. They are kept outside of the
+    /// ready queue while they run.
+    cores: Vec<Option<Pid>>,
+    /// The time at which the process running on each core has been
+    /// dispatched or has last stopped.
+    core_times: Vec<usize>,
+    /// The part of the time used by the process that is being charged
+    /// that has already been counted while the other cores were running.
+    overlap: usize,
+    /// The number of processes after which forks fail, or [`None`] for no limit.
+    max_processes: Option<usize>,
+    /// The shortest timeslice when the timeslice shrinks with the number
+    /// of ready processes, or [`None`] for a fixed timeslice.
+    minimum_timeslice: Option<NonZeroUsize>,
+    /// The bus that carries the signals to and from other schedulers,
+    /// with the number of this scheduler on it.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    event_bus: Option<(EventBus, usize)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    trace: Option<Trace>,
+}
+
+/// The state of a [`RoundRobinScheduler`] at some point of a simulation,
+/// from which it can go on with [`RoundRobinScheduler::restore`].
+///
+/// With the `serde` feature, the snapshot can be saved. The timeslices are
+/// saved as plain numbers and restoring a timeslice of 0 fails, the maps
+/// from events and groups are saved with their numbers as keys, which are
+/// strings in JSON.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct SchedulerSnapshot {
+    scheduler: RoundRobinScheduler,
 }
 
 impl RoundRobinScheduler {
     pub fn new(timeslice: NonZeroUsize, minimum_remaining_timeslice: usize) -> Self {
         Self {
-            processes: Vec::new(),
+            processes: ProcessTable::default(),
             ready_queue: VecDeque::new(),
             sleep_queue: VecDeque::new(),
             timeslice,
+            priority_timeslices: Vec::new(),
             minimum_remaining_timeslice,
             nr_processes: 0,
-            time: 0,
+            accounting: AccountingClock::new(),
+            init_exited: false,
+            exit_codes: VecDeque::new(),
+            last_dispatched: None,
+            context_switches: 0,
+            completed: 0,
+            total_turnaround: 0,
+            warmup: Warmup::default(),
+            recorder: StatsRecorder::default(),
+            kill_orphans: false,
+            groups: BTreeMap::new(),
+            events: EventTable::new(),
+            signal_policy: SignalPolicy::default(),
+            target_policy: TargetPolicy::default(),
+            level_triggered: false,
+            pending_signals: BTreeMap::new(),
+            cores: vec![None],
+            core_times: vec![0],
+            overlap: 0,
+            max_processes: None,
+            minimum_timeslice: None,
+            #[cfg(feature = "std")]
+            event_bus: None,
+            trace: None,
+        }
+    }
+
+    /// Creates a scheduler whose timeslice shrinks as more processes are
+    /// ready, so that they all get to run sooner. The `base` timeslice is
+    /// split between the ready processes, but a process is never granted
+    /// less than `min`.
+    pub fn new_adaptive(base: NonZeroUsize, min: NonZeroUsize) -> Self {
+        Self {
+            minimum_timeslice: Some(min),
+            ..Self::new(base, 0)
+        }
+    }
+
+    /// Creates a scheduler that grants every process the timeslice of its
+    /// priority in `slices`, from priority 0, so that the processes with a
+    /// higher priority run longer at once. The processes with a negative
+    /// priority get the first timeslice and those with a priority past the
+    /// end of `slices` get the last one. A process whose priority changes
+    /// gets the timeslice of its new priority with its next full timeslice.
+    ///
+    /// A process keeps running after a system call with what is left of
+    /// its timeslice if that is at least `minimum_remaining_timeslice`, or
+    /// its whole timeslice if that is shorter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slices` is empty.
+    pub fn with_priority_slices(
+        slices: Vec<NonZeroUsize>,
+        minimum_remaining_timeslice: usize,
+    ) -> Self {
+        let timeslice = *slices.first().expect("At least one timeslice is needed");
+        Self {
+            priority_timeslices: slices,
+            ..Self::new(timeslice, minimum_remaining_timeslice)
         }
     }
-}
 
+    /// Returns whether a process left with `remaining` time units out of its
+    /// `quantum` has too little time left to keep running. The minimum
+    /// remaining timeslice is lowered to the quantum if it is larger.
+    fn too_little_left(&self, remaining: usize, quantum: NonZeroUsize) -> bool {
+        remaining == 0 || remaining < self.minimum_remaining_timeslice.min(quantum.get())
+    }
 
+    /// The time a process is granted at most when `ready` processes are
+    /// ready, including it, or [`None`] if the timeslice is fixed.
+    fn adaptive_timeslice(&self, ready: usize) -> Option<usize> {
+        let min = self.minimum_timeslice?;
+        Some((self.timeslice.get() / ready.max(1)).max(min.get()))
+    }
 
-impl Scheduler for RoundRobinScheduler {
-    fn next(&mut self) -> SchedulingDecision {
-        if self.ready_queue.len() > 0 {
-            let mut i = 0;
-            for pid in self.ready_queue.iter() {
-                if *pid == 1 {
-                    i += 1;
+    /// Creates a scheduler that kills the children of exited processes,
+    /// and their children, if `kill_orphans` is set, instead of giving
+    /// them to the process with PID 1.
+    pub fn with_kill_orphans(
+        timeslice: NonZeroUsize,
+        minimum_remaining_timeslice: usize,
+        kill_orphans: bool,
+    ) -> Self {
+        Self {
+            kill_orphans,
+            ..Self::new(timeslice, minimum_remaining_timeslice)
+        }
+    }
+
+    /// Creates a scheduler whose signals are level triggered if
+    /// `level_triggered` is set, see
+    /// [`RoundRobinScheduler::level_triggered_signals`].
+    pub fn with_level_triggered_signals(
+        timeslice: NonZeroUsize,
+        minimum_remaining_timeslice: usize,
+        level_triggered: bool,
+    ) -> Self {
+        Self {
+            level_triggered,
+            ..Self::new(timeslice, minimum_remaining_timeslice)
+        }
+    }
+
+    /// Creates a scheduler for `cores` processors, driven through
+    /// [`RoundRobinScheduler::next_on`] and [`RoundRobinScheduler::stop_on`].
+    ///
+    /// The cores run at the same time and report what their processes do
+    /// in the order in which the processes stop. A process that stops
+    /// advances the time only by the part of the time it has used that
+    /// is later than the processes that have stopped before it on the
+    /// other cores.
+    pub fn with_cores(
+        timeslice: NonZeroUsize,
+        minimum_remaining_timeslice: usize,
+        cores: NonZeroUsize,
+    ) -> Self {
+        Self {
+            cores: vec![None; cores.get()],
+            core_times: vec![0; cores.get()],
+            ..Self::new(timeslice, minimum_remaining_timeslice)
+        }
+    }
+
+    /// Returns what `core` has to do next, like [`Scheduler::next`] does
+    /// for a single processor, or [`None`] if the core has to stay idle
+    /// while the other cores run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no such core.
+    pub fn next_on(&mut self, core: usize) -> Option<SchedulingDecision> {
+        if let Some(pid) = self.cores[core] {
+            let timeslice = self.processes[pid].timeslice();
+            return Some(SchedulingDecision::Run { pid, timeslice });
+        }
+        let busy = self.cores.iter().any(Option::is_some);
+        self.receive_signals();
+        self.wake_sleepers();
+        if busy && self.ready_queue.is_empty() {
+            return None;
+        }
+        let decision = self.next();
+        if let SchedulingDecision::Run { pid, .. } = decision {
+            self.ready_queue.pop_front();
+            self.cores[core] = Some(pid);
+            self.core_times[core] = self.accounting.time();
+        }
+        Some(decision)
+    }
+
+    /// Informs the scheduler that the process running on `core` has
+    /// stopped, like [`Scheduler::stop`] does for a single processor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no such core.
+    pub fn stop_on(&mut self, core: usize, reason: StopReason) -> SyscallResult {
+        let Some(pid) = self.cores[core].take() else {
+            if self.processes.is_empty() {
+                // the first process is created without running anything
+                return self.stop(reason);
+            }
+            return SyscallResult::NoRunningProcess;
+        };
+        // the process is handled as the running process of a single processor,
+        // that has run since the time of its core
+        self.ready_queue.push_front(pid);
+        self.overlap = self.accounting.time().saturating_sub(self.core_times[core]);
+        let result = self.stop(reason);
+        self.overlap = 0;
+        self.core_times[core] = self.accounting.time();
+        let still_running = self.ready_queue.front() == Some(&pid)
+            && self.processes[pid].state() == ProcessState::Running;
+        if still_running {
+            let process = &self.processes[pid];
+            if self.too_little_left(process.remaining, process.quantum) {
+                self.preempt();
+            } else {
+                self.ready_queue.pop_front();
+                self.cores[core] = Some(pid);
+            }
+        }
+        result
+    }
+
+    /// Sets which processes a [`Syscall::Signal`] system call wakes up,
+    /// by default all the processes waiting for the event.
+    pub fn signal_policy(mut self, signal_policy: SignalPolicy) -> Self {
+        self.signal_policy = signal_policy;
+        self
+    }
+
+    /// Sets which processes a process may kill with a [`Syscall::Kill`]
+    /// system call, by default itself and its descendants, and any
+    /// process for the process with PID 1.
+    pub fn target_policy(mut self, target_policy: TargetPolicy) -> Self {
+        self.target_policy = target_policy;
+        self
+    }
+
+    /// Sets what happens to a [`Syscall::Signal`] system call that finds
+    /// no process waiting for the event, with [`SignalPolicy::WakeAll`].
+    ///
+    /// By default signals are edge triggered: such a signal succeeds but
+    /// is dropped, and a process that waits for the event later waits for
+    /// the next signal. If `level_triggered` is set, the event stays
+    /// signaled and the next [`Syscall::Wait`] or [`Syscall::WaitTimeout`]
+    /// system call for it returns right away, clearing it. Several signals
+    /// before that wait count as one.
+    ///
+    /// With [`SignalPolicy::WakeOne`] the signals are always kept and
+    /// counted, like the posts of a semaphore. Signals to a group and
+    /// signals received through an event bus are never kept.
+    pub fn level_triggered_signals(mut self, level_triggered: bool) -> Self {
+        self.level_triggered = level_triggered;
+        self
+    }
+
+    /// Subscribes the scheduler to an event bus shared with other
+    /// schedulers. A [`Syscall::Signal`] system call is then also sent to
+    /// the other schedulers, and the signals they send wake up the
+    /// processes waiting here, according to the signal policy. A signal
+    /// received from another scheduler that finds no waiting process is
+    /// not kept, even with [`SignalPolicy::WakeOne`].
+    #[cfg(feature = "std")]
+    pub fn with_event_bus(mut self, bus: EventBus) -> Self {
+        let subscriber = bus.subscribe();
+        self.event_bus = Some((bus, subscriber));
+        self
+    }
+
+    /// Limits the number of processes, a [`Syscall::Fork`] system call
+    /// fails with [`SyscallResult::MaxProcessesReached`] when there are
+    /// already `max_processes` processes. By default there is no limit.
+    pub fn max_processes(mut self, max_processes: usize) -> Self {
+        self.max_processes = Some(max_processes);
+        self
+    }
+
+    /// Kills the children of exited processes, and their children, instead
+    /// of giving them to the process with PID 1, like
+    /// [`RoundRobinScheduler::with_kill_orphans`].
+    pub fn kill_orphans(mut self, kill_orphans: bool) -> Self {
+        self.kill_orphans = kill_orphans;
+        self
+    }
+
+    /// Returns the process at the front of the ready queue, first dropping
+    /// the PIDs in front of it whose process no longer exists, so that
+    /// a queue out of step with the processes does not make it panic.
+    fn ready_front(&mut self) -> Option<Pid> {
+        while let Some(&pid) = self.ready_queue.front() {
+            if self.processes.contains(pid) {
+                return Some(pid);
+            }
+            self.ready_queue.pop_front();
+        }
+        None
+    }
+
+    /// Reports `event` to the trace callback, if there is one.
+    fn emit(&mut self, event: SchedulerEvent) {
+        if let Some(trace) = self.trace.as_mut() {
+            trace(&event);
+        }
+    }
+
+    /// Checks that the process `pid` may act on the process `target`,
+    /// according to the target policy. Returns the result of a system call
+    /// that it is not allowed to issue, after reporting it to the trace,
+    /// or [`None`] if it is allowed or if `target` does not exist.
+    fn deny_target(&mut self, pid: Pid, target: Pid) -> Option<SyscallResult> {
+        if !self.processes.contains(target) {
+            return None;
+        }
+        let processes = &self.processes;
+        let parent = |p| processes.get(p).and_then(|p| p.parent());
+        if self.target_policy.allows(pid, target, parent) {
+            return None;
+        }
+        let time = self.accounting.time();
+        self.emit(SchedulerEvent::PermissionDenied { pid, target, time });
+        Some(SyscallResult::Error(SchedulerError::PermissionDenied(target)))
+    }
+
+    /// Charges the running process, the one at the front of the ready queue,
+    /// for `consumed` time units and credits the same amount of time to every
+    /// other process, as they have been waiting meanwhile, see
+    /// [`ProcessTable::credit_others`].
+    fn charge(&mut self, consumed: usize, kind: ChargeKind) {
+        let Some(&pid) = self.ready_queue.front() else {
+            return;
+        };
+        self.recorder.run(pid, consumed, kind == ChargeKind::Syscall);
+        let elapsed = consumed.saturating_sub(self.overlap);
+        if let Some(process) = self.processes.credit_others(pid, elapsed) {
+            let pcb = [&mut process.pcb];
+            self.accounting.charge_parallel(pid, consumed, elapsed, kind, pcb);
+        }
+        // processes that woke up meanwhile are ahead of the running process
+        self.wake_sleepers();
+    }
+
+    /// Charges the running process, if there is one, for the time it has
+    /// used until it has issued a system call with `remaining` time units
+    /// left from its timeslice, and returns its PID.
+    fn charge_syscall(&mut self, remaining: usize) -> Option<Pid> {
+        let running = self.ready_front();
+        if let Some(pid) = running {
+            let consumed = self.processes[pid].remaining.saturating_sub(remaining);
+            self.charge(consumed, ChargeKind::Syscall);
+            // a process cannot have more time left than it has been granted
+            let process = &mut self.processes[pid];
+            process.remaining = process.remaining.min(remaining);
+        }
+        running
+    }
+
+    /// Preempts the process that has issued a system call, if it keeps
+    /// running with no time left from its timeslice.
+    fn finish_syscall(&mut self, running: Option<Pid>, remaining: usize) {
+        // the system call has used the last time unit of the timeslice,
+        // so a process that keeps running is preempted like on expiry
+        if remaining == 0 && running.is_some() && self.ready_queue.front().copied() == running {
+            self.preempt();
+        }
+    }
+
+    /// Creates a ready process forked by `parent`, in its group, at the
+    /// back of the ready queue.
+    fn fork(&mut self, parent: Option<Pid>, priority: i8) -> SyscallResult {
+        if self.max_processes.is_some_and(|max| self.processes.len() >= max) {
+            return SyscallResult::MaxProcessesReached;
+        }
+        if self.nr_processes == usize::MAX {
+            return SyscallResult::Error(SchedulerError::PidOverflow);
+        }
+        let new_pid = Pid::new(self.nr_processes + 1);
+        self.nr_processes += 1;
+        let mut new_process = RoundRobinProcess::new(
+            new_pid,
+            ProcessState::Ready,
+            priority,
+            (0, 0, 0),
+            self.timeslice,
+        );
+        new_process.reset_quantum(self.timeslice, &self.priority_timeslices);
+        new_process.pcb.set_parent(parent);
+        new_process.pcb.set_created(self.accounting.time());
+        self.recorder.arrive(new_pid, self.accounting.time());
+        if let Some(parent) = parent {
+            new_process.group = self.processes[parent].group;
+        }
+        self.groups.entry(new_process.group).or_default().insert(new_pid);
+        self.processes.insert(new_process);
+        self.ready_queue.push_back(new_pid);
+        self.emit(SchedulerEvent::Fork { pid: new_pid, time: self.accounting.time() });
+        SyscallResult::Pid(new_pid)
+    }
+
+    /// Moves the running process, the one at the front of the ready queue,
+    /// to the back of the ready queue with a new timeslice.
+    fn preempt(&mut self) {
+        if let Some(pid) = self.ready_queue.pop_front() {
+            self.processes[pid].set_state(ProcessState::Ready);
+            self.processes[pid].reset_quantum(self.timeslice, &self.priority_timeslices);
+            self.ready_queue.push_back(pid);
+            self.emit(SchedulerEvent::Preempt { pid, time: self.accounting.time() });
+        }
+    }
+
+    /// Moves the processes whose sleep has ended to the back of the
+    /// ready queue, in the order in which they wake up. A sleeping process
+    /// keeps the time left from its timeslice when it has fallen asleep,
+    /// unless that is too little to be dispatched with.
+    fn wake_sleepers(&mut self) {
+        let mut woken = vec![];
+        let mut sleeping = VecDeque::new();
+        while let Some(pid) = self.sleep_queue.pop_front() {
+            let process = &self.processes[pid];
+            if process.wake_time <= self.accounting.time() {
+                // a wait for an event that has timed out gets a fresh
+                // quantum, like a wait that ends with the event
+                let waited = matches!(process.state(), ProcessState::Waiting { event: Some(_) });
+                let fresh = waited || self.too_little_left(process.remaining, process.quantum);
+                let process = &mut self.processes[pid];
+                if fresh {
+                    process.reset_quantum(self.timeslice, &self.priority_timeslices);
                 }
+                self.events.remove(pid);
+                process.set_state(ProcessState::Ready);
+                woken.push((process.wake_time, pid));
+            } else {
+                sleeping.push_back(pid);
             }
-            for pid in self.sleep_queue.iter() {
-                if *pid == 1 {
-                    i += 1;
+        }
+        self.sleep_queue = sleeping;
+        woken.sort_by_key(|(wake_time, _)| *wake_time);
+        for (_, pid) in woken {
+            self.ready_queue.push_back(pid);
+            self.emit(SchedulerEvent::Wake { pid, time: self.accounting.time() });
+        }
+    }
+
+    /// Removes a process from the scheduler, wherever it is queued,
+    /// wakes up the processes waiting for it to exit and handles
+    /// its orphaned children.
+    fn remove(&mut self, pid: Pid) {
+        for core in self.cores.iter_mut().filter(|core| **core == Some(pid)) {
+            *core = None;
+        }
+        let Some(process) = self.processes.remove(pid) else {
+            return;
+        };
+        self.leave_group(pid, process.group);
+        self.events.remove(pid);
+        // the running process is at the front, it is the one that exits most often
+        if self.ready_queue.front() == Some(&pid) {
+            self.ready_queue.pop_front();
+        } else {
+            self.ready_queue.retain(|&p| p != pid);
+        }
+        self.sleep_queue.retain(|&p| p != pid);
+        if pid == 1 {
+            self.init_exited = true;
+        }
+        self.recorder.leave(pid, self.accounting.time());
+        self.emit(SchedulerEvent::Exit { pid, time: self.accounting.time() });
+
+        for waiter in self.processes.waiting_for(pid) {
+            self.processes.set_waiting_for(waiter, None);
+            let process = &mut self.processes[waiter];
+            process.reset_quantum(self.timeslice, &self.priority_timeslices);
+            process.set_state(ProcessState::Ready);
+            self.ready_queue.push_back(waiter);
+            self.emit(SchedulerEvent::Wake { pid: waiter, time: self.accounting.time() });
+        }
+
+        for orphan in self.processes.children(pid) {
+            if self.kill_orphans {
+                self.remove(orphan);
+            } else {
+                self.processes.set_parent(orphan, (pid != 1).then(|| Pid::new(1)));
+            }
+        }
+    }
+
+    /// Changes the timeslice of the scheduler. The processes get the new
+    /// timeslice the next time they get a full one, except those added
+    /// with their own quantum by [`RoundRobinScheduler::add_process`]
+    /// and, with [`RoundRobinScheduler::with_priority_slices`], those that
+    /// get the timeslice of their priority.
+    /// The processes that have not started their timeslice yet get the new
+    /// one right away, those that have keep what is left of theirs.
+    ///
+    /// The minimum remaining timeslice is lowered to the new timeslice if
+    /// it is larger.
+    pub fn set_timeslice(&mut self, timeslice: NonZeroUsize) {
+        self.timeslice = timeslice;
+        self.minimum_remaining_timeslice = self.minimum_remaining_timeslice.min(timeslice.get());
+        for process in self.processes.iter_mut() {
+            let untouched = process.remaining == process.quantum.get()
+                && process.state() != ProcessState::Running;
+            if process.burst.is_none() && untouched {
+                process.reset_quantum(timeslice, &self.priority_timeslices);
+            }
+        }
+    }
+
+    /// Leaves the first `warmup` time units out of the statistics, so that
+    /// they show how the scheduler behaves once it has settled. The
+    /// processes are scheduled as usual during the warm-up, but the idle
+    /// time and the context switches until then, and the processes that
+    /// exit until then, are not counted by
+    /// [`RoundRobinScheduler::statistics`]. Its total time starts at the
+    /// end of the warm-up.
+    ///
+    /// Only what happens after the call is left out, so the warm-up is
+    /// meant to be set before the scheduler runs.
+    pub fn set_warmup(&mut self, warmup: usize) {
+        self.warmup.length = warmup;
+    }
+
+    /// Whether the time is still in the warm-up, see
+    /// [`RoundRobinScheduler::set_warmup`].
+    fn warming_up(&self) -> bool {
+        self.accounting.time() < self.warmup.length
+    }
+
+    /// Returns the execution time of a process, the same value as the last
+    /// element of its [`Process::timings`], or [`None`] if there is no
+    /// process with this PID, or if it has already exited.
+    pub fn cpu_time(&self, pid: Pid) -> Option<usize> {
+        self.processes.get(pid).map(|p| p.timings().2)
+    }
+
+    /// Returns the process group of a process, set by [`Syscall::SetGroup`]
+    /// and inherited from its parent when it is forked, 0 for the first
+    /// process, or [`None`] if there is no process with this PID.
+    pub fn group(&self, pid: Pid) -> Option<usize> {
+        self.processes.get(pid).map(|p| p.group)
+    }
+
+    /// Returns how many times a process has been looked up or visited in
+    /// the process table, and how many slots the table has, used or not.
+    /// This is meant for tests that check that the work done for every
+    /// system call does not grow with the number of processes.
+    #[cfg(feature = "testing")]
+    pub fn table_usage(&self) -> (usize, usize) {
+        (
+            self.processes.visits.0.load(Ordering::Relaxed),
+            self.processes.slots.len(),
+        )
+    }
+
+    /// Returns the processes that wait for `event`, in the order in which
+    /// a signal wakes them up, which is the order in which they have
+    /// started waiting. The list is empty if no process waits for it.
+    pub fn waiters(&self, event: usize) -> Vec<Pid> {
+        self.events.waiters(event).collect()
+    }
+
+    /// Removes a process from the members of `group`.
+    fn leave_group(&mut self, pid: Pid, group: usize) {
+        if let Some(members) = self.groups.get_mut(&group) {
+            members.remove(&pid);
+            if members.is_empty() {
+                self.groups.remove(&group);
+            }
+        }
+    }
+
+    /// Makes the running process wait for `event`, for at most `timeout`
+    /// time units if there is a timeout.
+    fn wait(&mut self, event: usize, timeout: Option<usize>) {
+        if let Some(pending) = self.pending_signals.get_mut(&event) {
+            // the event has already been signaled
+            *pending -= 1;
+            if *pending == 0 {
+                self.pending_signals.remove(&event);
+            }
+            return;
+        }
+        if let Some(pid) = self.ready_queue.pop_front() {
+            self.processes[pid].set_state(ProcessState::Waiting { event: Some(event) });
+            self.events.wait(pid, event);
+            // the process also sleeps in the sleep queue until the timeout
+            if let Some(timeout) = timeout {
+                self.processes[pid].wake_time = self.accounting.time() + timeout;
+                self.sleep_queue.push_back(pid);
+            }
+            self.emit(SchedulerEvent::Block { pid, time: self.accounting.time() });
+        }
+    }
+
+    /// Wakes up the processes waiting for `event`, or only those
+    /// that belong to `group` if there is one, according to the
+    /// signal policy.
+    ///
+    /// With [`SignalPolicy::WakeOne`], a signal to all the groups that
+    /// finds no waiting process is kept for the next wait. With
+    /// [`SignalPolicy::WakeAll`], it is kept only if the signals are
+    /// level triggered, and only once.
+    fn signal(&mut self, event: usize, group: Option<usize>) {
+        let mut woken = self
+            .events
+            .waiters(event)
+            .filter(|pid| {
+                group.is_none_or(|group| {
+                    self.groups
+                        .get(&group)
+                        .is_some_and(|members| members.contains(pid))
+                })
+            })
+            .collect::<Vec<_>>();
+        let lost = woken.is_empty() && group.is_none();
+        match self.signal_policy {
+            SignalPolicy::WakeOne => {
+                woken.truncate(1);
+                if lost {
+                    *self.pending_signals.entry(event).or_default() += 1;
                 }
             }
-            if i == 0 {
-                for process in self.processes.iter_mut() {
-                    process.set_state(ProcessState::Ready);
+            SignalPolicy::WakeAll => {
+                if lost && self.level_triggered {
+                    self.pending_signals.insert(event, 1);
                 }
-                return SchedulingDecision::Panic;
             }
         }
-        if let Some(pid) = self.ready_queue.pop_front() {
-            self.ready_queue.push_front(pid);
-            let process_index = self
-                .processes
-                .iter()
-                .position(|p| p.pid() == pid)
-                .expect("Process not found in the list");
-            if self.processes[process_index].sleep_time > 0 {
-                self.processes[process_index].remaining = self.processes[process_index].total_time;
-                self.processes[process_index].timings.0 += self.processes[process_index].sleep_time;
-                self.processes[process_index].sleep_time = 0;
-            }
-            if let Some(remaining) = NonZeroUsize::new(self.processes[process_index].remaining) {
-                if remaining.get() >= self.minimum_remaining_timeslice{
-                    self.processes[process_index].set_state(ProcessState::Running);
-                    return SchedulingDecision::Run { pid:pid, timeslice: remaining };
-                } else {
-                    self.ready_queue.push_back(pid);
-                    self.processes[process_index].set_state(ProcessState::Ready);
+        for pid in woken {
+            self.wake_waiter(pid);
+        }
+    }
+
+    /// Moves a process that waits for an event to the back of the ready queue.
+    fn wake_waiter(&mut self, pid: Pid) {
+        self.events.remove(pid);
+        self.processes[pid].reset_quantum(self.timeslice, &self.priority_timeslices);
+        self.processes[pid].set_state(ProcessState::Ready);
+        self.sleep_queue.retain(|&p| p != pid);
+        self.ready_queue.push_back(pid);
+        self.emit(SchedulerEvent::Wake { pid, time: self.accounting.time() });
+    }
+
+    /// Returns the processes woken up by `events` received from the event
+    /// bus, in the order in which they are woken up.
+    fn bus_woken(&self, events: &[usize]) -> Vec<Pid> {
+        let mut woken = vec![];
+        for &event in events {
+            let waiters = self
+                .events
+                .waiters(event)
+                .filter(|pid| !woken.contains(pid))
+                .collect::<Vec<_>>();
+            match self.signal_policy {
+                SignalPolicy::WakeAll => woken.extend(waiters),
+                SignalPolicy::WakeOne => woken.extend(waiters.first()),
+            }
+        }
+        woken
+    }
+
+    /// Wakes up the processes signaled by other schedulers through the event bus.
+    fn receive_signals(&mut self) {
+        for pid in self.bus_woken(&self.received(true)) {
+            self.wake_waiter(pid);
+        }
+    }
+
+    /// Returns the events received from the event bus, removing them if `take`.
+    #[cfg(feature = "std")]
+    fn received(&self, take: bool) -> Vec<usize> {
+        match &self.event_bus {
+            Some((bus, subscriber)) if take => bus.take(*subscriber),
+            Some((bus, subscriber)) => bus.pending(*subscriber),
+            None => vec![],
+        }
+    }
+
+    /// Without `std` there is no event bus, so no events are received.
+    #[cfg(not(feature = "std"))]
+    fn received(&self, _take: bool) -> Vec<usize> {
+        vec![]
+    }
+
+    /// Wakes up the processes of `group` that wait for `event`, according
+    /// to the signal policy, like a [`Syscall::SignalGroup`] does but without
+    /// a running process to charge for it. The woken processes are queued
+    /// in the order in which they have started waiting.
+    pub fn signal_group(&mut self, group: usize, event: usize) {
+        self.signal(event, Some(group));
+    }
+
+    /// Forks `count` processes with the priority `priority` in a single
+    /// system call of the running process, issued with `remaining` time
+    /// units left from its timeslice. The running process is charged once,
+    /// like for one [`Syscall::Fork`], instead of once for every child.
+    ///
+    /// The children get consecutive PIDs and are queued as ready in their
+    /// order. Returns their PIDs, fewer than `count` if a fork fails, like
+    /// when the limit set by [`RoundRobinScheduler::max_processes`] is
+    /// reached.
+    pub fn fork_many(&mut self, count: usize, priority: i8, remaining: usize) -> Vec<Pid> {
+        let parent = self.charge_syscall(remaining);
+        let pids = (0..count)
+            .map_while(|_| match self.fork(parent, priority) {
+                SyscallResult::Pid(pid) => Some(pid),
+                _ => None,
+            })
+            .collect();
+        self.finish_syscall(parent, remaining);
+        pids
+    }
+
+    /// Terminates a process from outside of the scheduled processes,
+    /// wherever it is queued. Killing the running process has the same
+    /// effect as if it had exited with code 0, it is counted as completed
+    /// and the next process is dispatched by the following call to
+    /// [`Scheduler::next`].
+    ///
+    /// Returns [`SyscallResult::NoSuchProcess`] if there is no process
+    /// with this PID.
+    pub fn kill(&mut self, pid: Pid) -> SyscallResult {
+        if !self.processes.contains(pid) {
+            return SyscallResult::NoSuchProcess;
+        }
+        if self.processes[pid].state() == ProcessState::Running {
+            self.exit(pid, 0);
+        } else {
+            self.remove(pid);
+        }
+        SyscallResult::Success
+    }
+
+    /// Removes the running process that has exited with `code`, counting
+    /// it as completed.
+    fn exit(&mut self, pid: Pid, code: i32) {
+        let turnaround = self.accounting.time() - self.processes[pid].pcb.created();
+        self.completed += 1;
+        self.total_turnaround = self.total_turnaround.saturating_add(turnaround);
+        if self.warming_up() {
+            self.warmup.completed += 1;
+            self.warmup.total_turnaround = self.warmup.total_turnaround.saturating_add(turnaround);
+        }
+        self.remove(pid);
+        if self.exit_codes.len() == EXIT_CODES {
+            self.exit_codes.pop_front();
+        }
+        self.exit_codes.push_back((pid, code));
+    }
+
+    /// Removes every process that is sleeping or waiting, like
+    /// [`RoundRobinScheduler::kill`] does, and returns their PIDs, sorted.
+    /// They are not counted as completed.
+    ///
+    /// This is meant for ending a simulation early, to find out which
+    /// processes could not finish.
+    pub fn drain_blocked(&mut self) -> Vec<Pid> {
+        let mut blocked = self
+            .processes
+            .iter()
+            .filter(|p| matches!(p.state(), ProcessState::Waiting { .. }))
+            .map(|p| p.pid())
+            .collect::<Vec<_>>();
+        blocked.sort();
+        for &pid in blocked.iter() {
+            // the process may have been killed with its parent
+            self.remove(pid);
+        }
+        blocked
+    }
+
+    /// Checks that the state of the scheduler is consistent, and returns
+    /// the first inconsistency found otherwise. This is meant for tests
+    /// and fuzzing, to find accounting bugs right where they happen.
+    ///
+    /// The checks are that:
+    /// * no process has run for longer than it has existed
+    /// * no process has more time left than its quantum
+    /// * the time charged to the processes plus the idle time is the time
+    ///   of the scheduler, unless the clock has stopped at its largest value
+    /// * every queued process exists, and it is queued once, in
+    ///   the ready queue, the sleep queue or on a core
+    /// * the processes in the ready queue are ready or running,
+    ///   those in the sleep queue are waiting, unless the process with
+    ///   PID 1 has exited and the scheduler has panicked
+    pub fn validate(&self) -> Result<(), String> {
+        for process in self.processes.iter() {
+            let (_, _, execution) = process.timings();
+            let total = process.total();
+            if execution > total {
+                return Err(format!(
+                    "process {} has run for {} time units out of {}",
+                    process.pid(), execution, total
+                ));
+            }
+            if process.remaining > process.quantum.get() {
+                return Err(format!(
+                    "process {} has {} time units left out of a quantum of {}",
+                    process.pid(), process.remaining, process.quantum
+                ));
+            }
+        }
+
+        let accounting = &self.accounting;
+        let time = accounting.time();
+        if time != usize::MAX
+            && accounting.busy_time().checked_add(accounting.idle_time()) != Some(time)
+        {
+            return Err(format!(
+                "the processes have been charged {} time units and the idle time is {}, \
+                 but the time is {}",
+                accounting.busy_time(), accounting.idle_time(), time
+            ));
+        }
+
+        let queued = self
+            .ready_queue
+            .iter()
+            .map(|&pid| ("ready queue", pid))
+            .chain(self.sleep_queue.iter().map(|&pid| ("sleep queue", pid)))
+            .chain(self.cores.iter().flatten().map(|&pid| ("cores", pid)));
+        let mut seen = BTreeMap::new();
+        for (queue, pid) in queued {
+            if let Some(other) = seen.insert(pid, queue) {
+                return Err(format!(
+                    "process {} is both in the {} and in the {}",
+                    pid, other, queue
+                ));
+            }
+            let Some(process) = self.processes.get(pid) else {
+                return Err(format!("process {} in the {} does not exist", pid, queue));
+            };
+            let state_fits = match queue {
+                "ready queue" => {
+                    matches!(process.state(), ProcessState::Ready | ProcessState::Running)
                 }
+                "sleep queue" => matches!(process.state(), ProcessState::Waiting { .. }),
+                _ => true,
+            };
+            // after a panic all the processes are shown as ready, wherever they are
+            if !state_fits && !self.init_exited {
+                return Err(format!(
+                    "process {} in the {} is {}",
+                    pid, queue, process.state()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the whole state of the scheduler, except for its trace
+    /// callback and its event bus.
+    pub fn snapshot(&self) -> SchedulerSnapshot {
+        SchedulerSnapshot {
+            scheduler: self.duplicate(),
+        }
+    }
+
+    /// Returns a copy of the scheduler without its trace callback and its event bus.
+    fn duplicate(&self) -> Self {
+        Self {
+            processes: self.processes.clone(),
+            ready_queue: self.ready_queue.clone(),
+            sleep_queue: self.sleep_queue.clone(),
+            timeslice: self.timeslice,
+            priority_timeslices: self.priority_timeslices.clone(),
+            minimum_remaining_timeslice: self.minimum_remaining_timeslice,
+            nr_processes: self.nr_processes,
+            accounting: self.accounting,
+            init_exited: self.init_exited,
+            exit_codes: self.exit_codes.clone(),
+            last_dispatched: self.last_dispatched,
+            context_switches: self.context_switches,
+            completed: self.completed,
+            total_turnaround: self.total_turnaround,
+            warmup: self.warmup,
+            recorder: self.recorder.clone(),
+            kill_orphans: self.kill_orphans,
+            groups: self.groups.clone(),
+            events: self.events.clone(),
+            signal_policy: self.signal_policy,
+            target_policy: self.target_policy,
+            level_triggered: self.level_triggered,
+            pending_signals: self.pending_signals.clone(),
+            cores: self.cores.clone(),
+            core_times: self.core_times.clone(),
+            overlap: self.overlap,
+            max_processes: self.max_processes,
+            minimum_timeslice: self.minimum_timeslice,
+            #[cfg(feature = "std")]
+            event_bus: None,
+            trace: None,
+        }
+    }
+
+    /// Creates a scheduler that goes on from a snapshot, it takes the same
+    /// decisions as the scheduler from which the snapshot has been taken.
+    pub fn restore(snapshot: SchedulerSnapshot) -> Self {
+        snapshot.scheduler
+    }
+
+    /// Returns the number of times a process has been dispatched after a
+    /// different one, counting the first dispatch.
+    pub fn context_switches(&self) -> usize {
+        self.context_switches
+    }
+
+    /// Returns a summary of what the scheduler has done so far, since the
+    /// end of its warm-up if it has one.
+    pub fn statistics(&self) -> SchedulerStats {
+        let completed = self.completed - self.warmup.completed;
+        let total_turnaround = self.total_turnaround - self.warmup.total_turnaround;
+        SchedulerStats {
+            total_time: self.accounting.time().saturating_sub(self.warmup.length),
+            idle_time: self.accounting.idle_time() - self.warmup.idle_time,
+            completed,
+            context_switches: self.context_switches - self.warmup.context_switches,
+            average_turnaround: if completed == 0 {
+                0.0
             } else {
-                self.ready_queue.push_back(pid);
-                self.processes[process_index].set_state(ProcessState::Ready);
+                total_turnaround as f64 / completed as f64
+            },
+        }
+    }
+
+    /// Returns the statistics of every process since the start of the
+    /// simulation, those still alive and those that have exited or have
+    /// been killed.
+    pub fn stats(&self) -> SimulationStats {
+        self.recorder.report(self.accounting.time())
+    }
+
+    /// Returns the exit code of a process that has exited recently, or
+    /// [`None`] if the process is still alive, has been killed while it was
+    /// not running, or has exited too long ago.
+    ///
+    /// The exit codes are forgotten when there are no more processes.
+    pub fn exit_code(&self, pid: Pid) -> Option<i32> {
+        self.exit_codes
+            .iter()
+            .find(|(p, _)| *p == pid)
+            .map(|(_, code)| *code)
+    }
+
+    /// Adds a ready process that is granted `burst` time units every time
+    /// it is dispatched, instead of the timeslice of the scheduler, without
+    /// a [`Syscall::Fork`] system call. The process gets the next PID and
+    /// has no parent. The processes it forks get the timeslice of the
+    /// scheduler.
+    ///
+    /// Fails like a [`Syscall::Fork`] would when the limit set by
+    /// [`RoundRobinScheduler::max_processes`] is reached or when there is
+    /// no PID left to give to the process.
+    pub fn add_process(&mut self, priority: i8, burst: NonZeroUsize) -> Result<Pid, FailReason> {
+        if self.max_processes.is_some_and(|max| self.processes.len() >= max) {
+            return Err(FailReason::MaxProcessesReached);
+        }
+        if self.nr_processes == usize::MAX {
+            return Err(FailReason::PidOverflow);
+        }
+        self.nr_processes += 1;
+        let pid = Pid::new(self.nr_processes);
+        let mut process =
+            RoundRobinProcess::new(pid, ProcessState::Ready, priority, (0, 0, 0), burst);
+        process.burst = Some(burst);
+        process.pcb.set_created(self.accounting.time());
+        self.recorder.arrive(pid, self.accounting.time());
+        self.groups.entry(process.group).or_default().insert(pid);
+        self.processes.insert(process);
+        self.ready_queue.push_back(pid);
+        Ok(pid)
+    }
+
+    /// Adds a process that has not been created by a [`Syscall::Fork`]
+    /// system call, placing it in the ready or sleep queue as if it had
+    /// always been handled by this scheduler.
+    ///
+    /// Fails like a [`Syscall::Fork`] would when the limit set by
+    /// [`RoundRobinScheduler::max_processes`] is reached or when there is
+    /// no PID left to give to the process.
+    pub fn adopt(&mut self, spec: AdoptSpec) -> Result<Pid, FailReason> {
+        if self.max_processes.is_some_and(|max| self.processes.len() >= max) {
+            return Err(FailReason::MaxProcessesReached);
+        }
+        let pid = match spec.pid {
+            Some(pid) if self.processes.contains(pid) => {
+                return Err(FailReason::PidInUse(pid));
             }
-            if let Some(pid) = self.ready_queue.pop_front() {
-                self.ready_queue.push_front(pid);
-                let process_index = self
-                .processes
+            Some(pid) => pid,
+            None if self.nr_processes == usize::MAX => return Err(FailReason::PidOverflow),
+            None => Pid::new(self.nr_processes + 1),
+        };
+        self.nr_processes = self.nr_processes.max(pid.get());
+
+        let mut process = RoundRobinProcess::new(
+            pid,
+            ProcessState::Ready,
+            spec.priority,
+            spec.timings,
+            self.timeslice,
+        );
+        process.reset_quantum(self.timeslice, &self.priority_timeslices);
+        process.group = spec.group;
+        match spec.state {
+            AdoptState::Sleeping { until } if until > self.accounting.time() => {
+                process.wake_time = until;
+                process.set_state(ProcessState::Waiting { event: None });
+                self.sleep_queue.push_back(pid);
+            }
+            _ => self.ready_queue.push_back(pid),
+        }
+        process.pcb.set_created(self.accounting.time());
+        self.recorder.arrive(pid, self.accounting.time());
+        self.groups.entry(process.group).or_default().insert(pid);
+        self.processes.insert(process);
+        Ok(pid)
+    }
+}
+
+impl RoundRobinScheduler {
+    /// Writes a table with the processes in `pids`, sorted by PID.
+    fn write_queue(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        name: &str,
+        pids: impl Iterator<Item = Pid>,
+    ) -> core::fmt::Result {
+        let mut pids = pids.collect::<Vec<_>>();
+        pids.sort();
+        writeln!(f, "{}:", name)?;
+        for pid in pids {
+            let process = &self.processes[pid];
+            writeln!(
+                f,
+                "  {:<5} {:<10} {:<8} {}",
+                process.pid().to_string(),
+                process.state().to_string(),
+                process.priority(),
+                process.remaining
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders the queues of the scheduler, one table for each queue, with the
+/// PID, state, priority and remaining time of every process.
+impl Display for RoundRobinScheduler {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "time: {}", self.accounting.time())?;
+        writeln!(f, "  {:<5} {:<10} {:<8} REMAINING", "PID", "STATE", "PRIORITY")?;
+        self.write_queue(f, "ready", self.ready_queue.iter().copied())?;
+        self.write_queue(f, "sleeping", self.sleep_queue.iter().copied())?;
+        self.write_queue(
+            f,
+            "waiting",
+            self.processes
                 .iter()
-                .position(|p| p.pid() == pid)
-                .expect("Process not found in the list");
-                if let Some(remaining) = NonZeroUsize::new(self.processes[process_index].remaining) {
-                    self.processes[process_index].set_state(ProcessState::Running);
-                    return SchedulingDecision::Run { pid:pid, timeslice: remaining };
-                } else {
-                    self.processes[process_index].set_state(ProcessState::Running);
-                    return SchedulingDecision::Run { pid:pid, timeslice: self.timeslice };
+                .filter(|p| {
+                    matches!(p.state(), ProcessState::Waiting { .. })
+                        && !self.sleep_queue.contains(&p.pid())
+                })
+                .map(|p| p.pid()),
+        )
+    }
+}
+
+/// The clone of a scheduler goes on independently from the original,
+/// without the trace callback. If the scheduler uses an event bus, the
+/// clone subscribes to it as well and has received the same signals.
+impl Clone for RoundRobinScheduler {
+    fn clone(&self) -> Self {
+        #[allow(unused_mut)]
+        let mut scheduler = self.duplicate();
+        #[cfg(feature = "std")]
+        {
+            scheduler.event_bus = self
+            .event_bus
+            .as_ref()
+                .map(|(bus, subscriber)| (bus.clone(), bus.fork(*subscriber)));
+        }
+        scheduler
+    }
+}
+
+impl Processes for RoundRobinScheduler {
+    fn processes(&self) -> impl Iterator<Item = &dyn Process> {
+        self.processes.iter().map(|p| p as &dyn Process)
+    }
+
+    fn get(&self, pid: Pid) -> Option<&dyn Process> {
+        self.processes.get(pid).map(|p| p as &dyn Process)
+    }
+}
+
+impl Scheduler for RoundRobinScheduler {
+    fn next(&mut self) -> SchedulingDecision {
+        if self.init_exited && !self.processes.is_empty() {
+            for process in self.processes.iter_mut() {
+                process.set_state(ProcessState::Ready);
+            }
+            return SchedulingDecision::Panic;
+        }
+        self.receive_signals();
+        self.wake_sleepers();
+        if let Some(pid) = self.ready_front() {
+            // a process left with too little of its timeslice goes to the
+            // back of the queue with a fresh one, the front is dispatched
+            let process = &self.processes[pid];
+            if self.too_little_left(process.remaining, process.quantum) {
+                self.preempt();
+            }
+            // the preempted process is still queued, so there is a front
+            let pid = self.ready_front().unwrap_or(pid);
+            if let Some(timeslice) = self.adaptive_timeslice(self.ready_queue.len()) {
+                let process = &mut self.processes[pid];
+                process.remaining = process.remaining.min(timeslice);
+            }
+            self.processes[pid].set_state(ProcessState::Running);
+            self.recorder.dispatch(pid, self.accounting.time());
+            if self.last_dispatched != Some(pid) {
+                self.last_dispatched = Some(pid);
+                self.context_switches += 1;
+                if self.warming_up() {
+                    self.warmup.context_switches += 1;
                 }
+            }
+            self.emit(SchedulerEvent::Dispatch { pid, time: self.accounting.time() });
+            SchedulingDecision::Run {
+                pid,
+                timeslice: self.processes[pid].timeslice(),
+            }
+        } else if let Some(wake_time) = self
+            .sleep_queue
+            .iter()
+            .map(|pid| self.processes[*pid].wake_time)
+            .min()
+        {
+            let time = self.accounting.time();
+            let amount = wake_time.saturating_sub(time);
+            // the sleep may end after the warm-up
+            self.warmup.idle_time += amount.min(self.warmup.length.saturating_sub(time));
+            // every process is credited by the clock of the table
+            self.accounting.advance_sleep(amount, iter::empty());
+            self.processes.credit(amount);
+            self.wake_sleepers();
+            match NonZeroUsize::new(amount) {
+                Some(amount) => SchedulingDecision::Sleep(amount),
+                // the sleep has already ended, the process is ready right away
+                None => self.next(),
+            }
+        } else if self.processes.is_empty() {
+            self.exit_codes.clear();
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        if self.init_exited && !self.processes.is_empty() {
+            return SchedulingDecision::Panic;
+        }
+
+        // the ready queue once the processes signaled by other schedulers
+        // and then those whose sleep has ended are woken up, with the time
+        // left from the quantum of every process
+        let signaled = self.bus_woken(&self.received(false));
+        let sleeping = self
+            .sleep_queue
+            .iter()
+            .filter(|pid| !signaled.contains(pid))
+            .map(|&pid| &self.processes[pid])
+            .collect::<Vec<_>>();
+        let mut woken = sleeping
+            .iter()
+            .copied()
+            .filter(|p| p.wake_time <= self.accounting.time())
+            .collect::<Vec<_>>();
+        woken.sort_by_key(|p| p.wake_time);
+        let mut queue = self
+            .ready_queue
+            .iter()
+            .filter_map(|&pid| self.processes.get(pid))
+            .map(|p| {
+                let fresh = p.fresh_quantum(self.timeslice, &self.priority_timeslices);
+                (p.pid(), p.remaining, p.quantum, fresh)
+            })
+            .chain(
+                signaled
+                    .iter()
+                    .map(|&pid| &self.processes[pid])
+                    .chain(woken.iter().copied())
+                    .map(|p| {
+                        let quantum =
+                            p.fresh_quantum(self.timeslice, &self.priority_timeslices);
+                        (p.pid(), quantum.get(), quantum, quantum)
+                    }),
+            )
+            .collect::<VecDeque<_>>();
+
+        if let Some((pid, remaining, quantum, fresh)) = queue.pop_front() {
+            if self.too_little_left(remaining, quantum) {
+                queue.push_back((pid, fresh.get(), fresh, fresh));
             } else {
-                SchedulingDecision::Done
-            }    
-        } else if let Some(pid) = self.sleep_queue.pop_front() { 
-            let process_index = self
-                .processes
-                .iter()
-                .position(|p| p.pid() == pid)
-                .expect("Process not found in the list");
-            let sleep = NonZeroUsize::new(self.processes[process_index].sleep_time).unwrap();
-            self.ready_queue.push_back(pid);
-            SchedulingDecision::Sleep(sleep)
-        }else{
+                queue.push_front((pid, remaining, quantum, fresh));
+            }
+            let (pid, mut remaining, quantum, _) = queue[0];
+            if let Some(timeslice) = self.adaptive_timeslice(queue.len()) {
+                remaining = remaining.min(timeslice);
+            }
+            return SchedulingDecision::Run {
+                pid,
+                timeslice: NonZeroUsize::new(remaining.min(quantum.get())).unwrap_or(quantum),
+            };
+        }
+
+        // every sleep that has ended is handled above
+        let wake_time = sleeping.iter().map(|p| p.wake_time).min();
+        let time = self.accounting.time();
+        if let Some(amount) = wake_time.and_then(|t| NonZeroUsize::new(t - time)) {
+            SchedulingDecision::Sleep(amount)
+        } else if self.processes.is_empty() {
             SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
         }
     }
     
     fn stop(&mut self, reason: StopReason) -> SyscallResult {
         match reason {
             StopReason::Syscall { syscall, remaining } => {
-                match syscall {
-                    Syscall::Fork(process_priority) => {
-                        if let Some(pid) = self.ready_queue.pop_front() {
-                            self.ready_queue.push_front(pid);
-                            let process_index = self
-                                .processes
-                                .iter()
-                                .position(|p| p.pid() == pid)
-                                .expect("Process not found in the list");
-                            self.time += self.processes[process_index].remaining - remaining;
-                            self.processes[process_index].timings.0 += self.processes[process_index].remaining - remaining;
-                            self.processes[process_index].timings.1 += 1;
-                            self.processes[process_index].timings.2 += self.processes[process_index].remaining - remaining - 1;
-                            for i in 1..self.ready_queue.len() {
-                                let pid = self.ready_queue.get(i).unwrap();
-                                let process_index = self
-                                    .processes
-                                    .iter()
-                                    .position(|p| p.pid() == *pid)
-                                    .expect("Process not found in the list");
-                                self.processes[process_index].timings.0 += self.processes[process_index].remaining - remaining;
-                            }
-                            self.processes[process_index].remaining = remaining;
-                        }
-                        let new_pid = Pid::new((self.nr_processes + 1).try_into().unwrap());
-                        self.nr_processes += 1;
-                        let new_process = RoundRobinProcess::new(
-                            new_pid,
-                            ProcessState::Ready,
-                            process_priority,
-                            (0, 0, 0),
-                            self.timeslice.into(),
-                        );
-                        self.processes.push(new_process);
-                        self.ready_queue.push_back(new_pid);
-                        return SyscallResult::Pid(new_pid);
+                let running = self.charge_syscall(remaining);
+                let result = match syscall {
+                    Syscall::Fork(process_priority) => self.fork(running, process_priority),
+                    Syscall::Sleep(amount) | Syscall::WaitTimeout { max: amount, .. }
+                        if self.accounting.time().checked_add(amount).is_none() =>
+                    {
+                        SyscallResult::Error(SchedulerError::InvalidSleepDuration(amount))
+                    }
+                    Syscall::Sleep(0) | Syscall::Yield => {
+                        // there is nothing to sleep, the process just gives up the
+                        // processor and gets a fresh quantum
+                        self.preempt();
+                        SyscallResult::Success
                     }
                     Syscall::Sleep(amount_of_time) => {
                         if let Some(pid) = self.ready_queue.pop_front() {
-                            self.ready_queue.push_front(pid);
-                            let process_index = self
-                                .processes
-                                .iter()
-                                .position(|p| p.pid() == pid)
-                                .expect("Process not found in the list");
-                            self.time += self.processes[process_index].remaining - remaining;
-                            self.processes[process_index].timings.0 += self.processes[process_index].remaining - remaining;
-                            self.processes[process_index].timings.1 += 1;
-                            self.processes[process_index].timings.2 += self.processes[process_index].remaining - remaining - 1;
-                            for i in 1..self.ready_queue.len() {
-                                let pid = self.ready_queue.get(i).unwrap();
-                                let process_index = self
-                                    .processes
-                                    .iter()
-                                    .position(|p| p.pid() == *pid)
-                                    .expect("Process not found in the list");
-                                self.processes[process_index].timings.0 += self.processes[process_index].remaining - remaining;
-                            }
-                            self.processes[process_index].remaining = remaining;
-                        }
-                        if let Some(pid) = self.ready_queue.pop_front() {
-                            let process_index = self
-                                .processes
-                                .iter()
-                                .position(|p| p.pid() == pid)
-                                .expect("Process not found in the list");
-                            self.processes[process_index].sleep_time = amount_of_time;
+                            self.processes[pid].wake_time = self.accounting.time() + amount_of_time;
                             let event = None;
-                            self.processes[process_index].set_state(ProcessState::Waiting {event});
+                            self.processes[pid].set_state(ProcessState::Waiting {event});
                             self.sleep_queue.push_back(pid);
+                            self.emit(SchedulerEvent::Block { pid, time: self.accounting.time() });
                         }
-                        
-                        return SyscallResult::Success;
+                        SyscallResult::Success
                     }
-                    Syscall::Wait(_event_number) => {
-                        return SyscallResult::Success;
+                    Syscall::SetPriority(priority) => {
+                        if let Some(pid) = running {
+                            self.processes[pid].pcb.set_priority(priority);
+                        }
+                        SyscallResult::Success
                     }
-                    Syscall::Signal(_event_number) => {
-                        return SyscallResult::Success;
+                    Syscall::Wait(event) => {
+                        self.wait(event, None);
+                        SyscallResult::Success
                     }
-                    Syscall::Exit => {
-                        if let Some(pid) = self.ready_queue.pop_front() {
-                            let process_index = self
-                                .processes
-                                .iter()
-                                .position(|p| p.pid() == pid)
-                                .expect("Process not found in the list");
-                            for i in 0..self.ready_queue.len() {
-                                let new_pid = self.ready_queue.get(i).unwrap();
-                                let new_process_index = self
-                                    .processes
-                                    .iter()
-                                    .position(|p| p.pid() == *new_pid)
-                                    .expect("Process not found in the list");
-                                self.processes[new_process_index].timings.0 += self.processes[process_index].remaining - remaining;
-                            }
-                            self.time += self.processes[process_index].remaining - remaining;
-                            self.processes.retain(|p| p.pid() != pid);
+                    Syscall::WaitTimeout { event, max } => {
+                        self.wait(event, Some(max));
+                        SyscallResult::Success
+                    }
+                    Syscall::Signal(event) => {
+                        self.signal(event, None);
+                        #[cfg(feature = "std")]
+                        if let Some((bus, subscriber)) = &self.event_bus {
+                            bus.publish(*subscriber, event);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::SignalGroup(group, event) => {
+                        self.signal(event, Some(group));
+                        SyscallResult::Success
+                    }
+                    Syscall::SetGroup(group) => {
+                        if let Some(pid) = running {
+                            let old_group = self.processes[pid].group;
+                            self.processes[pid].group = group;
+                            self.leave_group(pid, old_group);
+                            self.groups.entry(group).or_default().insert(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(pid) if running == Some(pid) => {
+                        SyscallResult::Error(SchedulerError::WaitForSelf)
+                    }
+                    Syscall::WaitPid(pid) if self.processes.contains(pid) => {
+                        if let Some(waiter) = self.ready_queue.pop_front() {
+                            self.processes.set_waiting_for(waiter, Some(pid));
+                            self.processes[waiter]
+                                .set_state(ProcessState::Waiting { event: None });
+                            let time = self.accounting.time();
+                            self.emit(SchedulerEvent::Block { pid: waiter, time });
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(pid) if pid.get() <= self.nr_processes => {
+                        // the process has already exited
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(_) => SyscallResult::NoSuchProcess,
+                    Syscall::Kill(target) => {
+                        match running.and_then(|pid| self.deny_target(pid, target)) {
+                            Some(denied) => denied,
+                            None => self.kill(target),
+                        }
+                    }
+                    Syscall::Exit | Syscall::ExitWith(_) => {
+                        if let Some(pid) = running {
+                            let code = match syscall {
+                                Syscall::ExitWith(code) => code,
+                                _ => 0,
+                            };
+                            self.exit(pid, code);
                         }
-                        return SyscallResult::Success;
-                    }    
+                        SyscallResult::Success
+                    }
+                };
+                // a process that gives up the processor has already been preempted
+                if !matches!(syscall, Syscall::Sleep(0) | Syscall::Yield) {
+                    self.finish_syscall(running, remaining);
                 }
+                result
             }
             StopReason::Expired => {
-                if let Some(pid) = self.ready_queue.pop_front() {
-                    self.processes
-                        .iter_mut()
-                        .find(|p| p.pid() == pid)
-                        .unwrap() 
-                        .set_state(ProcessState::Ready);
-                    let process_index = self
-                        .processes
-                        .iter()
-                        .position(|p| p.pid() == pid)
-                        .expect("Process not found in the list");
-                    self.time += self.processes[process_index].remaining;
-                    self.processes[process_index].timings.2 += self.processes[process_index].remaining;
-                    self.processes[process_index].timings.0 += self.processes[process_index].remaining;
-                    
-                    for i in 0..self.ready_queue.len() {
-                        let pid = self.ready_queue.get(i).unwrap();
-                        let new_process_index = self
-                            .processes
-                            .iter()
-                            .position(|p| p.pid() == *pid)
-                            .expect("Process not found in the list");
-                        self.processes[new_process_index].timings.0 += self.processes[process_index].remaining;
-                    }
-                    self.processes[process_index].remaining = self.timeslice.into();
-                    self.ready_queue.push_back(pid);
+                if let Some(pid) = self.ready_front() {
+                    self.charge(self.processes[pid].remaining, ChargeKind::Execution);
+                    self.preempt();
                 }
+                SyscallResult::Success
             }
         }
-        SyscallResult::Success
     }
 
     fn list(&mut self) -> Vec<&dyn Process> {
-        self.processes.iter().map(|p| p as &dyn Process).collect::<Vec<&dyn Process>>()
+        self.processes().collect()
+    }
+
+    /// The processes that are ready, running or sleeping, including those
+    /// that wait for an event with a timeout, can make progress. As the
+    /// scheduler does not know which process will signal an event, any
+    /// process waiting for an event can be woken up as long as some
+    /// process can make progress. A process waiting for another process
+    /// to exit can be woken up only if that process can.
+    fn deadlocked(&self) -> Vec<Pid> {
+        let mut alive = self
+            .processes
+            .iter()
+            .filter(|p| {
+                !matches!(p.state(), ProcessState::Waiting { .. })
+                    || self.sleep_queue.contains(&p.pid())
+            })
+            .map(|p| p.pid())
+            .collect::<BTreeSet<_>>();
+        loop {
+            let woken = self
+                .processes
+                .iter()
+                .filter(|p| !alive.contains(&p.pid()))
+                .filter(|p| match p.waiting_for {
+                    Some(pid) => alive.contains(&pid),
+                    None => !alive.is_empty(),
+                })
+                .map(|p| p.pid())
+                .collect::<Vec<_>>();
+            if woken.is_empty() {
+                break;
+            }
+            alive.extend(woken);
+        }
+        let mut deadlocked = self
+            .processes
+            .iter()
+            .map(|p| p.pid())
+            .filter(|pid| !alive.contains(pid))
+            .collect::<Vec<_>>();
+        deadlocked.sort();
+        deadlocked
+    }
+
+    fn set_trace(&mut self, trace: Trace) {
+        self.trace = Some(trace);
     }
 }
@@ -0,0 +1,437 @@
+use crate::accounting::{AccountingClock, ChargeKind};
+use crate::events::EventTable;
+use crate::pcb::ProcessControlBlock;
+use crate::scheduler::{
+    Pid, Process, ProcessState, Processes, Scheduler, SchedulerError, SchedulingDecision,
+    StopReason, Syscall, SyscallResult,
+};
+use core::num::NonZeroUsize;
+use alloc::{format, string::String, vec::Vec};
+
+pub struct SrtfProcess {
+    pcb: ProcessControlBlock,
+    /// The execution time that the process still needs, or [`None`]
+    /// if it has not been declared.
+    remaining: Option<usize>,
+    wake_time: usize,
+    /// Whether the process waits for an event only until `wake_time`.
+    has_timeout: bool,
+    /// The process that this process waits to exit.
+    waiting_for: Option<Pid>,
+}
+
+impl SrtfProcess {
+    pub fn new(pid: Pid, priority: i8) -> Self {
+        SrtfProcess {
+            pcb: ProcessControlBlock::new(pid, ProcessState::Ready, priority, (0, 0, 0)),
+            remaining: None,
+            wake_time: 0,
+            has_timeout: false,
+            waiting_for: None,
+        }
+    }
+
+    pub fn set_state(&mut self, new_state: ProcessState) {
+        self.pcb.set_state(new_state);
+    }
+
+    /// Whether the process waits until its `wake_time`, either sleeping
+    /// or waiting for an event with a timeout.
+    fn is_sleeping(&self) -> bool {
+        match self.state() {
+            ProcessState::Waiting { event: None } => self.waiting_for.is_none(),
+            ProcessState::Waiting { event: Some(_) } => self.has_timeout,
+            _ => false,
+        }
+    }
+
+    /// The key by which processes are picked, processes that have not
+    /// declared their execution time go last.
+    fn order(&self) -> (usize, Pid) {
+        (self.remaining.unwrap_or(usize::MAX), self.pid())
+    }
+}
+
+impl Process for SrtfProcess {
+    fn pid(&self) -> Pid {
+        self.pcb.pid()
+    }
+
+    fn state(&self) -> ProcessState {
+        self.pcb.state()
+    }
+
+    fn timings(&self) -> (usize, usize, usize) {
+        self.pcb.timings()
+    }
+
+    fn priority(&self) -> i8 {
+        self.pcb.priority()
+    }
+
+    fn extra(&self) -> String {
+        match self.remaining {
+            Some(remaining) => format!("remaining={}", remaining),
+            None => String::from("remaining=?"),
+        }
+    }
+
+    fn parent(&self) -> Option<Pid> {
+        self.pcb.parent()
+    }
+}
+
+/// A preemptive shortest remaining time first scheduler.
+///
+/// Every time it is asked for a decision, the scheduler runs the ready
+/// process with the least execution time left, declared with
+/// [`SrtfScheduler::set_remaining`], the one with the lowest PID if there
+/// are several. A running process is preempted as soon as a shorter one
+/// becomes ready.
+pub struct SrtfScheduler {
+    processes: Vec<SrtfProcess>,
+    running: Option<Pid>,
+    events: EventTable,
+    /// The longest time a process is granted at once.
+    timeslice: NonZeroUsize,
+    /// The time granted to the running process.
+    granted: usize,
+    nr_processes: usize,
+    accounting: AccountingClock,
+    /// Set when the process with PID 1 exits, any process left
+    /// after that makes the scheduler panic.
+    init_exited: bool,
+}
+
+impl SrtfScheduler {
+    pub fn new(timeslice: NonZeroUsize) -> Self {
+        Self {
+            processes: Vec::new(),
+            running: None,
+            events: EventTable::new(),
+            timeslice,
+            granted: 0,
+            nr_processes: 0,
+            accounting: AccountingClock::new(),
+            init_exited: false,
+        }
+    }
+
+    /// Declares the execution time that a process still needs, which is
+    /// reduced as the process runs. Processes with no declared time run
+    /// after all the others.
+    ///
+    /// Returns [`SyscallResult::NoSuchProcess`] if there is no process
+    /// with this PID.
+    pub fn set_remaining(&mut self, pid: Pid, remaining: usize) -> SyscallResult {
+        match self.processes.iter_mut().find(|p| p.pid() == pid) {
+            Some(process) => {
+                process.remaining = Some(remaining);
+                SyscallResult::Success
+            }
+            None => SyscallResult::NoSuchProcess,
+        }
+    }
+
+    fn process_index(&self, pid: Pid) -> usize {
+        self.processes
+            .iter()
+            .position(|p| p.pid() == pid)
+            .expect("Process not found in the list")
+    }
+
+    /// Charges the running process for `consumed` time units and credits
+    /// the same amount of time to every other process.
+    fn charge(&mut self, pid: Pid, consumed: usize, kind: ChargeKind) {
+        let processes = self.processes.iter_mut().map(|p| &mut p.pcb);
+        self.accounting.charge(pid, consumed, kind, processes);
+        let process_index = self.process_index(pid);
+        let process = &mut self.processes[process_index];
+        if let Some(remaining) = process.remaining.as_mut() {
+            *remaining = remaining.saturating_sub(consumed);
+        }
+    }
+
+    /// Takes the processor from the running process and puts it in `state`.
+    fn block(&mut self, state: ProcessState) -> Option<Pid> {
+        let pid = self.running.take()?;
+        let process_index = self.process_index(pid);
+        self.processes[process_index].set_state(state);
+        Some(pid)
+    }
+
+    /// Makes a process ready.
+    fn wake(&mut self, pid: Pid) {
+        let process_index = self.process_index(pid);
+        self.processes[process_index].set_state(ProcessState::Ready);
+        self.processes[process_index].waiting_for = None;
+    }
+
+    /// Makes ready the processes whose sleep has ended.
+    fn wake_sleepers(&mut self) {
+        let woken = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping() && p.wake_time <= self.accounting.time())
+            .map(|p| p.pid())
+            .collect::<Vec<_>>();
+        for pid in woken {
+            // a wait for an event may have timed out
+            self.events.remove(pid);
+            self.wake(pid);
+        }
+    }
+
+    /// Whether the process is ready, or will be once the processes
+    /// whose sleep has ended are woken up.
+    fn is_ready(&self, process: &SrtfProcess) -> bool {
+        match process.state() {
+            ProcessState::Ready | ProcessState::Running => true,
+            _ => process.is_sleeping() && process.wake_time <= self.accounting.time(),
+        }
+    }
+
+    /// Returns the process to run and the time to grant it, the process
+    /// runs until it is done, or until a sleeping process that might be
+    /// shorter wakes up.
+    fn pick(&self) -> Option<(Pid, usize)> {
+        let shortest = self
+            .processes
+            .iter()
+            .filter(|p| self.is_ready(p))
+            .min_by_key(|p| p.order())?;
+        let wake_time = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping() && !self.is_ready(p))
+            .map(|p| p.wake_time)
+            .min();
+
+        let mut timeslice = self.timeslice.get();
+        if let Some(remaining) = shortest.remaining {
+            timeslice = timeslice.min(remaining);
+        }
+        if let Some(wake_time) = wake_time {
+            timeslice = timeslice.min(wake_time - self.accounting.time());
+        }
+        Some((shortest.pid(), timeslice.max(1)))
+    }
+
+    /// Removes a process and wakes up the processes waiting for it to exit.
+    fn remove(&mut self, pid: Pid) {
+        if self.running == Some(pid) {
+            self.running = None;
+        }
+        self.events.remove(pid);
+        self.processes.retain(|p| p.pid() != pid);
+        if pid == 1 {
+            self.init_exited = true;
+        }
+        let waiters = self
+            .processes
+            .iter()
+            .filter(|p| p.waiting_for == Some(pid))
+            .map(|p| p.pid())
+            .collect::<Vec<_>>();
+        for waiter in waiters {
+            self.wake(waiter);
+        }
+    }
+}
+
+impl Processes for SrtfScheduler {
+    fn processes(&self) -> impl Iterator<Item = &dyn Process> {
+        // the processes are kept sorted by PID
+        self.processes.iter().map(|p| p as &dyn Process)
+    }
+}
+
+impl Scheduler for SrtfScheduler {
+    fn next(&mut self) -> SchedulingDecision {
+        if self.init_exited && !self.processes.is_empty() {
+            return SchedulingDecision::Panic;
+        }
+        self.wake_sleepers();
+
+        if let Some((pid, granted)) = self.pick() {
+            // the running process is preempted by a shorter one
+            if let Some(running) = self.running.filter(|&running| running != pid) {
+                let process_index = self.process_index(running);
+                self.processes[process_index].set_state(ProcessState::Ready);
+            }
+            self.running = Some(pid);
+            let process_index = self.process_index(pid);
+            self.processes[process_index].set_state(ProcessState::Running);
+            self.granted = granted;
+            return SchedulingDecision::Run {
+                pid,
+                timeslice: NonZeroUsize::new(granted).unwrap_or(NonZeroUsize::MIN),
+            };
+        }
+
+        let wake_time = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping())
+            .map(|p| p.wake_time)
+            .min();
+        if let Some(wake_time) = wake_time {
+            let amount = wake_time.saturating_sub(self.accounting.time());
+            let processes = self.processes.iter_mut().map(|p| &mut p.pcb);
+            self.accounting.advance_sleep(amount, processes);
+            match NonZeroUsize::new(amount) {
+                Some(amount) => SchedulingDecision::Sleep(amount),
+                // the sleep has already ended, the process is ready right away
+                None => self.next(),
+            }
+        } else if self.processes.is_empty() {
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        if self.init_exited && !self.processes.is_empty() {
+            return SchedulingDecision::Panic;
+        }
+
+        if let Some((pid, granted)) = self.pick() {
+            return SchedulingDecision::Run {
+                pid,
+                timeslice: NonZeroUsize::new(granted).unwrap_or(NonZeroUsize::MIN),
+            };
+        }
+
+        // every sleep that has ended is handled above
+        let wake_time = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping())
+            .map(|p| p.wake_time)
+            .min();
+        let time = self.accounting.time();
+        if let Some(amount) = wake_time.and_then(|t| NonZeroUsize::new(t - time)) {
+            SchedulingDecision::Sleep(amount)
+        } else if self.processes.is_empty() {
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        match reason {
+            StopReason::Syscall { syscall, remaining } => {
+                let running = self.running;
+                if let Some(pid) = running {
+                    self.charge(pid, self.granted.saturating_sub(remaining), ChargeKind::Syscall);
+                    // a process cannot have more time left than it has been granted
+                    self.granted = self.granted.min(remaining);
+                } else if !matches!(syscall, Syscall::Fork(_)) {
+                    return SyscallResult::NoRunningProcess;
+                }
+                match syscall {
+                    Syscall::Fork(priority) => {
+                        self.nr_processes += 1;
+                        let pid = Pid::new(self.nr_processes);
+                        self.processes.push(SrtfProcess::new(pid, priority));
+                        SyscallResult::Pid(pid)
+                    }
+                    Syscall::Sleep(amount) | Syscall::WaitTimeout { max: amount, .. }
+                        if self.accounting.time().checked_add(amount).is_none() =>
+                    {
+                        SyscallResult::Error(SchedulerError::InvalidSleepDuration(amount))
+                    }
+                    Syscall::Sleep(0) | Syscall::Yield => {
+                        // the process is picked again if it is still the shortest
+                        self.block(ProcessState::Ready);
+                        SyscallResult::Success
+                    }
+                    Syscall::Sleep(amount) => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: None }) {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].wake_time =
+                                self.accounting.time() + amount;
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::Wait(event) => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: Some(event) })
+                        {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].has_timeout = false;
+                            self.events.wait(pid, event);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitTimeout { event, max } => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: Some(event) })
+                        {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].wake_time = self.accounting.time() + max;
+                            self.processes[process_index].has_timeout = true;
+                            self.events.wait(pid, event);
+                        }
+                        SyscallResult::Success
+                    }
+                    // all the processes are in group 0
+                    Syscall::Signal(event) | Syscall::SignalGroup(0, event) => {
+                        for pid in self.events.signal(event) {
+                            self.wake(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::SetGroup(_) | Syscall::SignalGroup(..) => SyscallResult::Success,
+                    Syscall::SetPriority(priority) => {
+                        if let Some(pid) = running {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].pcb.set_priority(priority);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(pid) if running == Some(pid) => {
+                        SyscallResult::Error(SchedulerError::WaitForSelf)
+                    }
+                    Syscall::WaitPid(pid) if self.processes.iter().any(|p| p.pid() == pid) => {
+                        if let Some(waiter) = self.block(ProcessState::Waiting { event: None }) {
+                            let process_index = self.process_index(waiter);
+                            self.processes[process_index].waiting_for = Some(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(pid) if pid.get() <= self.nr_processes => {
+                        // the process has already exited
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(_) => SyscallResult::NoSuchProcess,
+                    Syscall::Kill(pid) => {
+                        if self.processes.iter().any(|p| p.pid() == pid) {
+                            self.remove(pid);
+                            SyscallResult::Success
+                        } else {
+                            SyscallResult::NoSuchProcess
+                        }
+                    }
+                    Syscall::Exit | Syscall::ExitWith(_) => {
+                        if let Some(pid) = running {
+                            self.remove(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                }
+            }
+            StopReason::Expired => {
+                if let Some(pid) = self.running {
+                    self.charge(pid, self.granted, ChargeKind::Execution);
+                    self.granted = 0;
+                }
+                SyscallResult::Success
+            }
+        }
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.processes().collect()
+    }
+}
@@ -0,0 +1,431 @@
+use crate::accounting::{AccountingClock, ChargeKind};
+use crate::events::EventTable;
+use crate::pcb::ProcessControlBlock;
+use crate::scheduler::{
+    Pid, Process, ProcessState, Processes, Scheduler, SchedulerError, SchedulingDecision,
+    StopReason, Syscall, SyscallResult,
+};
+use core::num::NonZeroUsize;
+use alloc::{format, string::String, vec::Vec};
+
+/// The amount that is split between the tickets of a process
+/// to find its stride.
+const STRIDE1: usize = 1 << 20;
+
+pub struct StrideProcess {
+    pcb: ProcessControlBlock,
+    /// The virtual time of the process, the process with the lowest
+    /// pass runs next.
+    pass: usize,
+    wake_time: usize,
+    /// Whether the process waits for an event only until `wake_time`.
+    has_timeout: bool,
+    /// The process that this process waits to exit.
+    waiting_for: Option<Pid>,
+}
+
+impl StrideProcess {
+    pub fn new(pid: Pid, priority: i8, pass: usize) -> Self {
+        StrideProcess {
+            pcb: ProcessControlBlock::new(pid, ProcessState::Ready, priority, (0, 0, 0)),
+            pass,
+            wake_time: 0,
+            has_timeout: false,
+            waiting_for: None,
+        }
+    }
+
+    pub fn set_state(&mut self, new_state: ProcessState) {
+        self.pcb.set_state(new_state);
+    }
+
+    /// The share of the processor of the process, one more than its
+    /// priority, processes with a negative priority have one ticket.
+    pub fn tickets(&self) -> usize {
+        self.priority().max(0) as usize + 1
+    }
+
+    /// How much the pass of the process grows every time it is dispatched.
+    pub fn stride(&self) -> usize {
+        STRIDE1 / self.tickets()
+    }
+
+    /// Whether the process waits until its `wake_time`, either sleeping
+    /// or waiting for an event with a timeout.
+    fn is_sleeping(&self) -> bool {
+        match self.state() {
+            ProcessState::Waiting { event: None } => self.waiting_for.is_none(),
+            ProcessState::Waiting { event: Some(_) } => self.has_timeout,
+            _ => false,
+        }
+    }
+}
+
+impl Process for StrideProcess {
+    fn pid(&self) -> Pid {
+        self.pcb.pid()
+    }
+
+    fn state(&self) -> ProcessState {
+        self.pcb.state()
+    }
+
+    fn timings(&self) -> (usize, usize, usize) {
+        self.pcb.timings()
+    }
+
+    fn priority(&self) -> i8 {
+        self.pcb.priority()
+    }
+
+    fn extra(&self) -> String {
+        format!("tickets={} pass={}", self.tickets(), self.pass)
+    }
+
+    fn parent(&self) -> Option<Pid> {
+        self.pcb.parent()
+    }
+}
+
+/// A proportional share scheduler, the deterministic version of a
+/// lottery scheduler.
+///
+/// Every process has a number of tickets, given by its priority, and a
+/// stride inversely proportional to them. The ready process with the
+/// lowest pass, the one with the lowest PID if there are several, is
+/// dispatched for a timeslice and its pass grows by its stride, so that
+/// every process runs in proportion to its tickets.
+///
+/// A new process, or one that wakes up, starts from at least the lowest
+/// pass of the ready processes, so that it cannot take the processor for
+/// a long time to catch up.
+pub struct StrideScheduler {
+    processes: Vec<StrideProcess>,
+    running: Option<Pid>,
+    events: EventTable,
+    timeslice: NonZeroUsize,
+    /// The time left from the timeslice of the running process.
+    remaining: usize,
+    nr_processes: usize,
+    accounting: AccountingClock,
+    /// Set when the process with PID 1 exits, any process left
+    /// after that makes the scheduler panic.
+    init_exited: bool,
+}
+
+impl StrideScheduler {
+    pub fn new(timeslice: NonZeroUsize) -> Self {
+        Self {
+            processes: Vec::new(),
+            running: None,
+            events: EventTable::new(),
+            timeslice,
+            remaining: 0,
+            nr_processes: 0,
+            accounting: AccountingClock::new(),
+            init_exited: false,
+        }
+    }
+
+    fn process_index(&self, pid: Pid) -> usize {
+        self.processes
+            .iter()
+            .position(|p| p.pid() == pid)
+            .expect("Process not found in the list")
+    }
+
+    /// The lowest pass of the processes that are ready or running.
+    fn minimum_pass(&self) -> Option<usize> {
+        self.processes
+            .iter()
+            .filter(|p| matches!(p.state(), ProcessState::Ready | ProcessState::Running))
+            .map(|p| p.pass)
+            .min()
+    }
+
+    /// Charges the running process for `consumed` time units and credits
+    /// the same amount of time to every other process.
+    fn charge(&mut self, pid: Pid, consumed: usize, kind: ChargeKind) {
+        let processes = self.processes.iter_mut().map(|p| &mut p.pcb);
+        self.accounting.charge(pid, consumed, kind, processes);
+    }
+
+    /// Takes the processor from the running process and puts it in `state`.
+    fn block(&mut self, state: ProcessState) -> Option<Pid> {
+        let pid = self.running.take()?;
+        let process_index = self.process_index(pid);
+        self.processes[process_index].set_state(state);
+        Some(pid)
+    }
+
+    /// Makes a process ready, with a pass of at least `minimum`.
+    fn wake(&mut self, pid: Pid, minimum: usize) {
+        let process_index = self.process_index(pid);
+        let process = &mut self.processes[process_index];
+        process.set_state(ProcessState::Ready);
+        process.waiting_for = None;
+        process.pass = process.pass.max(minimum);
+    }
+
+    /// Makes ready the processes whose sleep has ended.
+    fn wake_sleepers(&mut self) {
+        let minimum = self.minimum_pass().unwrap_or(0);
+        let woken = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping() && p.wake_time <= self.accounting.time())
+            .map(|p| p.pid())
+            .collect::<Vec<_>>();
+        for pid in woken {
+            // a wait for an event may have timed out
+            self.events.remove(pid);
+            self.wake(pid, minimum);
+        }
+    }
+
+    /// Returns the process to run and the time to grant it. The running
+    /// process goes on if it has time left from its timeslice, otherwise
+    /// the process with the lowest pass gets a new timeslice.
+    fn pick(&self) -> Option<(Pid, usize)> {
+        if let Some(pid) = self.running.filter(|_| self.remaining > 0) {
+            return Some((pid, self.remaining));
+        }
+        // the processes whose sleep has ended start from the lowest pass
+        // of the processes that are already ready
+        let minimum = self.minimum_pass().unwrap_or(0);
+        self.processes
+            .iter()
+            .filter_map(|p| match p.state() {
+                ProcessState::Ready | ProcessState::Running => Some((p.pass, p.pid())),
+                _ if p.is_sleeping() && p.wake_time <= self.accounting.time() => {
+                    Some((p.pass.max(minimum), p.pid()))
+                }
+                _ => None,
+            })
+            .min()
+            .map(|(_, pid)| (pid, self.timeslice.get()))
+    }
+
+    /// Removes a process and wakes up the processes waiting for it to exit.
+    fn remove(&mut self, pid: Pid) {
+        if self.running == Some(pid) {
+            self.running = None;
+        }
+        self.events.remove(pid);
+        self.processes.retain(|p| p.pid() != pid);
+        if pid == 1 {
+            self.init_exited = true;
+        }
+        let waiters = self
+            .processes
+            .iter()
+            .filter(|p| p.waiting_for == Some(pid))
+            .map(|p| p.pid())
+            .collect::<Vec<_>>();
+        let minimum = self.minimum_pass().unwrap_or(0);
+        for waiter in waiters {
+            self.wake(waiter, minimum);
+        }
+    }
+}
+
+impl Processes for StrideScheduler {
+    fn processes(&self) -> impl Iterator<Item = &dyn Process> {
+        // the processes are kept sorted by PID
+        self.processes.iter().map(|p| p as &dyn Process)
+    }
+}
+
+impl Scheduler for StrideScheduler {
+    fn next(&mut self) -> SchedulingDecision {
+        if self.init_exited && !self.processes.is_empty() {
+            return SchedulingDecision::Panic;
+        }
+        self.wake_sleepers();
+
+        if let Some((pid, granted)) = self.pick() {
+            if self.running != Some(pid) || self.remaining == 0 {
+                if let Some(running) = self.running.take() {
+                    let process_index = self.process_index(running);
+                    self.processes[process_index].set_state(ProcessState::Ready);
+                }
+                let process_index = self.process_index(pid);
+                let process = &mut self.processes[process_index];
+                process.pass += process.stride();
+                process.set_state(ProcessState::Running);
+                self.running = Some(pid);
+                self.remaining = granted;
+            }
+            return SchedulingDecision::Run {
+                pid,
+                timeslice: NonZeroUsize::new(granted).unwrap_or(NonZeroUsize::MIN),
+            };
+        }
+
+        let wake_time = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping())
+            .map(|p| p.wake_time)
+            .min();
+        if let Some(wake_time) = wake_time {
+            let amount = wake_time.saturating_sub(self.accounting.time());
+            let processes = self.processes.iter_mut().map(|p| &mut p.pcb);
+            self.accounting.advance_sleep(amount, processes);
+            match NonZeroUsize::new(amount) {
+                Some(amount) => SchedulingDecision::Sleep(amount),
+                // the sleep has already ended, the process is ready right away
+                None => self.next(),
+            }
+        } else if self.processes.is_empty() {
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        if self.init_exited && !self.processes.is_empty() {
+            return SchedulingDecision::Panic;
+        }
+
+        if let Some((pid, granted)) = self.pick() {
+            return SchedulingDecision::Run {
+                pid,
+                timeslice: NonZeroUsize::new(granted).unwrap_or(NonZeroUsize::MIN),
+            };
+        }
+
+        // every sleep that has ended is handled above
+        let wake_time = self
+            .processes
+            .iter()
+            .filter(|p| p.is_sleeping())
+            .map(|p| p.wake_time)
+            .min();
+        let time = self.accounting.time();
+        if let Some(amount) = wake_time.and_then(|t| NonZeroUsize::new(t - time)) {
+            SchedulingDecision::Sleep(amount)
+        } else if self.processes.is_empty() {
+            SchedulingDecision::Done
+        } else {
+            SchedulingDecision::Deadlock
+        }
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        match reason {
+            StopReason::Syscall { syscall, remaining } => {
+                let running = self.running;
+                if let Some(pid) = running {
+                    self.charge(pid, self.remaining.saturating_sub(remaining), ChargeKind::Syscall);
+                    // a process cannot have more time left than it has been granted
+                    self.remaining = self.remaining.min(remaining);
+                } else if !matches!(syscall, Syscall::Fork(_)) {
+                    return SyscallResult::NoRunningProcess;
+                }
+                match syscall {
+                    Syscall::Fork(priority) => {
+                        let pass = self.minimum_pass().unwrap_or(0);
+                        self.nr_processes += 1;
+                        let pid = Pid::new(self.nr_processes);
+                        self.processes.push(StrideProcess::new(pid, priority, pass));
+                        SyscallResult::Pid(pid)
+                    }
+                    Syscall::Sleep(amount) | Syscall::WaitTimeout { max: amount, .. }
+                        if self.accounting.time().checked_add(amount).is_none() =>
+                    {
+                        SyscallResult::Error(SchedulerError::InvalidSleepDuration(amount))
+                    }
+                    Syscall::Sleep(0) | Syscall::Yield => {
+                        self.block(ProcessState::Ready);
+                        SyscallResult::Success
+                    }
+                    Syscall::Sleep(amount) => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: None }) {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].wake_time =
+                                self.accounting.time() + amount;
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::Wait(event) => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: Some(event) })
+                        {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].has_timeout = false;
+                            self.events.wait(pid, event);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitTimeout { event, max } => {
+                        if let Some(pid) = self.block(ProcessState::Waiting { event: Some(event) })
+                        {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].wake_time = self.accounting.time() + max;
+                            self.processes[process_index].has_timeout = true;
+                            self.events.wait(pid, event);
+                        }
+                        SyscallResult::Success
+                    }
+                    // all the processes are in group 0
+                    Syscall::Signal(event) | Syscall::SignalGroup(0, event) => {
+                        let minimum = self.minimum_pass().unwrap_or(0);
+                        for pid in self.events.signal(event) {
+                            self.wake(pid, minimum);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::SetGroup(_) | Syscall::SignalGroup(..) => SyscallResult::Success,
+                    Syscall::SetPriority(priority) => {
+                        if let Some(pid) = running {
+                            let process_index = self.process_index(pid);
+                            self.processes[process_index].pcb.set_priority(priority);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(pid) if running == Some(pid) => {
+                        SyscallResult::Error(SchedulerError::WaitForSelf)
+                    }
+                    Syscall::WaitPid(pid) if self.processes.iter().any(|p| p.pid() == pid) => {
+                        if let Some(waiter) = self.block(ProcessState::Waiting { event: None }) {
+                            let process_index = self.process_index(waiter);
+                            self.processes[process_index].waiting_for = Some(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(pid) if pid.get() <= self.nr_processes => {
+                        // the process has already exited
+                        SyscallResult::Success
+                    }
+                    Syscall::WaitPid(_) => SyscallResult::NoSuchProcess,
+                    Syscall::Kill(pid) => {
+                        if self.processes.iter().any(|p| p.pid() == pid) {
+                            self.remove(pid);
+                            SyscallResult::Success
+                        } else {
+                            SyscallResult::NoSuchProcess
+                        }
+                    }
+                    Syscall::Exit | Syscall::ExitWith(_) => {
+                        if let Some(pid) = running {
+                            self.remove(pid);
+                        }
+                        SyscallResult::Success
+                    }
+                }
+            }
+            StopReason::Expired => {
+                if let Some(pid) = self.running {
+                    self.charge(pid, self.remaining, ChargeKind::Execution);
+                    self.remaining = 0;
+                }
+                SyscallResult::Success
+            }
+        }
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.processes().collect()
+    }
+}
@@ -0,0 +1,180 @@
+use crate::scheduler::Pid;
+use alloc::{collections::BTreeMap, vec::Vec};
+
+/// What a process has been through, from its creation until it has
+/// left the scheduler, or until now if it is still there.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessStats {
+    pub pid: Pid,
+
+    /// The time at which the process has been created.
+    pub arrival: usize,
+
+    /// The time between the creation of the process and its exit, or the
+    /// time at which it has been killed, or [`None`] if it is still alive.
+    pub turnaround: Option<usize>,
+
+    /// The time during which the process has existed without running,
+    /// whether it has been ready, sleeping or waiting for an event.
+    pub waiting: usize,
+
+    /// The time between the creation of the process and its first
+    /// dispatch, or [`None`] if it has never been dispatched.
+    pub response: Option<usize>,
+
+    /// The number of times the process has been dispatched.
+    pub dispatches: usize,
+
+    /// The number of system calls issued by the process.
+    pub syscalls: usize,
+}
+
+/// The mean and the largest of a value over a number of processes,
+/// both 0 if there are no processes.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Aggregate {
+    pub mean: f64,
+    pub max: usize,
+}
+
+impl Aggregate {
+    fn of(values: impl Iterator<Item = usize>) -> Self {
+        let (count, sum, max) = values.fold((0, 0u128, 0), |(count, sum, max), value| {
+            (count + 1, sum + value as u128, max.max(value))
+        });
+        if count == 0 {
+            return Aggregate::default();
+        }
+        Aggregate {
+            mean: sum as f64 / count as f64,
+            max,
+        }
+    }
+}
+
+/// The statistics of every process of a simulation, and their aggregates.
+///
+/// The turnaround is aggregated over the processes that have left the
+/// scheduler and the response over the processes that have been
+/// dispatched, the other values over all the processes.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SimulationStats {
+    /// The statistics of the processes, sorted by PID.
+    pub processes: Vec<ProcessStats>,
+    pub turnaround: Aggregate,
+    pub waiting: Aggregate,
+    pub response: Aggregate,
+    pub dispatches: Aggregate,
+    pub syscalls: Aggregate,
+}
+
+impl SimulationStats {
+    /// Computes the aggregates of the statistics of `processes`.
+    pub fn new(mut processes: Vec<ProcessStats>) -> Self {
+        processes.sort_by_key(|p| p.pid);
+        let all = || processes.iter();
+        SimulationStats {
+            turnaround: Aggregate::of(all().filter_map(|p| p.turnaround)),
+            waiting: Aggregate::of(all().map(|p| p.waiting)),
+            response: Aggregate::of(all().filter_map(|p| p.response)),
+            dispatches: Aggregate::of(all().map(|p| p.dispatches)),
+            syscalls: Aggregate::of(all().map(|p| p.syscalls)),
+            processes,
+        }
+    }
+
+    /// Returns the statistics of the process with PID `pid`, if it
+    /// has been seen.
+    pub fn process(&self, pid: Pid) -> Option<&ProcessStats> {
+        self.processes.iter().find(|p| p.pid == pid)
+    }
+}
+
+/// What a process has done so far, kept by a [`StatsRecorder`].
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Record {
+    arrival: usize,
+    first_dispatch: Option<usize>,
+    dispatches: usize,
+    /// The time during which the process has run, system calls included.
+    ran: usize,
+    syscalls: usize,
+}
+
+impl Record {
+    /// Returns the statistics of the process at time `now`, with its
+    /// turnaround if it leaves the scheduler at that time.
+    fn stats(&self, pid: Pid, now: usize, left: bool) -> ProcessStats {
+        let existed = now.saturating_sub(self.arrival);
+        ProcessStats {
+            pid,
+            arrival: self.arrival,
+            turnaround: left.then_some(existed),
+            waiting: existed.saturating_sub(self.ran),
+            response: self.first_dispatch.map(|time| time - self.arrival),
+            dispatches: self.dispatches,
+            syscalls: self.syscalls,
+        }
+    }
+}
+
+/// Collects the statistics of the processes of a scheduler, which reports
+/// what happens to them. The processes that have left the scheduler are
+/// kept as a summary.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct StatsRecorder {
+    alive: BTreeMap<Pid, Record>,
+    left: Vec<ProcessStats>,
+}
+
+impl StatsRecorder {
+    /// Records that the process `pid` has been created at `time`.
+    pub fn arrive(&mut self, pid: Pid, time: usize) {
+        self.alive.insert(
+            pid,
+            Record {
+                arrival: time,
+                ..Record::default()
+            },
+        );
+    }
+
+    /// Records that the process `pid` has been dispatched at `time`.
+    pub fn dispatch(&mut self, pid: Pid, time: usize) {
+        if let Some(record) = self.alive.get_mut(&pid) {
+            record.first_dispatch.get_or_insert(time);
+            record.dispatches += 1;
+        }
+    }
+
+    /// Records that the process `pid` has run for `consumed` time units,
+    /// until it has issued a system call if `syscall` is set.
+    pub fn run(&mut self, pid: Pid, consumed: usize, syscall: bool) {
+        if let Some(record) = self.alive.get_mut(&pid) {
+            record.ran = record.ran.saturating_add(consumed);
+            record.syscalls += usize::from(syscall);
+        }
+    }
+
+    /// Records that the process `pid` has left the scheduler at `time`,
+    /// because it has exited or because it has been killed.
+    pub fn leave(&mut self, pid: Pid, time: usize) {
+        if let Some(record) = self.alive.remove(&pid) {
+            self.left.push(record.stats(pid, time, true));
+        }
+    }
+
+    /// Returns the statistics of every process at time `now`.
+    pub fn report(&self, now: usize) -> SimulationStats {
+        let alive = self
+            .alive
+            .iter()
+            .map(|(&pid, record)| record.stats(pid, now, false));
+        SimulationStats::new(self.left.iter().copied().chain(alive).collect())
+    }
+}
@@ -0,0 +1,552 @@
+//! A property-based harness that plays random workloads on a scheduler
+//! and checks its invariants after every call, behind the `testing`
+//! feature.
+//!
+//! The workloads are lists of [`Action`]s, what the running process does
+//! every time it is dispatched. They are played in a way that follows the
+//! protocol of the OS: the first process is forked before anything runs,
+//! the scheduler is only stopped while a process runs and the process
+//! with PID 1 only exits once it is the last one. When a workload breaks
+//! an invariant, it is shrunk to the shortest and simplest one that still
+//! breaks it, so that the [`Failure`] shows the few calls that matter.
+//!
+//! ```ignore
+//! let harness = Harness::new().cases(100);
+//! if let Err(failure) = harness.check(|| RoundRobinScheduler::new(timeslice, 1)) {
+//!     panic!("{}", failure);
+//! }
+//! ```
+
+use crate::scheduler::{
+    Pid, ProcessState, Scheduler, SchedulingDecision, StopReason, Syscall, SyscallResult,
+};
+use alloc::{vec, vec::Vec};
+use core::fmt::{self, Display};
+use core::mem;
+
+/// The number of events that the processes wait for and signal, few
+/// enough that most of the signals wake up a process.
+const EVENTS: usize = 3;
+
+/// The number of decisions in a row without a process to run after which
+/// the scheduler is considered to be stuck.
+const MAX_IDLE: usize = 100;
+
+/// What the running process does once it has been dispatched.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Op {
+    /// Runs until its timeslice expires.
+    Expire,
+
+    /// Forks a process with this priority.
+    Fork(i8),
+
+    /// Sleeps for this many time units, at least one.
+    Sleep(usize),
+
+    /// Waits for an event.
+    Wait(usize),
+
+    /// Signals an event.
+    Signal(usize),
+
+    /// Gives up the processor.
+    Yield,
+
+    /// Exits, or yields if it is the process with PID 1
+    /// and other processes are still alive.
+    Exit,
+}
+
+/// A step of a workload: the running process uses `used` time units,
+/// at least one, and then does `op`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Action {
+    pub used: usize,
+    pub op: Op,
+}
+
+impl Action {
+    pub fn new(used: usize, op: Op) -> Self {
+        Action { used, op }
+    }
+
+    /// The actions that are simpler than this one, tried in this order
+    /// when a workload is shrunk. Every one of them is closer to
+    /// yielding right away, so shrinking always ends.
+    fn simpler(&self) -> Vec<Action> {
+        let mut simpler = vec![];
+        if self.op != Op::Yield {
+            simpler.push(Action::new(self.used, Op::Yield));
+        }
+        let op = match self.op {
+            Op::Fork(priority) if priority != 0 => Some(Op::Fork(0)),
+            Op::Sleep(amount) if amount > 1 => Some(Op::Sleep(1)),
+            Op::Wait(event) if event > 0 => Some(Op::Wait(0)),
+            Op::Signal(event) if event > 0 => Some(Op::Signal(0)),
+            _ => None,
+        };
+        simpler.extend(op.map(|op| Action::new(self.used, op)));
+        if self.used > 1 {
+            simpler.push(Action::new(1, self.op));
+        }
+        simpler
+    }
+}
+
+/// A call made to the scheduler while a workload is played.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Call {
+    Next(SchedulingDecision),
+    Stop(StopReason, SyscallResult),
+}
+
+impl Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Call::Next(decision) => write!(f, "next: {}", decision),
+            Call::Stop(reason, result) => write!(f, "stop: {} -> {:?}", reason, result),
+        }
+    }
+}
+
+/// An invariant that the scheduler has broken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// A process has been scheduled but it is not listed.
+    UnknownPid(Pid),
+
+    /// After a process has been scheduled, the running processes
+    /// are not exactly that process.
+    Running { pid: Pid, running: Vec<Pid> },
+
+    /// A process has run and waited for longer than it has existed.
+    Timings {
+        pid: Pid,
+        timings: (usize, usize, usize),
+    },
+
+    /// Two listed processes have the same PID.
+    DuplicatePid(Pid),
+
+    /// The scheduler is done but these processes are listed.
+    DoneWithProcesses(Vec<Pid>),
+
+    /// The scheduler has not scheduled a process, or has not let the
+    /// processes finish, for too long.
+    Stuck,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::UnknownPid(pid) => {
+                write!(f, "process {} is scheduled but not listed", pid)
+            }
+            Violation::Running { pid, running } => write!(
+                f,
+                "process {} is scheduled but the running processes are {:?}",
+                pid, running
+            ),
+            Violation::Timings { pid, timings } => write!(
+                f,
+                "process {} has timings {:?}, more than it has existed",
+                pid, timings
+            ),
+            Violation::DuplicatePid(pid) => write!(f, "process {} is listed twice", pid),
+            Violation::DoneWithProcesses(pids) => {
+                write!(f, "the scheduler is done but lists {:?}", pids)
+            }
+            Violation::Stuck => write!(f, "the scheduler makes no progress"),
+        }
+    }
+}
+
+/// A workload that makes a scheduler break an invariant, once shrunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// The seed of the workload before it was shrunk.
+    pub seed: u64,
+
+    /// The shrunk workload.
+    pub actions: Vec<Action>,
+
+    /// The calls made to the scheduler while playing the shrunk
+    /// workload, the last one breaks the invariant.
+    pub calls: Vec<Call>,
+
+    pub violation: Violation,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} (seed {}), after the calls:",
+            self.violation, self.seed
+        )?;
+        for call in self.calls.iter() {
+            writeln!(f, "  {}", call)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Failure {}
+
+/// A xorshift generator, so that the workloads are the same on every run.
+struct Random(u64);
+
+impl Random {
+    fn new(seed: u64) -> Self {
+        // xorshift gets stuck on 0
+        Random(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number from `0` to `bound - 1`.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn action(&mut self) -> Action {
+        let op = match self.below(20) {
+            0..=3 => Op::Expire,
+            4..=7 => Op::Fork(self.below(6) as i8),
+            8..=9 => Op::Sleep(1 + self.below(4)),
+            10..=11 => Op::Wait(self.below(EVENTS)),
+            12..=16 => Op::Signal(self.below(EVENTS)),
+            17..=18 => Op::Yield,
+            _ => Op::Exit,
+        };
+        Action::new(1 + self.below(4), op)
+    }
+}
+
+/// Generates random workloads, checks a scheduler with each one and
+/// shrinks the first one that breaks an invariant.
+///
+/// The invariants, checked after every call, are that:
+/// * exactly one process, the one scheduled, is running after
+///   a [`SchedulingDecision::Run`]
+/// * the scheduler only schedules the processes that it lists
+/// * [`SchedulingDecision::Done`] is only returned when no process is listed
+/// * no process has run and waited for longer than it has existed
+/// * no two listed processes have the same PID
+/// * the scheduler does not stay without a process to run forever
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Harness {
+    cases: usize,
+    max_actions: usize,
+    seed: u64,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Harness {
+            cases: 256,
+            max_actions: 64,
+            seed: 0,
+        }
+    }
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of workloads to generate, 256 by default.
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// The largest number of actions of a workload, 64 by default.
+    pub fn max_actions(mut self, max_actions: usize) -> Self {
+        self.max_actions = max_actions.max(1);
+        self
+    }
+
+    /// The seed of the first workload, the others use the next seeds.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns the workload generated from `seed`.
+    pub fn workload(&self, seed: u64) -> Vec<Action> {
+        let mut random = Random::new(seed);
+        let len = 1 + random.below(self.max_actions);
+        (0..len).map(|_| random.action()).collect()
+    }
+
+    /// Plays the generated workloads, each one on a new scheduler made by
+    /// `make`, and returns the first one that breaks an invariant, shrunk.
+    pub fn check<S: Scheduler>(&self, mut make: impl FnMut() -> S) -> Result<(), Failure> {
+        for case in 0..self.cases as u64 {
+            let seed = self.seed.wrapping_add(case);
+            let actions = self.workload(seed);
+            if let (_, _, Err(violation)) = play(&mut make(), &actions, false) {
+                let (actions, calls, violation) = shrink(&mut make, actions, violation);
+                return Err(Failure {
+                    seed,
+                    actions,
+                    calls,
+                    violation,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Decodes a workload from any bytes, three for every action: what the
+/// process does, the parameter of what it does and the time it uses from
+/// its timeslice, which is cut to the timeslice when the action is played.
+/// The bytes left after the last full action are ignored.
+///
+/// This is meant for fuzzing, every workload decoded follows the protocol.
+pub fn decode(bytes: &[u8]) -> Vec<Action> {
+    bytes
+        .chunks_exact(3)
+        .map(|action| {
+            let parameter = action[1] as usize;
+            let op = match action[0] % 7 {
+                0 => Op::Expire,
+                1 => Op::Fork(action[1] as i8),
+                2 => Op::Sleep(1 + parameter % 16),
+                3 => Op::Wait(parameter % EVENTS),
+                4 => Op::Signal(parameter % EVENTS),
+                5 => Op::Yield,
+                _ => Op::Exit,
+            };
+            Action::new(1 + action[2] as usize, op)
+        })
+        .collect()
+}
+
+/// Plays `actions` on `scheduler` like [`Harness::check`] does, checking
+/// the same invariants, and then makes the processes left finish: the
+/// running process signals an event that a process waits for if there is
+/// one, and exits otherwise.
+///
+/// Returns the last decision of the scheduler, which is
+/// [`SchedulingDecision::Done`] unless the processes have deadlocked
+/// before the end of the workload, or the invariant broken.
+pub fn play_to_end<S: Scheduler + ?Sized>(
+    scheduler: &mut S,
+    actions: &[Action],
+) -> Result<SchedulingDecision, Violation> {
+    let (_, _, result) = play(scheduler, actions, true);
+    result
+}
+
+/// Plays `actions` on `scheduler` until they are all done, and then
+/// until the processes have finished if `finish` is set, or until the
+/// scheduler stops scheduling processes. Returns the calls made to the
+/// scheduler, the number of actions done and the last decision, or the
+/// invariant broken by the last call.
+fn play<S: Scheduler + ?Sized>(
+    scheduler: &mut S,
+    actions: &[Action],
+    finish: bool,
+) -> (Vec<Call>, usize, Result<SchedulingDecision, Violation>) {
+    let mut calls = vec![];
+    let mut done = 0;
+    let result = play_calls(scheduler, actions, finish, &mut calls, &mut done);
+    (calls, done, result)
+}
+
+fn play_calls<S: Scheduler + ?Sized>(
+    scheduler: &mut S,
+    actions: &[Action],
+    finish: bool,
+    calls: &mut Vec<Call>,
+    done: &mut usize,
+) -> Result<SchedulingDecision, Violation> {
+    let reason = StopReason::syscall(Syscall::Fork(0));
+    let result = scheduler.stop(reason);
+    calls.push(Call::Stop(reason, result));
+    check(scheduler)?;
+    let mut idle = 0;
+    // every process left needs a few actions to finish
+    let mut finishing = MAX_IDLE * (actions.len() + 1);
+    loop {
+        let decision = scheduler.next();
+        calls.push(Call::Next(decision));
+        check(scheduler)?;
+        check_decision(scheduler, decision)?;
+        let SchedulingDecision::Run { pid, timeslice } = decision else {
+            if !matches!(decision, SchedulingDecision::Sleep(_)) {
+                return Ok(decision);
+            }
+            idle += 1;
+            if idle == MAX_IDLE {
+                return Err(Violation::Stuck);
+            }
+            continue;
+        };
+        idle = 0;
+        let action = match actions.get(*done) {
+            Some(&action) => {
+                *done += 1;
+                action
+            }
+            None if finish && finishing > 0 => {
+                finishing -= 1;
+                Action::new(1, finishing_op(scheduler))
+            }
+            None if finish => return Err(Violation::Stuck),
+            None => return Ok(decision),
+        };
+        let remaining = timeslice.get().saturating_sub(action.used.max(1));
+        let syscall = match action.op {
+            Op::Expire => None,
+            Op::Fork(priority) => Some(Syscall::Fork(priority)),
+            Op::Sleep(amount) => Some(Syscall::Sleep(amount.max(1))),
+            Op::Wait(event) => Some(Syscall::Wait(event)),
+            Op::Signal(event) => Some(Syscall::Signal(event)),
+            Op::Yield => Some(Syscall::Yield),
+            Op::Exit if pid == 1 && scheduler.list().len() > 1 => Some(Syscall::Yield),
+            Op::Exit => Some(Syscall::Exit),
+        };
+        let reason = match syscall {
+            Some(syscall) => StopReason::Syscall { syscall, remaining },
+            None => StopReason::Expired,
+        };
+        let result = scheduler.stop(reason);
+        calls.push(Call::Stop(reason, result));
+        check(scheduler)?;
+    }
+}
+
+/// What the running process does once the workload is over, so that
+/// the processes finish.
+fn finishing_op<S: Scheduler + ?Sized>(scheduler: &mut S) -> Op {
+    scheduler
+        .list()
+        .iter()
+        .find_map(|p| match p.state() {
+            ProcessState::Waiting { event: Some(event) } => Some(Op::Signal(event)),
+            _ => None,
+        })
+        .unwrap_or(Op::Exit)
+}
+
+/// Checks the invariants that hold after every call.
+fn check<S: Scheduler + ?Sized>(scheduler: &mut S) -> Result<(), Violation> {
+    let mut pids = vec![];
+    for process in scheduler.list() {
+        let timings = process.timings();
+        if timings.0 < timings.1 + timings.2 {
+            return Err(Violation::Timings {
+                pid: process.pid(),
+                timings,
+            });
+        }
+        pids.push(process.pid());
+    }
+    pids.sort();
+    match pids.windows(2).find(|pair| pair[0] == pair[1]) {
+        Some(pair) => Err(Violation::DuplicatePid(pair[0])),
+        None => Ok(()),
+    }
+}
+
+/// Checks the invariants that hold after `decision`.
+fn check_decision<S: Scheduler + ?Sized>(
+    scheduler: &mut S,
+    decision: SchedulingDecision,
+) -> Result<(), Violation> {
+    let processes = scheduler.list();
+    match decision {
+        SchedulingDecision::Run { pid, .. } => {
+            if !processes.iter().any(|p| p.pid() == pid) {
+                return Err(Violation::UnknownPid(pid));
+            }
+            let running = processes
+                .iter()
+                .filter(|p| p.state() == ProcessState::Running)
+                .map(|p| p.pid())
+                .collect::<Vec<_>>();
+            if running != [pid] {
+                return Err(Violation::Running { pid, running });
+            }
+        }
+        SchedulingDecision::Done if !processes.is_empty() => {
+            return Err(Violation::DoneWithProcesses(
+                processes.iter().map(|p| p.pid()).collect(),
+            ));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Shrinks `actions`, which break `violation`, to a workload that
+/// breaks the same invariant with as few and as simple actions as can
+/// be found, and returns it with its calls and what it breaks.
+fn shrink<S: Scheduler>(
+    make: &mut impl FnMut() -> S,
+    mut actions: Vec<Action>,
+    violation: Violation,
+) -> (Vec<Action>, Vec<Call>, Violation) {
+    let kind = mem::discriminant(&violation);
+    // the failure with the smallest workload so far, without
+    // the actions that come after the broken invariant
+    let mut fails = |actions: &[Action]| match play(&mut make(), actions, false) {
+        (calls, done, Err(violation)) if mem::discriminant(&violation) == kind => {
+            Some((actions[..done].to_vec(), calls, violation))
+        }
+        _ => None,
+    };
+    let mut best = fails(&actions).expect("The workload breaks the invariant");
+    actions = best.0.clone();
+    loop {
+        let mut shrunk = false;
+        // remove chunks of actions, from halves down to single actions
+        let mut size = actions.len().div_ceil(2);
+        while size > 0 {
+            let mut start = 0;
+            while start < actions.len() {
+                let mut candidate = actions.clone();
+                candidate.drain(start..(start + size).min(actions.len()));
+                match fails(&candidate) {
+                    Some(failure) => {
+                        actions = failure.0.clone();
+                        best = failure;
+                        shrunk = true;
+                    }
+                    None => start += size,
+                }
+            }
+            size /= 2;
+        }
+        // make the actions left simpler, one at a time
+        let mut index = 0;
+        while index < actions.len() {
+            for simpler in actions[index].simpler() {
+                let mut candidate = actions.clone();
+                candidate[index] = simpler;
+                if let Some(failure) = fails(&candidate) {
+                    actions = failure.0.clone();
+                    best = failure;
+                    shrunk = true;
+                    break;
+                }
+            }
+            index += 1;
+        }
+        if !shrunk {
+            return best;
+        }
+    }
+}
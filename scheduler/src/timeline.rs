@@ -0,0 +1,193 @@
+use crate::scheduler::{
+    Pid, Process, Scheduler, SchedulingDecision, StopReason, SyscallResult, Trace,
+};
+use core::fmt::{self, Display};
+#[cfg(feature = "serde")]
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// What the processor does during a [`Segment`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Activity {
+    /// The process runs, including the system calls it issues.
+    Running(Pid),
+
+    /// No process runs, the scheduler has asked the OS to sleep.
+    Sleeping,
+}
+
+impl Display for Activity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Activity::Running(pid) => write!(f, "RUN {}", pid),
+            Activity::Sleeping => write!(f, "SLEEP"),
+        }
+    }
+}
+
+/// An interval of time during which the processor does one thing,
+/// from `start` up to, but without, `end`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Segment {
+    pub start: usize,
+    pub end: usize,
+    pub what: Activity,
+}
+
+/// The history of a scheduler as consecutive [`Segment`]s, the data
+/// of a Gantt chart.
+///
+/// The plain text rendering has one line for every segment, with
+/// the start time, the end time and the activity.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct Timeline {
+    segments: Vec<Segment>,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the segments, in chronological order.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// The time at which the last segment ends.
+    pub fn end(&self) -> usize {
+        self.segments.last().map_or(0, |segment| segment.end)
+    }
+
+    /// Returns the intervals during which processes have run, as
+    /// `(pid, start, end)` for every contiguous run, in chronological
+    /// order. The intervals when no process has run are omitted.
+    pub fn gantt(&self) -> Vec<(Pid, usize, usize)> {
+        self.segments
+            .iter()
+            .filter_map(|segment| match segment.what {
+                Activity::Running(pid) => Some((pid, segment.start, segment.end)),
+                Activity::Sleeping => None,
+            })
+            .collect()
+    }
+
+    /// Adds `duration` time units of `what` after the last segment,
+    /// extending the last segment if it has the same activity.
+    pub fn push(&mut self, what: Activity, duration: usize) {
+        if duration == 0 {
+            return;
+        }
+        let start = self.end();
+        match self.segments.last_mut() {
+            Some(last) if last.what == what => last.end += duration,
+            _ => self.segments.push(Segment {
+                start,
+                end: start + duration,
+                what,
+            }),
+        }
+    }
+
+    /// Returns the segments as a JSON array.
+    #[cfg(feature = "serde")]
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(self).expect("A timeline is always serializable")
+    }
+}
+
+impl Display for Timeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in self.segments.iter() {
+            writeln!(
+                f,
+                "{:>5} {:>5}  {}",
+                segment.start, segment.end, segment.what
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A scheduler that records the [`Timeline`] of another scheduler.
+///
+/// The running time of a process is the timeslice it has been granted
+/// by [`Scheduler::next`], without the time it reports as remaining
+/// when it stops.
+pub struct TimelineScheduler<S: Scheduler> {
+    scheduler: S,
+    timeline: Timeline,
+    /// The process that has been dispatched and the timeslice it has been granted.
+    granted: Option<(Pid, usize)>,
+}
+
+impl<S: Scheduler> TimelineScheduler<S> {
+    pub fn new(scheduler: S) -> Self {
+        Self {
+            scheduler,
+            timeline: Timeline::new(),
+            granted: None,
+        }
+    }
+
+    /// Returns what the scheduler has done so far.
+    pub fn timeline(&self) -> &Timeline {
+        &self.timeline
+    }
+
+    /// Returns the scheduler that has been recorded.
+    pub fn into_inner(self) -> S {
+        self.scheduler
+    }
+}
+
+impl<S: Scheduler> Scheduler for TimelineScheduler<S> {
+    fn next(&mut self) -> SchedulingDecision {
+        let decision = self.scheduler.next();
+        match decision {
+            SchedulingDecision::Run { pid, timeslice } => {
+                self.granted = Some((pid, timeslice.get()));
+            }
+            SchedulingDecision::Sleep(amount) => {
+                self.timeline.push(Activity::Sleeping, amount.get());
+            }
+            _ => {}
+        }
+        decision
+    }
+
+    fn peek_next(&self) -> SchedulingDecision {
+        self.scheduler.peek_next()
+    }
+
+    fn stop(&mut self, reason: StopReason) -> SyscallResult {
+        if let Some((pid, timeslice)) = self.granted.take() {
+            let remaining = match reason {
+                StopReason::Syscall { remaining, .. } => {
+                    // the process may issue more system calls before the next dispatch
+                    self.granted = Some((pid, remaining));
+                    remaining
+                }
+                StopReason::Expired => 0,
+            };
+            self.timeline
+                .push(Activity::Running(pid), timeslice.saturating_sub(remaining));
+        }
+        self.scheduler.stop(reason)
+    }
+
+    fn list(&mut self) -> Vec<&dyn Process> {
+        self.scheduler.list()
+    }
+
+    fn deadlocked(&self) -> Vec<Pid> {
+        self.scheduler.deadlocked()
+    }
+
+    fn set_trace(&mut self, trace: Trace) {
+        self.scheduler.set_trace(trace);
+    }
+}
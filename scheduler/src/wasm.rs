@@ -0,0 +1,63 @@
+//! Bindings for JavaScript, to drive the schedulers step by step from a
+//! browser. The values are passed as JSON strings and returned as JS
+//! objects, with the same shape as their serde serialization.
+//!
+//! ```js
+//! const s = new WasmScheduler("round-robin", '{"timeslice": 3}');
+//! s.step_stop('{"Syscall": {"syscall": {"Fork": 0}, "remaining": 0}}'); // {Pid: 1}
+//! s.step_next(); // {Run: {pid: 1, timeslice: 3}}
+//! s.step_stop('"Expired"'); // "Success"
+//! s.snapshot(); // [{pid: 1, state: "Ready", ...}]
+//! ```
+
+use crate::scheduler::{ProcessSnapshot, Scheduler, StopReason};
+use crate::schedulers::{make_scheduler, SchedulerConfig, SchedulerKind};
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use wasm_bindgen::prelude::*;
+
+/// Converts `value` to a JS object.
+fn to_js<T: serde::Serialize>(value: &T) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(value).map_err(|error| JsError::new(&error.to_string()))
+}
+
+/// One of the bundled schedulers, used from JavaScript.
+#[wasm_bindgen]
+pub struct WasmScheduler {
+    scheduler: Box<dyn Scheduler>,
+}
+
+#[wasm_bindgen]
+impl WasmScheduler {
+    /// Creates the scheduler named `kind`, like `"round-robin"`, with the
+    /// settings of a [`SchedulerConfig`] given as JSON.
+    #[wasm_bindgen(constructor)]
+    pub fn new(kind: &str, config_json: &str) -> Result<WasmScheduler, JsError> {
+        let kind = kind
+            .parse::<SchedulerKind>()
+            .map_err(|error| JsError::new(&error.to_string()))?;
+        let config = serde_json::from_str::<SchedulerConfig>(config_json)
+            .map_err(|error| JsError::new(&error.to_string()))?;
+        Ok(WasmScheduler {
+            scheduler: make_scheduler(kind, config),
+        })
+    }
+
+    /// Returns the next decision of the scheduler.
+    pub fn step_next(&mut self) -> Result<JsValue, JsError> {
+        to_js(&self.scheduler.next())
+    }
+
+    /// Stops the running process for the [`StopReason`] given as JSON
+    /// and returns the result.
+    pub fn step_stop(&mut self, reason_json: &str) -> Result<JsValue, JsError> {
+        let reason = serde_json::from_str::<StopReason>(reason_json)
+            .map_err(|error| JsError::new(&error.to_string()))?;
+        to_js(&self.scheduler.stop(reason))
+    }
+
+    /// Returns all the processes, sorted by PID.
+    pub fn snapshot(&mut self) -> Result<JsValue, JsError> {
+        to_js(&ProcessSnapshot::all(self.scheduler.as_mut()))
+    }
+}
@@ -0,0 +1,64 @@
+//! Runs in a JS engine with `wasm-pack test --node -- --features wasm`.
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use scheduler::wasm::WasmScheduler;
+use scheduler::{Pid, SchedulingDecision, SyscallResult};
+use std::num::NonZeroUsize;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn decision(value: JsValue) -> SchedulingDecision {
+    serde_wasm_bindgen::from_value(value).unwrap()
+}
+
+fn result(value: JsValue) -> SyscallResult {
+    serde_wasm_bindgen::from_value(value).unwrap()
+}
+
+fn run(pid: usize, timeslice: usize) -> SchedulingDecision {
+    SchedulingDecision::Run {
+        pid: Pid::new(pid),
+        timeslice: NonZeroUsize::new(timeslice).unwrap(),
+    }
+}
+
+#[wasm_bindgen_test]
+fn round_robin_forks_two_processes() {
+    let mut scheduler = WasmScheduler::new(
+        "round-robin",
+        r#"{"timeslice": 3, "minimum_remaining_timeslice": 1}"#,
+    )
+    .unwrap();
+
+    let fork = |scheduler: &mut WasmScheduler, remaining: usize| {
+        let reason = format!(
+            r#"{{"Syscall": {{"syscall": {{"Fork": 0}}, "remaining": {}}}}}"#,
+            remaining
+        );
+        result(scheduler.step_stop(&reason).unwrap())
+    };
+
+    assert_eq!(fork(&mut scheduler, 0), SyscallResult::Pid(Pid::new(1)));
+    assert_eq!(decision(scheduler.step_next().unwrap()), run(1, 3));
+    assert_eq!(fork(&mut scheduler, 2), SyscallResult::Pid(Pid::new(2)));
+    assert_eq!(decision(scheduler.step_next().unwrap()), run(1, 2));
+    assert_eq!(fork(&mut scheduler, 1), SyscallResult::Pid(Pid::new(3)));
+    assert_eq!(decision(scheduler.step_next().unwrap()), run(1, 1));
+    assert_eq!(
+        result(scheduler.step_stop(r#""Expired""#).unwrap()),
+        SyscallResult::Success
+    );
+    assert_eq!(decision(scheduler.step_next().unwrap()), run(2, 3));
+
+    let processes: Vec<serde_json::Value> =
+        serde_wasm_bindgen::from_value(scheduler.snapshot().unwrap()).unwrap();
+    assert_eq!(processes.len(), 3);
+}
+
+#[wasm_bindgen_test]
+fn invalid_arguments() {
+    assert!(WasmScheduler::new("lottery", r#"{"timeslice": 3}"#).is_err());
+    assert!(WasmScheduler::new("round-robin", r#"{"timeslice": 0}"#).is_err());
+    let mut scheduler = WasmScheduler::new("fcfs", r#"{"timeslice": 3}"#).unwrap();
+    assert!(scheduler.step_stop(r#""Interrupted""#).is_err());
+}